@@ -3,7 +3,7 @@
 set -euxo pipefail
 
 TARGETS=("thumbv6m-none-eabi" "thumbv7em-none-eabi" "thumbv7em-none-eabihf")
-FEATURES=("stm32")
+FEATURES=("stm32" "profile")
 
 for TARGET in "${TARGETS[@]}"; do
     rustup target add "$TARGET"
@@ -16,18 +16,45 @@ pub struct AccessArgs {
     pub effect: Option<Meta>,
 }
 
+impl AccessArgs {
+    fn get_effect<E: FromMeta>(&self) -> syn::Result<Option<E>> {
+        Ok(self.effect.as_ref().map(E::from_meta).transpose()?)
+    }
+}
+
+/// Side effects of reading a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub enum ReadEffect {
+    /// Reading the field clears it (rc_r).
+    Clear,
+}
+
+/// Side effects of writing a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub enum WriteEffect {
+    /// Writing ones clears the corresponding bits,
+    /// writing zeros has no effect (rc_w1).
+    OneToClear,
+    /// Writing zeros clears the corresponding bits,
+    /// writing ones has no effect (rc_w0).
+    ZeroToClear,
+    /// Writing the field triggers an action, the
+    /// field holds no state. Writing zeros has no effect.
+    Trigger,
+}
+
 #[derive(Debug, Clone)]
 pub struct Read {
     pub schema: Schema,
     pub entitlements: HashSet<Path>,
-    pub effects: (),
+    pub effect: Option<ReadEffect>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Write {
     pub schema: Schema,
     pub entitlements: HashSet<Path>,
-    pub effects: (),
+    pub effect: Option<WriteEffect>,
 }
 
 #[derive(Debug, Clone)]
@@ -93,12 +120,12 @@ impl Access {
                     read: Read {
                         schema: read_schema,
                         entitlements: get_access_entitlements(read_args)?,
-                        effects: (),
+                        effect: read_args.get_effect()?,
                     },
                     write: Write {
                         schema: write_schema,
                         entitlements: get_access_entitlements(write_args)?,
-                        effects: (),
+                        effect: write_args.get_effect()?,
                     },
                 })
             }
@@ -108,7 +135,7 @@ impl Access {
                 Some(Access::Read(Read {
                     schema: read_schema,
                     entitlements: get_access_entitlements(args)?,
-                    effects: (),
+                    effect: args.get_effect()?,
                 }))
             }
             (None, Some(args)) => {
@@ -117,7 +144,7 @@ impl Access {
                 Some(Access::Write(Write {
                     schema: write_schema,
                     entitlements: get_access_entitlements(args)?,
-                    effects: (),
+                    effect: args.get_effect()?,
                 }))
             }
             (None, None) => None,
@@ -137,4 +164,18 @@ impl Access {
             Self::Read(_) => false,
         }
     }
+
+    pub fn read_effect(&self) -> Option<ReadEffect> {
+        match self {
+            Self::Read(read) | Self::ReadWrite { read, write: _ } => read.effect,
+            Self::Write(_) => None,
+        }
+    }
+
+    pub fn write_effect(&self) -> Option<WriteEffect> {
+        match self {
+            Self::Write(write) | Self::ReadWrite { read: _, write } => write.effect,
+            Self::Read(_) => None,
+        }
+    }
 }
@@ -0,0 +1,359 @@
+use std::ops::Range;
+
+use inflector::cases::snakecase::to_snake_case;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned as _,
+    AngleBracketedGenericArguments, Expr, ExprLit, Ident, Lit, Member, Token,
+};
+
+use crate::utils::{parse_expr_range, SynErrorCombinator};
+
+/// A call of the writer of a register (i.e. `.nargs(1)`).
+struct Call {
+    method: Ident,
+    turbofish: Option<AngleBracketedGenericArguments>,
+    args: Punctuated<Expr, Token![,]>,
+}
+
+impl ToTokens for Call {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let method = &self.method;
+        let turbofish = &self.turbofish;
+        let args = &self.args;
+
+        tokens.extend(quote! { .#method #turbofish (#args) });
+    }
+}
+
+enum Operation {
+    /// Transition resolvable fields to a state
+    /// (i.e. `rcc.ahb1enr.cordicen => enabled`), or a selection
+    /// of the fields of an array (i.e. `gpioa.moder.modeX[0..8] => output`).
+    Transition { fields: Vec<Ident>, state: Ident },
+    /// Write unresolvable fields with the writer of the
+    /// register (i.e. `cordic.csr.nargs(1)`).
+    Write { calls: Vec<Call> },
+}
+
+/// An operation on a register, identified by the
+/// place holding the register (i.e. `cordic.csr`).
+struct Entry {
+    register: Expr,
+    /// The place holding the register, resolved to the
+    /// identifiers it is composed of, so entries spelling
+    /// the same place differently (i.e. with parentheses)
+    /// configure the same register.
+    place: Vec<Ident>,
+    operation: Operation,
+}
+
+impl Entry {
+    fn new(register: Expr, operation: Operation) -> syn::Result<Self> {
+        fn resolve(expr: &Expr, place: &mut Vec<Ident>) -> syn::Result<()> {
+            match expr {
+                Expr::Path(path) if path.qself.is_none() && path.path.leading_colon.is_none() => {
+                    place.extend(
+                        path.path
+                            .segments
+                            .iter()
+                            .map(|segment| segment.ident.clone()),
+                    );
+
+                    Ok(())
+                }
+                Expr::Field(field) => {
+                    resolve(&field.base, place)?;
+
+                    match &field.member {
+                        Member::Named(ident) => place.push(ident.clone()),
+                        Member::Unnamed(index) => {
+                            place.push(format_ident!("_{}", index.index, span = index.span))
+                        }
+                    }
+
+                    Ok(())
+                }
+                Expr::Paren(paren) => resolve(&paren.expr, place),
+                Expr::Group(group) => resolve(&group.expr, place),
+                _ => Err(syn::Error::new_spanned(
+                    expr,
+                    "expected a place holding a register (i.e. `cordic.csr`)",
+                )),
+            }
+        }
+
+        let mut place = Vec::new();
+
+        resolve(&register, &mut place)?;
+
+        Ok(Self {
+            register,
+            place,
+            operation,
+        })
+    }
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expr = input.parse::<Expr>()?;
+
+        if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            let state = input.parse()?;
+
+            // fields of arrays are selected by index (i.e. `modeX[3]`)
+            // or range (i.e. `modeX[0..8]`)
+            let (expr, indices) = match expr {
+                Expr::Index(index) => (*index.expr, Some(parse_indices(&index.index)?)),
+                expr => (expr, None),
+            };
+
+            let Expr::Field(field) = expr else {
+                Err(syn::Error::new_spanned(
+                    expr,
+                    "expected a path to a field (i.e. `rcc.ahb1enr.cordicen`)",
+                ))?
+            };
+
+            let Member::Named(ident) = field.member else {
+                Err(syn::Error::new_spanned(field.member, "expected a field"))?
+            };
+
+            let fields = match indices {
+                Some(indices) => {
+                    // the last `X` is replaced by the index,
+                    // as in the description of the array
+                    let name = ident.to_string();
+                    let pos = name.rfind('X').ok_or(syn::Error::new(
+                        ident.span(),
+                        "field array ident must contain an 'X' to indicate replacement location",
+                    ))?;
+
+                    indices
+                        .map(|i| {
+                            let mut name = name.clone();
+                            name.replace_range(pos..pos + 1, &i.to_string());
+
+                            Ident::new(&name, ident.span())
+                        })
+                        .collect()
+                }
+                None => vec![ident],
+            };
+
+            return Self::new(*field.base, Operation::Transition { fields, state });
+        }
+
+        // the register is the receiver of the
+        // outermost call of the writer
+        let mut calls = Vec::new();
+        let mut receiver = expr;
+
+        while let Expr::MethodCall(call) = receiver {
+            calls.push(Call {
+                method: call.method,
+                turbofish: call.turbofish,
+                args: call.args,
+            });
+
+            receiver = *call.receiver;
+        }
+
+        if calls.is_empty() {
+            Err(syn::Error::new_spanned(
+                &receiver,
+                "expected a state transition (i.e. `rcc.ahb1enr.cordicen => enabled`) or a write (i.e. `cordic.csr.nargs(1)`)",
+            ))?
+        }
+
+        calls.reverse();
+
+        Self::new(receiver, Operation::Write { calls })
+    }
+}
+
+/// The indices selecting fields of an array, either
+/// a single index or a range of indices.
+fn parse_indices(expr: &Expr) -> syn::Result<Range<u32>> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => {
+            let index = lit.base10_parse::<u32>()?;
+
+            Ok(index..index + 1)
+        }
+        Expr::Range(range) => {
+            let indices = parse_expr_range(range)?;
+
+            if indices.is_empty() {
+                Err(syn::Error::new_spanned(
+                    range,
+                    "range of indices must not be empty",
+                ))?
+            }
+
+            Ok(indices)
+        }
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "expected an index (i.e. `[3]`) or a range of indices (i.e. `[0..8]`)",
+        )),
+    }
+}
+
+pub struct Configure {
+    entries: Vec<Entry>,
+}
+
+impl Parse for Configure {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let entries = Punctuated::<Entry, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        if entries.is_empty() {
+            Err(syn::Error::new(
+                Span::call_site(),
+                "at least one register must be configured",
+            ))?
+        }
+
+        // a field transitioned more than once would silently
+        // hold only the last state listed
+        let mut errors = SynErrorCombinator::new();
+        let mut transitioned = Vec::<(&[Ident], &Ident)>::new();
+
+        for entry in &entries {
+            let Operation::Transition { fields, .. } = &entry.operation else {
+                continue;
+            };
+
+            for field in fields {
+                if transitioned
+                    .iter()
+                    .any(|(place, other)| *place == entry.place.as_slice() && *other == field)
+                {
+                    errors.push(syn::Error::new(
+                        field.span(),
+                        format!("field `{field}` is transitioned more than once"),
+                    ));
+                } else {
+                    transitioned.push((&entry.place, field));
+                }
+            }
+        }
+
+        errors.coalesce()?;
+
+        Ok(Self { entries })
+    }
+}
+
+impl ToTokens for Configure {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        // each register is configured exactly once, regardless
+        // of how many of its fields are listed
+        let mut registers = Vec::<(&[Ident], &Expr, Vec<&Operation>)>::new();
+
+        for entry in &self.entries {
+            match registers
+                .iter_mut()
+                .find(|(place, ..)| *place == entry.place.as_slice())
+            {
+                Some((.., operations)) => operations.push(&entry.operation),
+                None => registers.push((&entry.place, &entry.register, vec![&entry.operation])),
+            }
+        }
+
+        let builder = Ident::new("b", Span::mixed_site());
+        let writer = Ident::new("w", Span::mixed_site());
+        let depth = Ident::new("depth", Span::mixed_site());
+
+        let idents = (0..registers.len())
+            .map(|index| format_ident!("register{}", index, span = Span::mixed_site()))
+            .collect::<Vec<_>>();
+        let depth_idents = (0..registers.len())
+            .map(|index| format_ident!("depth{}", index, span = Span::mixed_site()))
+            .collect::<Vec<_>>();
+        let configuration_idents = (0..registers.len())
+            .map(|index| format_ident!("configuration{}", index, span = Span::mixed_site()))
+            .collect::<Vec<_>>();
+
+        let mut configurations = Vec::new();
+
+        for ((_, register, operations), ident) in registers.iter().zip(&idents) {
+            let mut transitions = Vec::new();
+            let mut calls = Vec::new();
+
+            for operation in operations {
+                match operation {
+                    Operation::Transition { fields, state } => {
+                        let accessor = Ident::new(&to_snake_case(&state.to_string()), state.span());
+
+                        transitions
+                            .extend(fields.iter().map(|field| quote! { .#field().#accessor() }));
+                    }
+                    Operation::Write { calls: write_calls } => calls.extend(write_calls),
+                }
+            }
+
+            // states are transitioned through the state builder so
+            // the register holds the new states, other fields are
+            // written in the same access
+            configurations.push(match (transitions.is_empty(), calls.is_empty()) {
+                (false, true) => quote_spanned! { register.span() =>
+                    #register.transition(|#builder| #builder #(#transitions)*)
+                },
+                (false, false) => quote_spanned! { register.span() =>
+                    #register.build_state() #(#transitions)* .finish_with(|#writer| #writer #(#calls)*)
+                },
+                (true, _) => quote_spanned! { register.span() =>
+                    {
+                        let #ident = #register;
+
+                        #ident.modify(|_, #writer| #writer #(#calls)*);
+
+                        #ident
+                    }
+                },
+            });
+        }
+
+        let registers = registers.iter().map(|(_, register, _)| register);
+
+        // registers providing entitlements are configured before the
+        // registers depending on them, otherwise in order of first
+        // appearance
+        tokens.extend(quote! {
+            {
+                #(
+                    let #depth_idents = ::proto_hal::macro_utils::depth_of(&#registers);
+                )*
+
+                #(
+                    let mut #configuration_idents = ::core::option::Option::Some(move || #configurations);
+                    let mut #idents = ::core::option::Option::None;
+                )*
+
+                for #depth in 0..=[#(#depth_idents,)*].into_iter().max().unwrap_or(0) {
+                    #(
+                        if #depth_idents == #depth {
+                            // each configuration is performed exactly once
+                            #idents = #configuration_idents.take().map(|f| f());
+
+                            ::core::sync::atomic::fence(::core::sync::atomic::Ordering::SeqCst);
+                        }
+                    )*
+                }
+
+                (#(#idents.unwrap(),)*)
+            }
+        });
+    }
+}
@@ -0,0 +1,140 @@
+use darling::FromMeta;
+use quote::ToTokens as _;
+use syn::{parse::Parser as _, punctuated::Punctuated, Attribute, Expr, Ident, Item, Meta, Token};
+
+/// The name of the argument restricting an element
+/// to a subset of the devices in a family.
+const ONLY_ON: &str = "only_on";
+
+/// The devices of a family an element is present on.
+#[derive(Debug, Clone)]
+pub struct Devices {
+    pub elems: Vec<Ident>,
+}
+
+impl FromMeta for Devices {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        Ok(Self {
+            elems: parse_devices(expr)?,
+        })
+    }
+}
+
+/// Determine whether an element restricted to the provided
+/// devices is present on the target device.
+///
+/// Without a target device, all elements are present.
+pub fn is_present_on(device: Option<&Ident>, only_on: Option<&[Ident]>) -> bool {
+    match (device, only_on) {
+        (Some(device), Some(devices)) => devices.contains(device),
+        _ => true,
+    }
+}
+
+/// Remove all items not present on the target device, stripping
+/// the `only_on` arguments from the attributes of the remaining
+/// items (recursively).
+pub fn filter_items(device: Option<&Ident>, items: &[Item]) -> syn::Result<Vec<Item>> {
+    let mut filtered = Vec::new();
+
+    for item in items {
+        let mut item = item.clone();
+
+        let attrs = match &mut item {
+            Item::Mod(module) => &mut module.attrs,
+            Item::Struct(s) => &mut s.attrs,
+            _ => {
+                filtered.push(item);
+                continue;
+            }
+        };
+
+        if !is_present_on(device, take_only_on(attrs)?.as_deref()) {
+            continue;
+        }
+
+        if let Item::Mod(module) = &mut item {
+            if let Some((_, items)) = &mut module.content {
+                *items = filter_items(device, items)?;
+            }
+        }
+
+        filtered.push(item);
+    }
+
+    Ok(filtered)
+}
+
+/// Remove the `only_on` argument from the provided attributes,
+/// returning the devices it names.
+fn take_only_on(attrs: &mut [Attribute]) -> syn::Result<Option<Vec<Ident>>> {
+    let mut found = None;
+
+    for attr in attrs {
+        let Meta::List(list) = &mut attr.meta else {
+            continue;
+        };
+
+        // attributes which are not lists of metas are not ours to filter
+        let Ok(metas) = Punctuated::<Meta, Token![,]>::parse_terminated.parse2(list.tokens.clone())
+        else {
+            continue;
+        };
+
+        let mut retained = Punctuated::<Meta, Token![,]>::new();
+
+        for meta in metas {
+            let Meta::NameValue(name_value) = &meta else {
+                retained.push(meta);
+                continue;
+            };
+
+            if !name_value.path.is_ident(ONLY_ON) {
+                retained.push(meta);
+                continue;
+            }
+
+            if found.is_some() {
+                Err(syn::Error::new_spanned(
+                    &meta,
+                    "devices can only be specified once per item",
+                ))?
+            }
+
+            found.replace(parse_devices(&name_value.value)?);
+        }
+
+        list.tokens = retained.into_token_stream();
+    }
+
+    Ok(found)
+}
+
+/// Parse an array of device identifiers (i.e. `[G474, G484]`).
+fn parse_devices(expr: &Expr) -> syn::Result<Vec<Ident>> {
+    let Expr::Array(array) = expr else {
+        Err(syn::Error::new_spanned(expr, "expected array of devices"))?
+    };
+
+    if array.elems.is_empty() {
+        Err(syn::Error::new_spanned(
+            array,
+            "at least one device must be specified",
+        ))?
+    }
+
+    array
+        .elems
+        .iter()
+        .map(|expr| {
+            if let Expr::Path(path) = expr {
+                path.path
+                    .get_ident()
+                    .cloned()
+                    .ok_or(syn::Error::new_spanned(path, "expected device identifier"))
+            } else {
+                Err(syn::Error::new_spanned(expr, "expected device identifier"))
+            }
+        })
+        .collect()
+}
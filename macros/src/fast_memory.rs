@@ -0,0 +1,113 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, ItemFn,
+};
+
+/// The section functions marked without a memory are placed in,
+/// which is collected into the first memory described.
+const DEFAULT_SECTION: &str = ".ram_func";
+
+/// The fast memory a function is placed in (if named).
+pub struct RamFuncArgs {
+    memory: Option<Ident>,
+}
+
+impl Parse for RamFuncArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let memory = if input.is_empty() {
+            None
+        } else {
+            Some(input.parse()?)
+        };
+
+        if !input.is_empty() {
+            Err(input.error("expected the name of a fast memory (i.e. `ccm`)"))?
+        }
+
+        Ok(Self { memory })
+    }
+}
+
+/// A function placed in a fast memory.
+pub struct RamFunc {
+    args: RamFuncArgs,
+    item: ItemFn,
+}
+
+impl RamFunc {
+    pub fn new(args: RamFuncArgs, item: ItemFn) -> syn::Result<Self> {
+        if item.sig.constness.is_some() {
+            Err(syn::Error::new_spanned(
+                item.sig.constness,
+                "const functions cannot be placed in a fast memory",
+            ))?
+        }
+
+        Ok(Self { args, item })
+    }
+}
+
+impl ToTokens for RamFunc {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let item = &self.item;
+
+        // each function gets its own section so the linker
+        // can still discard unused functions
+        let section = format!(
+            "{}.{}",
+            self.args
+                .memory
+                .as_ref()
+                .map(|memory| format!(".{memory}"))
+                .unwrap_or(DEFAULT_SECTION.to_string()),
+            item.sig.ident
+        );
+
+        tokens.extend(quote! {
+            #[link_section = #section]
+            #[inline(never)]
+            #item
+        });
+    }
+}
+
+/// The fast memory to be initialized.
+pub struct InitFastMemory {
+    memory: Ident,
+}
+
+impl Parse for InitFastMemory {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            memory: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for InitFastMemory {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        // symbols are defined by the linker script fragment
+        // generated by `proto-hal-build`
+        let start = format_ident!("__s{}", self.memory, span = Span::mixed_site());
+        let end = format_ident!("__e{}", self.memory, span = Span::mixed_site());
+        let load = format_ident!("__si{}", self.memory, span = Span::mixed_site());
+
+        tokens.extend(quote! {
+            {
+                extern "C" {
+                    static mut #start: u32;
+                    static mut #end: u32;
+                    static #load: u32;
+                }
+
+                ::proto_hal::memory::init_section(
+                    ::core::ptr::addr_of_mut!(#start),
+                    ::core::ptr::addr_of_mut!(#end),
+                    ::core::ptr::addr_of!(#load),
+                )
+            }
+        });
+    }
+}
@@ -1,16 +1,23 @@
+use configure::Configure;
 use darling::{ast::NestedMeta, FromMeta};
+use fast_memory::{InitFastMemory, RamFunc, RamFuncArgs};
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
+use read::Read;
 use structures::{
     block::{Block, BlockArgs, BlockSpec},
-    interrupts::InterruptsSpec,
+    interrupts::{InterruptsArgs, InterruptsSpec},
     Args,
 };
-use syn::{parse2, ItemEnum, ItemMod};
-use tiva::Validate;
+use syn::{parse2, ItemEnum, ItemFn, ItemMod};
+use utils::Validate;
 
 mod access;
+mod configure;
+mod device;
+mod fast_memory;
+mod read;
 mod structures;
 mod utils;
 
@@ -20,6 +27,10 @@ fn block_inner(args: TokenStream, item: TokenStream) -> Result<TokenStream2, syn
 
     let module = parse2::<ItemMod>(item.into())?;
 
+    if !block_args.is_present() {
+        return Ok(quote! {});
+    }
+
     let block: Block = BlockSpec::parse(
         module.ident.clone(),
         module.vis.clone(),
@@ -33,6 +44,25 @@ fn block_inner(args: TokenStream, item: TokenStream) -> Result<TokenStream2, syn
     })
 }
 
+/// Define a block of registers.
+///
+/// Each block expands independently of all others, so
+/// devices with many peripherals can define each block
+/// in its own source file.
+///
+/// For the same reason, HALs compose across crates: a board
+/// crate can re-export the blocks of a device crate alongside
+/// blocks of its own (e.g. `dynamic` blocks for external
+/// memory-mapped devices), omitting those it does not use.
+///
+/// Dynamic blocks (`#[block(dynamic)]`) are located at runtime
+/// with `Reset::new_at`. With `instances = N`, the block is
+/// expanded once per instance (`instance0`, `instance1`, ...),
+/// so each instance is located independently.
+///
+/// Blocks can be gated behind cargo features by placing
+/// `#[cfg(feature = "...")]` *before* the `#[block]`
+/// attribute, so disabled blocks are not expanded at all.
 #[proc_macro_attribute]
 pub fn block(args: TokenStream, item: TokenStream) -> TokenStream {
     match block_inner(args, item) {
@@ -42,10 +72,12 @@ pub fn block(args: TokenStream, item: TokenStream) -> TokenStream {
     .into()
 }
 
-fn interrupts_inner(_args: TokenStream, item: TokenStream) -> Result<TokenStream2, syn::Error> {
+fn interrupts_inner(args: TokenStream, item: TokenStream) -> Result<TokenStream2, syn::Error> {
+    let interrupts_args = InterruptsArgs::from_list(&NestedMeta::parse_meta_list(args.into())?)?;
+
     let e = parse2::<ItemEnum>(item.into())?;
 
-    let interrupts = InterruptsSpec::parse(&e)?;
+    let interrupts = InterruptsSpec::parse(interrupts_args, &e)?;
 
     Ok(quote! {
         #interrupts
@@ -54,6 +86,10 @@ fn interrupts_inner(_args: TokenStream, item: TokenStream) -> Result<TokenStream
 
 /// Define the interrupt vector table
 /// in accordance to the `cortex-m-rt` spec.
+///
+/// The runtime crate providing the `interrupt` attribute
+/// can be changed for other architectures with
+/// `#[interrupts(runtime = ::riscv_rt)]`.
 #[proc_macro_attribute]
 pub fn interrupts(args: TokenStream, item: TokenStream) -> TokenStream {
     match interrupts_inner(args, item) {
@@ -62,3 +98,134 @@ pub fn interrupts(args: TokenStream, item: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// Read fields of one or more registers, performing exactly
+/// one read per register.
+///
+/// The field values are yielded as a tuple, in the order listed,
+/// or populate the members of a user-defined struct:
+///
+/// ```ignore
+/// struct Status {
+///     ready: bool,
+///     count: u8,
+/// }
+///
+/// let status = unsafe {
+///     proto_hal::read! {
+///         into Status {
+///             ready: cordic::csr::rrdy,
+///             count: foo::bar::cnt,
+///         }
+///     }
+/// };
+/// ```
+///
+/// # Safety
+///
+/// See the `read` function of each register.
+#[proc_macro]
+pub fn read(item: TokenStream) -> TokenStream {
+    match parse2::<Read>(item.into()) {
+        Ok(read) => quote! { #read },
+        Err(e) => e.to_compile_error(),
+    }
+    .into()
+}
+
+/// Configure fields of one or more registers, performing
+/// exactly one access per register.
+///
+/// Fields listed with `=>` transition to the provided state
+/// through the state builder of the register, so the register
+/// holds the new state. Fields of an array are selected by
+/// index or range (i.e. `gpioa.moder.modeX[0..8] => Output`),
+/// replacing the `X` of the array with each index. Each field
+/// may be transitioned at most once, and resolvable fields which
+/// are not listed keep the state held by the register. Other
+/// fields are written through the writer of the register in the
+/// same access. Registers without transitions listed are modified,
+/// preserving all fields not listed.
+///
+/// Registers providing entitlements to the fields (or blocks) of
+/// others are configured first, followed by those depending on them,
+/// with a memory fence after each. Registers which do not depend on
+/// each other are configured in the order they first appear.
+/// The registers are yielded as a tuple, in order of first appearance:
+///
+/// ```ignore
+/// let (ahb1enr, csr) = proto_hal::configure! {
+///     rcc.ahb1enr.cordicen => Enabled,
+///     cordic.csr.func => Sqrt,
+///     cordic.csr.precision => P60,
+///     cordic.csr.nargs(1),
+/// };
+/// ```
+#[proc_macro]
+pub fn configure(item: TokenStream) -> TokenStream {
+    match parse2::<Configure>(item.into()) {
+        Ok(configure) => quote! { #configure },
+        Err(e) => e.to_compile_error(),
+    }
+    .into()
+}
+
+fn ram_func_inner(args: TokenStream, item: TokenStream) -> Result<TokenStream2, syn::Error> {
+    let args = parse2::<RamFuncArgs>(args.into())?;
+    let item = parse2::<ItemFn>(item.into())?;
+
+    let ram_func = RamFunc::new(args, item)?;
+
+    Ok(quote! {
+        #ram_func
+    })
+}
+
+/// Place a function in a fast memory (i.e. CCM, ITCM)
+/// described to `proto_hal_build::memory`.
+///
+/// The memory is named by the argument (i.e. `#[ram_func(ccm)]`),
+/// otherwise the function is placed in the first memory described.
+///
+/// ```ignore
+/// #[proto_hal::ram_func(ccm)]
+/// fn filter(samples: &mut [i16]) {
+///     // ...
+/// }
+/// ```
+///
+/// *Note: The function is loaded from flash, so the memory must
+/// be initialized with `init_fast_memory!` before it is called.*
+#[proc_macro_attribute]
+pub fn ram_func(args: TokenStream, item: TokenStream) -> TokenStream {
+    match ram_func_inner(args, item) {
+        Ok(tokens) => tokens,
+        Err(e) => e.to_compile_error(),
+    }
+    .into()
+}
+
+/// Copy the functions placed in a fast memory from flash
+/// into the memory.
+///
+/// ```ignore
+/// #[entry]
+/// fn main() -> ! {
+///     unsafe { proto_hal::init_fast_memory!(ccm) };
+///
+///     // ...
+/// }
+/// ```
+///
+/// # Safety
+///
+/// Must be called before any function placed in the memory,
+/// and not while any such function is executing.
+#[proc_macro]
+pub fn init_fast_memory(item: TokenStream) -> TokenStream {
+    match parse2::<InitFastMemory>(item.into()) {
+        Ok(init) => quote! { #init },
+        Err(e) => e.to_compile_error(),
+    }
+    .into()
+}
@@ -0,0 +1,201 @@
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned as _,
+    Ident, Path, Token,
+};
+
+mod kw {
+    syn::custom_keyword!(into);
+}
+
+/// A field to be read, identified by the path to its module
+/// (i.e. `cordic::csr::rrdy`).
+struct FieldPath {
+    register: Path,
+    field: Ident,
+}
+
+impl Parse for FieldPath {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut register = input.call(Path::parse_mod_style)?;
+
+        if register.segments.len() < 2 {
+            Err(syn::Error::new_spanned(
+                &register,
+                "expected a path to a field (i.e. `cordic::csr::rrdy`)",
+            ))?
+        }
+
+        let field = register.segments.pop().unwrap().into_value().ident;
+
+        // remove the trailing separator left by the field
+        let register = Path {
+            leading_colon: register.leading_colon,
+            segments: register
+                .segments
+                .into_pairs()
+                .map(|pair| pair.into_value())
+                .collect(),
+        };
+
+        Ok(Self { register, field })
+    }
+}
+
+/// A struct member populated by a field.
+struct Member {
+    ident: Ident,
+    path: FieldPath,
+}
+
+impl Parse for Member {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+
+        Ok(Self {
+            ident,
+            path: input.parse()?,
+        })
+    }
+}
+
+enum Output {
+    /// Yield a tuple of the field values, in the order listed.
+    Tuple(Vec<FieldPath>),
+    /// Populate the members of a user-defined struct.
+    Struct { ty: Path, members: Vec<Member> },
+}
+
+pub struct Read {
+    output: Output,
+}
+
+impl Parse for Read {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let output = if input.peek(kw::into) {
+            input.parse::<kw::into>()?;
+            let ty = input.call(Path::parse_mod_style)?;
+
+            let content;
+            braced!(content in input);
+
+            let members = Punctuated::<Member, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect::<Vec<_>>();
+
+            input.parse::<Option<Token![,]>>()?;
+
+            Output::Struct { ty, members }
+        } else {
+            Output::Tuple(
+                Punctuated::<FieldPath, Token![,]>::parse_terminated(input)?
+                    .into_iter()
+                    .collect(),
+            )
+        };
+
+        if !input.is_empty() {
+            Err(input.error("unexpected tokens after the fields to read"))?
+        }
+
+        if output.paths().is_empty() {
+            Err(syn::Error::new(
+                Span::call_site(),
+                "at least one field must be read",
+            ))?
+        }
+
+        Ok(Self { output })
+    }
+}
+
+impl Output {
+    fn paths(&self) -> Vec<&FieldPath> {
+        match self {
+            Output::Tuple(paths) => paths.iter().collect(),
+            Output::Struct { members, .. } => members.iter().map(|member| &member.path).collect(),
+        }
+    }
+}
+
+impl ToTokens for Read {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        // each register is read exactly once, in order of
+        // first appearance, regardless of how many of its
+        // fields are listed
+        let mut registers = Vec::<&Path>::new();
+
+        let indices = self
+            .output
+            .paths()
+            .into_iter()
+            .map(|path| {
+                let key = path.register.to_token_stream().to_string();
+
+                registers
+                    .iter()
+                    .position(|register| register.to_token_stream().to_string() == key)
+                    .unwrap_or_else(|| {
+                        registers.push(&path.register);
+
+                        registers.len() - 1
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let reads = registers.iter().enumerate().map(|(index, register)| {
+            let reader = format_ident!("reader{}", index, span = Span::mixed_site());
+
+            quote_spanned! { register.span() =>
+                let #reader = #register::read();
+            }
+        });
+
+        let values = self
+            .output
+            .paths()
+            .into_iter()
+            .zip(indices)
+            .map(|(path, index)| {
+                let field = &path.field;
+                let reader = format_ident!(
+                    "reader{}",
+                    index,
+                    span = field.span().resolved_at(Span::mixed_site())
+                );
+
+                quote_spanned! { field.span() =>
+                    #reader.#field()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let output = match &self.output {
+            Output::Tuple(..) => quote! { (#(#values,)*) },
+            Output::Struct { ty, members } => {
+                let idents = members.iter().map(|member| &member.ident);
+
+                quote! {
+                    #ty {
+                        #(
+                            #idents: #values,
+                        )*
+                    }
+                }
+            }
+        };
+
+        tokens.extend(quote! {
+            {
+                #(#reads)*
+
+                #output
+            }
+        });
+    }
+}
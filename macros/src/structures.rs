@@ -1,3 +1,5 @@
+//! The representation of a device description.
+
 use darling::FromMeta;
 use proc_macro2::Span;
 use syn::{spanned::Spanned as _, Attribute};
@@ -5,11 +7,16 @@ use syn::{spanned::Spanned as _, Attribute};
 use crate::utils::Spanned;
 
 pub mod block;
+pub mod cluster;
+pub mod embedded_hal;
+pub mod entitlement_group;
+pub mod fact;
 pub mod field;
 pub mod field_array;
 pub mod interrupts;
 pub mod register;
 pub mod schema;
+pub mod trigger;
 pub mod variant;
 pub mod variant_array;
 
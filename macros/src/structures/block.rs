@@ -4,43 +4,133 @@ use std::{
 };
 
 use darling::FromMeta;
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote_spanned, ToTokens};
 use syn::{parse_quote, Ident, Item, Path, Visibility};
-use tiva::Validator;
 
-use crate::utils::{extract_items_from, require_module, PathArray, Spanned, SynErrorCombinator};
+use crate::{
+    access::Access,
+    device::{self, Devices},
+    utils::{
+        extract_items_from, require_module, sorted_paths, AllowArgs, PathArray, RegisterOffset,
+        Spanned, SynErrorCombinator, Validator,
+    },
+};
 
 use super::{
-    register::{Register, RegisterArgs, RegisterSpec},
-    schema::{Schema, SchemaArgs, SchemaSpec},
+    cluster::ClusterArgs,
+    embedded_hal::EmbeddedHalArgs,
+    entitlement_group::{EntitlementGroupArgs, EntitlementGroups},
+    fact::{Fact, FactArgs},
+    field::Field,
+    register::{
+        validate_bank, AtomicStrategy, Register, RegisterArgs, RegisterSpec, ReservedPolicy,
+    },
+    schema::{Numericity, Schema, SchemaArgs, SchemaSpec},
+    trigger::{Trigger, TriggerArgs},
     Args,
 };
 
+/// The Cortex-M peripheral bit-band region.
+const BIT_BAND_REGION: std::ops::Range<u32> = 0x4000_0000..0x4010_0000;
+
+/// The non-secure alias of a block on devices
+/// with TrustZone (ARMv8-M).
 #[derive(Debug, Clone, Default, FromMeta)]
 #[darling(default)]
-pub struct BlockArgs {
+pub struct NonSecureArgs {
+    /// The base address of the non-secure alias.
+    pub base_addr: u32,
+    /// The (secure) states attributing the block as non-secure.
+    pub entitlements: PathArray,
+}
+
+/// An alternate address of a block, at which the block is
+/// mapped while a field is in a state (i.e. SYSCFG remap bits).
+#[derive(Debug, Clone, FromMeta)]
+pub struct RemapArgs {
+    /// The name of the mapping (i.e. `sram` for `remap_sram`).
+    pub name: Ident,
     pub base_addr: u32,
+    /// The state mapping the block to this address.
+    pub state: Path,
+}
+
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct BlockArgs {
+    pub base_addr: Option<u32>,
+    pub non_secure: Option<NonSecureArgs>,
+    #[darling(multiple)]
+    pub remap: Vec<RemapArgs>,
+    /// The state mapping the block to its base address,
+    /// required when the block can be remapped.
+    pub default_mapping: Option<Path>,
     pub entitlements: PathArray,
+    /// The state enabling the power domain the block resides in
+    /// (i.e. `super::pwr::cr::d2en::Enabled`), which must be
+    /// reachable, and is held by the block while attached.
+    pub power_domain: Option<Path>,
+    pub plugins: PathArray,
+    pub embedded_hal: Option<EmbeddedHalArgs>,
+    pub device: Option<Ident>,
+    pub only_on: Option<Devices>,
 
     #[darling(default)]
     pub auto_increment: bool,
     #[darling(default)]
+    pub dynamic: bool,
+    /// The number of instances of a dynamic block, each
+    /// located independently at runtime.
+    pub instances: Option<usize>,
+    #[darling(default)]
     pub erase_mod: bool,
+    #[darling(default)]
+    pub debug: bool,
+    #[darling(default)]
+    pub defmt: bool,
+    /// Warn of writable registers with reserved bits
+    /// but no reserved bit policy.
+    #[darling(default)]
+    pub warn_reserved: bool,
+    /// Warn of variants which can never be entered.
+    #[darling(default)]
+    pub warn_unreachable: bool,
 }
 
 impl Args for BlockArgs {
     const NAME: &str = "block";
 }
 
+impl BlockArgs {
+    /// Whether the block is present on the target device.
+    pub fn is_present(&self) -> bool {
+        device::is_present_on(
+            self.device.as_ref(),
+            self.only_on
+                .as_ref()
+                .map(|devices| devices.elems.as_slice()),
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct BlockSpec {
     pub args: Spanned<BlockArgs>,
     pub ident: Ident,
-    pub base_addr: u32,
-    pub entitlements: HashSet<Path>,
+    pub base_addr: Option<u32>,
+    /// The offset of this block relative to its parent (if it is a cluster).
+    pub offset: Option<RegisterOffset>,
+    /// The validation checks suppressed for this block (if it is a cluster).
+    pub allow: AllowArgs,
+    /// The entitlements of this block, in declaration order.
+    pub entitlements: Vec<Path>,
     pub registers: Vec<Register>,
+    pub clusters: Vec<Block>,
+    pub facts: Vec<Fact>,
+    pub triggers: Vec<Trigger>,
     pub schemas: HashMap<Ident, Schema>,
+    pub entitlement_groups: EntitlementGroups,
 
     pub vis: Visibility,
 }
@@ -64,41 +154,167 @@ impl BlockSpec {
         vis: Visibility,
         args: Spanned<BlockArgs>,
         items: impl Iterator<Item = &'a Item>,
+    ) -> syn::Result<Self> {
+        Self::parse_with(
+            ident,
+            vis,
+            args,
+            None,
+            HashMap::new(),
+            EntitlementGroups::default(),
+            items,
+        )
+    }
+
+    /// Parse a cluster of registers nested within this block.
+    ///
+    /// Clusters are blocks whose address is relative to the parent
+    /// block. Schemas and entitlement groups of the parent block are
+    /// visible to the cluster.
+    fn parse_cluster<'a>(
+        &self,
+        ident: Ident,
+        vis: Visibility,
+        args: Spanned<ClusterArgs>,
+        offset: RegisterOffset,
+        items: impl Iterator<Item = &'a Item>,
+    ) -> syn::Result<Self> {
+        let block_args = BlockArgs {
+            base_addr: None,
+            // clusters share the alias and mappings of the parent block
+            non_secure: None,
+            remap: Vec::new(),
+            default_mapping: None,
+            entitlements: args.entitlements.clone(),
+            // clusters reside within the power domain of the parent block
+            power_domain: None,
+            plugins: PathArray::default(),
+            embedded_hal: None,
+            device: self.args.device.clone(),
+            only_on: None,
+            auto_increment: args.auto_increment,
+            dynamic: false,
+            instances: None,
+            erase_mod: false,
+            debug: self.args.debug,
+            defmt: self.args.defmt,
+            warn_reserved: self.args.warn_reserved,
+            warn_unreachable: self.args.warn_unreachable,
+        }
+        .with_span(args.span());
+
+        Ok(Self {
+            allow: args.allow,
+            ..Self::parse_with(
+                ident,
+                vis,
+                block_args,
+                Some(offset),
+                self.schemas.clone(),
+                self.entitlement_groups.inherited(),
+                items,
+            )?
+        })
+    }
+
+    fn parse_with<'a>(
+        ident: Ident,
+        vis: Visibility,
+        args: Spanned<BlockArgs>,
+        offset: Option<RegisterOffset>,
+        schemas: HashMap<Ident, Schema>,
+        mut entitlement_groups: EntitlementGroups,
+        items: impl Iterator<Item = &'a Item>,
     ) -> syn::Result<Self> {
         let mut errors = SynErrorCombinator::new();
 
+        let items =
+            device::filter_items(args.device.as_ref(), &items.cloned().collect::<Vec<_>>())?;
+        entitlement_groups.extend_from(items.iter())?;
+        let items = entitlement_groups.expand_items(&items)?;
+
         let mut block = Self {
             args: args.clone(),
             ident,
             base_addr: args.base_addr,
-            entitlements: HashSet::new(),
+            offset,
+            allow: AllowArgs::default(),
+            entitlements: Vec::new(),
             registers: Vec::new(),
-            schemas: HashMap::new(),
+            clusters: Vec::new(),
+            facts: Vec::new(),
+            triggers: Vec::new(),
+            schemas,
+            entitlement_groups,
             vis,
         };
 
-        for entitlement in &args.entitlements.elems {
-            if !block.entitlements.insert(entitlement.clone()) {
+        for entitlement in &block
+            .entitlement_groups
+            .expand(args.entitlements.elems.iter().cloned())
+        {
+            if block.entitlements.contains(entitlement) {
                 errors.push(syn::Error::new_spanned(
                     entitlement,
                     "entitlement exists already",
                 ));
+            } else {
+                block.entitlements.push(entitlement.clone());
+            }
+        }
+
+        // the power domain must be enabled to use the block,
+        // so the domain state is an entitlement of the block
+        if let Some(power_domain) = &args.power_domain {
+            if block.entitlements.contains(power_domain) {
+                errors.push(syn::Error::new_spanned(
+                    power_domain,
+                    "power domain is already an entitlement",
+                ));
+            } else {
+                block.entitlements.push(power_domain.clone());
             }
         }
 
         let mut register_offset = 0u32;
 
-        for item in items {
+        for item in &items {
             let module = require_module(item)?;
 
+            // entitlement groups were collected ahead of time
+            if EntitlementGroupArgs::get(module.attrs.iter())?.is_some() {
+                continue;
+            }
+
+            if let Some(fact_args) = FactArgs::get(module.attrs.iter())? {
+                errors.try_maybe_then(Fact::parse(module, fact_args), |fact| {
+                    block.facts.push(fact);
+
+                    Ok(())
+                });
+
+                continue;
+            }
+
+            if let Some(trigger_args) = TriggerArgs::get(module.attrs.iter())? {
+                errors.try_maybe_then(Trigger::parse(module, trigger_args), |trigger| {
+                    block.triggers.push(trigger);
+
+                    Ok(())
+                });
+
+                continue;
+            }
+
             // TODO: this isn't the most flexible solution
             // but it does work for now.
             // args should be dispatched procedurally.
             match (
                 SchemaArgs::get(module.attrs.iter())?,
                 RegisterArgs::get(module.attrs.iter())?,
+                ClusterArgs::get(module.attrs.iter())?,
             ) {
-                (Some(schema_args), None) => {
+                (Some(schema_args), None, None) => {
                     errors.try_maybe_then(
                         SchemaSpec::parse(
                             module.ident.clone(),
@@ -114,7 +330,7 @@ impl BlockSpec {
                         },
                     );
                 }
-                (None, Some(register_args)) => {
+                (None, Some(register_args), None) => {
                     errors.try_maybe_then(
                         RegisterSpec::parse(
                             module.ident.clone(),
@@ -126,79 +342,1903 @@ impl BlockSpec {
                         |spec| {
                             let register = Register::validate(spec)?;
 
-                            register_offset = register.args.offset.unwrap_or(register_offset) + 0x4;
+                            register_offset = register.offset + register.byte_size();
                             block.registers.push(register);
 
                             Ok(())
                         },
                     );
                 }
-                (None, None) => {
-                    errors.push(syn::Error::new_spanned(module, "extraneous item"));
+                (None, None, Some(cluster_args)) => {
+                    if cluster_args.offset.is_none() && !args.auto_increment {
+                        errors.push(syn::Error::new(
+                            cluster_args.span(),
+                            "cluster offset must be specified. to infer offsets, use `auto_increment`",
+                        ));
+                    }
+
+                    let offset = cluster_args.offset.unwrap_or(register_offset);
+
+                    errors.try_maybe_then(
+                        block.parse_cluster(
+                            module.ident.clone(),
+                            module.vis.clone(),
+                            cluster_args,
+                            offset,
+                            extract_items_from(module)?.iter(),
+                        ),
+                        |spec| {
+                            let cluster = Block::validate(spec)?;
+
+                            register_offset = offset + cluster.domain_size();
+                            block.clusters.push(cluster);
+
+                            Ok(())
+                        },
+                    );
+                }
+                (None, None, None) => {
+                    errors.push(syn::Error::new_spanned(module, "extraneous item"));
+                }
+                (schema_args, register_args, cluster_args) => {
+                    let msg = "only one module annotation is permitted";
+
+                    for span in [
+                        schema_args.map(|args| args.span()),
+                        register_args.map(|args| args.span()),
+                        cluster_args.map(|args| args.span()),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    {
+                        errors.push(syn::Error::new(span, msg));
+                    }
+                }
+            }
+        }
+
+        errors.coalesce()?;
+
+        Ok(block)
+    }
+}
+
+impl Validator<BlockSpec> for Block {
+    type Error = syn::Error;
+
+    fn validate(spec: BlockSpec) -> Result<Self, Self::Error> {
+        let mut errors = SynErrorCombinator::new();
+
+        if spec.args.dynamic && spec.base_addr.is_some() {
+            errors.push(syn::Error::new(
+                spec.args.span(),
+                "dynamic blocks are located at runtime and cannot specify a base address",
+            ));
+        }
+
+        if let Some(instances) = spec.args.instances {
+            if !spec.args.dynamic {
+                errors.push(syn::Error::new(
+                    spec.args.span(),
+                    "only dynamic blocks can have multiple instances",
+                ));
+            }
+
+            if instances == 0 {
+                errors.push(syn::Error::new(
+                    spec.args.span(),
+                    "dynamic blocks must have at least one instance",
+                ));
+            }
+
+            if spec.args.erase_mod {
+                errors.push(syn::Error::new(
+                    spec.args.span(),
+                    "the module of blocks with multiple instances cannot be erased",
+                ));
+            }
+        }
+
+        if spec.args.power_domain.is_some() {
+            for ident in spec
+                .registers
+                .iter()
+                .map(|register| &register.ident)
+                .chain(spec.clusters.iter().map(|cluster| &cluster.ident))
+                .filter(|ident| *ident == "power_domain")
+            {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "`power_domain` collides with the power domain accessor of the block",
+                ));
+            }
+        }
+
+        if !spec.entitlement_groups.is_empty() {
+            for ident in spec
+                .registers
+                .iter()
+                .map(|register| &register.ident)
+                .chain(spec.clusters.iter().map(|cluster| &cluster.ident))
+                .filter(|ident| *ident == "entitlement_groups")
+            {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "`entitlement_groups` collides with the entitlement groups of the block",
+                ));
+            }
+        }
+
+        if spec.args.dynamic && spec.args.non_secure.is_some() {
+            errors.push(syn::Error::new(
+                spec.args.span(),
+                "dynamic blocks are located at runtime and cannot specify a non-secure alias",
+            ));
+        }
+
+        for (i, remap) in spec.args.remap.iter().enumerate() {
+            if spec.args.dynamic {
+                errors.push(syn::Error::new_spanned(
+                    &remap.name,
+                    "dynamic blocks are located at runtime and cannot be remapped",
+                ));
+            }
+
+            if spec.args.non_secure.is_some() {
+                errors.push(syn::Error::new_spanned(
+                    &remap.name,
+                    "blocks with a non-secure alias cannot be remapped",
+                ));
+            }
+
+            if spec.args.remap[..i]
+                .iter()
+                .any(|other| other.name == remap.name)
+            {
+                errors.push(syn::Error::new_spanned(
+                    &remap.name,
+                    "mapping is already declared",
+                ));
+            }
+
+            if Some(remap.base_addr) == spec.base_addr {
+                errors.push(syn::Error::new_spanned(
+                    &remap.name,
+                    "mapping is at the base address of the block",
+                ));
+            }
+
+            if remap.name == "default" {
+                errors.push(syn::Error::new_spanned(
+                    &remap.name,
+                    "the default mapping is specified with `default_mapping`",
+                ));
+            }
+        }
+
+        match (&spec.args.default_mapping, spec.args.remap.first()) {
+            (None, Some(remap)) => errors.push(syn::Error::new_spanned(
+                &remap.name,
+                "remapped blocks must specify the state of their default mapping with `default_mapping`",
+            )),
+            (Some(default_mapping), None) => errors.push(syn::Error::new_spanned(
+                default_mapping,
+                "only remapped blocks have a default mapping",
+            )),
+            _ => {}
+        }
+
+        // bit-banded registers must reside within the peripheral
+        // bit-band region at every address the block is mapped to
+        if spec.args.dynamic {
+            for (register, _) in spec.bit_banded_registers(0) {
+                errors.push(syn::Error::new(
+                    register.args.span(),
+                    "bit-banded registers cannot reside in dynamic blocks, as their address is not known to be within the bit-band region",
+                ));
+            }
+        } else if let Some(base_addr) = spec.base_addr {
+            for base_addr in std::iter::once(base_addr)
+                .chain(spec.args.remap.iter().map(|remap| remap.base_addr))
+            {
+                for (register, addr) in spec.bit_banded_registers(base_addr) {
+                    if !(BIT_BAND_REGION.contains(&addr)
+                        && addr + register.byte_size() <= BIT_BAND_REGION.end)
+                    {
+                        errors.push(syn::Error::new(
+                            register.args.span(),
+                            format!(
+                                "bit-banded register `{}` at {addr:#010x} is outside of the bit-band region ({:#010x}..{:#010x})",
+                                register.ident, BIT_BAND_REGION.start, BIT_BAND_REGION.end,
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        // the mapping of remapped blocks is held by the block
+        if !spec.args.remap.is_empty() {
+            for ident in spec
+                .registers
+                .iter()
+                .map(|register| &register.ident)
+                .chain(spec.clusters.iter().map(|cluster| &cluster.ident))
+                .filter(|ident| *ident == "mapping")
+            {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "`mapping` is reserved for the mapping of remapped blocks",
+                ));
+            }
+        }
+
+        for register in &spec.registers {
+            if register.args.offset.is_none() && !spec.args.auto_increment {
+                errors.push(syn::Error::new(
+                    register.args.span(),
+                    "register offset must be specified. to infer offsets, use `auto_increment`",
+                ));
+            }
+        }
+
+        let mut paired = HashSet::new();
+
+        for register in &spec.registers {
+            let Some(pair) = &register.args.pair else {
+                continue;
+            };
+
+            if pair.high == register.ident {
+                errors.push(syn::Error::new_spanned(
+                    &pair.high,
+                    "register cannot be paired with itself",
+                ));
+            } else if let Some(high) = spec.registers.iter().find(|other| other.ident == pair.high)
+            {
+                // registers of a block share its power domain, but banked
+                // registers are only reachable while their bank is selected
+                if register.args.bank != high.args.bank {
+                    errors.push(syn::Error::new_spanned(
+                        &pair.high,
+                        "paired registers must reside in the same bank",
+                    ));
+                }
+            } else {
+                errors.push(syn::Error::new_spanned(
+                    &pair.high,
+                    "paired registers must reside in the same block (and power domain)",
+                ));
+            }
+
+            for ident in [&register.ident, &pair.high] {
+                if !paired.insert(ident) {
+                    errors.push(syn::Error::new_spanned(
+                        &pair.high,
+                        format!("register '{ident}' is already paired"),
+                    ));
+                }
+            }
+        }
+
+        if let Some(embedded_hal) = &spec.args.embedded_hal {
+            errors.maybe(|| embedded_hal.validate(&spec.registers));
+        }
+
+        let mut config_names = HashSet::new();
+
+        for register in &spec.registers {
+            for field in &register.fields {
+                let Some(config) = &field.args.config else {
+                    continue;
+                };
+
+                if register.args.bank.is_some() {
+                    errors.push(syn::Error::new(
+                        config.span(),
+                        "fields of banked registers cannot be configured",
+                    ));
+                }
+
+                let name = config.name.as_ref().unwrap_or(&field.ident);
+
+                if !config_names.insert(name) {
+                    errors.push(syn::Error::new(
+                        config.span(),
+                        format!("`{name}` is already configured. name this field with `config(name = ...)`"),
+                    ));
+                }
+            }
+        }
+
+        for fact in &spec.facts {
+            errors.maybe(|| fact.validate(&spec.registers));
+        }
+
+        for register in &spec.registers {
+            if let Some(stream) = &register.args.stream {
+                errors.maybe(|| stream.validate(&spec.registers));
+            }
+
+            if let Some(bank) = &register.args.bank {
+                errors.maybe(|| validate_bank(&spec.registers, bank));
+            }
+        }
+
+        for trigger in &spec.triggers {
+            errors.maybe(|| trigger.validate(&spec.registers));
+        }
+
+        // registers and clusters share the address space of the block
+        let mut domains = spec
+            .registers
+            .iter()
+            .map(|register| {
+                (
+                    &register.ident,
+                    register.offset,
+                    register.offset + register.byte_size(),
+                    register.args.allow.overlap,
+                    register.args.bank.as_ref(),
+                )
+            })
+            .chain(spec.clusters.iter().map(|cluster| {
+                let offset = cluster.offset.unwrap_or(0);
+
+                (
+                    &cluster.ident,
+                    offset,
+                    offset + cluster.domain_size(),
+                    cluster.allow.overlap,
+                    None,
+                )
+            }))
+            .collect::<Vec<_>>();
+
+        domains.sort_by_key(|(_, start, ..)| *start);
+
+        // banks at a shared address are not adjacent in general,
+        // so every pair of domains is compared
+        for (i, (lhs_ident, lhs_start, lhs_end, lhs_allowed, lhs_bank)) in
+            domains.iter().enumerate()
+        {
+            for (rhs_ident, rhs_start, rhs_end, rhs_allowed, rhs_bank) in &domains[i + 1..] {
+                // aliased items are permitted to overlap
+                if *lhs_allowed || *rhs_allowed {
+                    continue;
+                }
+
+                // registers in distinct banks of the same selector are never visible together
+                if let (Some(lhs_bank), Some(rhs_bank)) = (lhs_bank, rhs_bank) {
+                    let segments = |bank: &Path| {
+                        bank.segments
+                            .iter()
+                            .map(|segment| segment.ident.clone())
+                            .collect::<Vec<_>>()
+                    };
+
+                    let (lhs_bank, rhs_bank) = (segments(lhs_bank), segments(rhs_bank));
+
+                    if lhs_bank[..lhs_bank.len().saturating_sub(1)]
+                        == rhs_bank[..rhs_bank.len().saturating_sub(1)]
+                        && lhs_bank != rhs_bank
+                    {
+                        continue;
+                    }
+                }
+
+                if lhs_end > rhs_start {
+                    let msg = format!(
+                        "register domains overlapping. {} {{ domain: {}..{} }}, {} {{ domain: {}..{} }}",
+                        lhs_ident, lhs_start, lhs_end,
+                        rhs_ident, rhs_start, rhs_end,
+                    );
+
+                    errors.push(syn::Error::new(spec.args.span(), msg));
+                }
+            }
+        }
+
+        errors.coalesce()?;
+
+        Ok(Self { spec })
+    }
+}
+
+impl BlockSpec {
+    /// The registers of this block (and its clusters) written through
+    /// the bit-band alias region, with their addresses when the block
+    /// resides at `base_addr`.
+    fn bit_banded_registers(&self, base_addr: u32) -> Vec<(&Register, u32)> {
+        self.registers
+            .iter()
+            .filter(|register| matches!(register.args.atomic, Some(AtomicStrategy::BitBand)))
+            .map(|register| (register, base_addr + register.offset))
+            .chain(self.clusters.iter().flat_map(|cluster| {
+                cluster.bit_banded_registers(base_addr + cluster.offset.unwrap_or(0))
+            }))
+            .collect()
+    }
+}
+
+impl Block {
+    /// The size of the address space occupied by this block.
+    pub fn domain_size(&self) -> u32 {
+        self.registers
+            .iter()
+            .map(|register| register.offset + register.byte_size())
+            .chain(
+                self.clusters
+                    .iter()
+                    .map(|cluster| cluster.offset.unwrap_or(0) + cluster.domain_size()),
+            )
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Generate a register snapshot facility (if
+    /// the `introspection` feature is enabled).
+    fn generate_introspection(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        // reading registers with read effects would
+        // disturb the state being inspected
+        let registers = self
+            .registers
+            .iter()
+            .filter(|register| {
+                // banked registers read whichever bank is selected
+                register.args.bank.is_none()
+                    && register.fields.iter().any(|field| field.access.is_read())
+                    && !register
+                        .fields
+                        .iter()
+                        .any(|field| field.access.read_effect().is_some())
+            })
+            .collect::<Vec<_>>();
+
+        let register_idents = registers
+            .iter()
+            .map(|register| &register.ident)
+            .collect::<Vec<_>>();
+        let register_count = registers.len();
+
+        let field_infos = registers.iter().enumerate().flat_map(|(index, register)| {
+            let register_name = register.ident.to_string();
+
+            register
+                .fields
+                .iter()
+                .filter(|field| field.access.is_read())
+                .map(move |field| {
+                    let field_name = field.ident.to_string();
+                    let offset = field.offset;
+                    let width = field.width();
+
+                    quote_spanned! { span =>
+                        ::proto_hal::introspection::FieldInfo {
+                            register: #register_name,
+                            field: #field_name,
+                            index: #index,
+                            offset: #offset,
+                            width: #width,
+                        },
+                    }
+                })
+        });
+
+        let entitlement_graph = self.entitlement_graph();
+
+        quote_spanned! { span =>
+            ::proto_hal::__introspection! {
+                /// The fields, variants, and entitlements of this
+                /// block, in DOT (graphviz).
+                ///
+                /// Edges point from entitlements to the states
+                /// (solid), fields (dashed), or block (bold)
+                /// which require them.
+                pub const ENTITLEMENT_GRAPH: &str = #entitlement_graph;
+
+                /// The raw values of the readable registers of this block.
+                ///
+                /// *Note: Registers with fields which have read effects are omitted.*
+                #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                pub struct Snapshot {
+                    #(
+                        pub #register_idents: u32,
+                    )*
+                }
+
+                impl Snapshot {
+                    /// The readable fields of the registers in the snapshot.
+                    pub const FIELDS: &'static [::proto_hal::introspection::FieldInfo] = &[
+                        #(
+                            #field_infos
+                        )*
+                    ];
+
+                    fn values(&self) -> [u32; #register_count] {
+                        [
+                            #(
+                                self.#register_idents,
+                            )*
+                        ]
+                    }
+
+                    /// Compare this snapshot with a later one, yielding
+                    /// the fields whose values differ.
+                    pub fn diff(&self, after: &Self) -> impl Iterator<Item = ::proto_hal::introspection::Change> {
+                        ::proto_hal::introspection::diff(Self::FIELDS, self.values(), after.values())
+                    }
+                }
+
+                /// Read the current value of every readable register in this block.
+                ///
+                /// # Safety
+                ///
+                /// The block must be accessible (e.g. clocked).
+                pub unsafe fn snapshot() -> Snapshot {
+                    Snapshot {
+                        #(
+                            #register_idents: #register_idents::read_raw(),
+                        )*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Invoke the user provided plugin macros with a description
+    /// of this block, allowing them to emit additional items within
+    /// the block module.
+    ///
+    /// The description takes the form:
+    ///
+    /// ```ignore
+    /// block: cordic,
+    /// registers: {
+    ///     csr: {
+    ///         offset: 0x0,
+    ///         size: 32,
+    ///         fields: {
+    ///             func: { offset: 0, width: 4, access: read_write, resolvable: true, variants: [Cos, Sin] },
+    ///         },
+    ///     },
+    /// },
+    /// ```
+    fn generate_plugin_invocations(&self) -> TokenStream2 {
+        let span = self.args.span();
+        let ident = &self.ident;
+
+        let registers = self.registers.iter().map(|register| {
+            let register_ident = &register.ident;
+            let offset = register.offset;
+            let size = register.size();
+
+            let fields = register.fields.iter().map(|field| {
+                let field_ident = &field.ident;
+                let offset = field.offset;
+                let width = field.width();
+                let access = Ident::new(
+                    match &field.access {
+                        Access::Read(_) => "read",
+                        Access::Write(_) => "write",
+                        Access::ReadWrite { read: _, write: _ } => "read_write",
+                    },
+                    span,
+                );
+                let resolvable = field.is_resolvable();
+                let variants = match field
+                    .read_schema()
+                    .or(field.write_schema())
+                    .map(|schema| &schema.numericity)
+                {
+                    Some(Numericity::Enumerated { variants }) => variants.iter().collect(),
+                    _ => Vec::new(),
+                }
+                .into_iter()
+                .map(|variant| &variant.ident);
+
+                quote_spanned! { span =>
+                    #field_ident: {
+                        offset: #offset,
+                        width: #width,
+                        access: #access,
+                        resolvable: #resolvable,
+                        variants: [#(#variants),*],
+                    },
+                }
+            });
+
+            quote_spanned! { span =>
+                #register_ident: {
+                    offset: #offset,
+                    size: #size,
+                    fields: {
+                        #(#fields)*
+                    },
+                },
+            }
+        });
+
+        let description = quote_spanned! { span =>
+            block: #ident,
+            registers: {
+                #(#registers)*
+            },
+        };
+
+        self.args
+            .plugins
+            .elems
+            .iter()
+            .map(|plugin| {
+                quote_spanned! { span =>
+                    #plugin! { #description }
+                }
+            })
+            .collect()
+    }
+
+    /// The graph of the fields, variants, and entitlements
+    /// of this block, in DOT (graphviz).
+    ///
+    /// Edges point from entitlements to the states or
+    /// fields which require them.
+    fn entitlement_graph(&self) -> String {
+        // entitlements are resolved as rust paths from the module they
+        // are declared in (`scope`, relative to the block), i.e. those
+        // of fields from the register, and those of the block from the
+        // block. paths leaving the block are kept as written relative
+        // to the block, and absolute paths are kept as written
+        fn node(scope: &[&Ident], path: &Path) -> String {
+            let segments = path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>();
+
+            if path.leading_colon.is_some() {
+                return format!("::{}", segments.join("::"));
+            }
+
+            if segments.first().is_some_and(|segment| segment == "crate") {
+                return segments.join("::");
+            }
+
+            let depth = segments
+                .iter()
+                .take_while(|segment| *segment == "super")
+                .count();
+
+            let prefix = match scope.len().checked_sub(depth) {
+                Some(remaining) => scope[..remaining]
+                    .iter()
+                    .map(|ident| ident.to_string())
+                    .collect(),
+                None => vec!["super".to_string(); depth - scope.len()],
+            };
+
+            prefix
+                .into_iter()
+                .chain(segments[depth..].iter().cloned())
+                .collect::<Vec<_>>()
+                .join("::")
+        }
+
+        let mut graph = format!("digraph {} {{\n", self.ident);
+
+        for register in &self.registers {
+            graph.push_str(&format!(
+                "    subgraph \"cluster_{0}\" {{\n        label = \"{0}\";\n",
+                register.ident
+            ));
+
+            for field in &register.fields {
+                let variants = match field
+                    .read_schema()
+                    .or(field.write_schema())
+                    .map(|schema| &schema.numericity)
+                {
+                    Some(Numericity::Enumerated { variants }) => variants.as_slice(),
+                    _ => &[],
+                };
+
+                if variants.is_empty() {
+                    graph.push_str(&format!(
+                        "        \"{0}::{1}\" [label = \"{1}\", shape = box];\n",
+                        register.ident, field.ident
+                    ));
+                }
+
+                for variant in variants {
+                    graph.push_str(&format!(
+                        "        \"{0}::{1}::{2}\" [label = \"{1}::{2}\"];\n",
+                        register.ident, field.ident, variant.ident
+                    ));
+                }
+            }
+
+            graph.push_str("    }\n");
+
+            for field in &register.fields {
+                for entitlement in sorted_paths(field.access_entitlements()) {
+                    graph.push_str(&format!(
+                        "    \"{}\" -> \"{}::{}\" [style = dashed];\n",
+                        node(&[&register.ident], entitlement),
+                        register.ident,
+                        field.ident
+                    ));
+                }
+
+                let Some(Numericity::Enumerated { variants }) = field
+                    .read_schema()
+                    .or(field.write_schema())
+                    .map(|schema| &schema.numericity)
+                else {
+                    continue;
+                };
+
+                for variant in variants {
+                    for entitlement in sorted_paths(&variant.entitlements) {
+                        graph.push_str(&format!(
+                            "    \"{}\" -> \"{}::{}::{}\";\n",
+                            node(&[&register.ident], entitlement),
+                            register.ident,
+                            field.ident,
+                            variant.ident
+                        ));
+                    }
+                }
+            }
+        }
+
+        for entitlement in &self.entitlements {
+            graph.push_str(&format!(
+                "    \"{}\" -> \"{}\" [style = bold];\n",
+                node(&[], entitlement),
+                self.ident
+            ));
+        }
+
+        graph.push_str("}\n");
+
+        graph
+    }
+
+    /// A human-readable map of the registers in this block, in markdown.
+    fn register_map(&self) -> String {
+        let mut map = String::from("# Register Map\n\n");
+
+        match (self.offset, self.base_addr) {
+            (Some(offset), _) => map.push_str(&format!("Cluster offset: `{offset:#06x}`\n\n")),
+            (None, Some(base_addr)) => {
+                map.push_str(&format!("Base address: `{base_addr:#010x}`\n\n"))
+            }
+            (None, None) => {}
+        }
+
+        if let Some(default_mapping) = &self.args.default_mapping {
+            map.push_str("Remapped:\n");
+
+            let mappings = [(
+                self.base_addr.unwrap_or(0),
+                default_mapping,
+                "default".to_string(),
+            )]
+            .into_iter()
+            .chain(
+                self.args
+                    .remap
+                    .iter()
+                    .map(|remap| (remap.base_addr, &remap.state, remap.name.to_string())),
+            );
+
+            for (base_addr, state, name) in mappings {
+                let state = state
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+
+                map.push_str(&format!("- `{base_addr:#010x}` while `{state}` ({name})\n"));
+            }
+
+            map.push('\n');
+        }
+
+        if !self.entitlements.is_empty() {
+            map.push_str("Entitlements:\n");
+
+            for entitlement in &self.entitlements {
+                let path = entitlement
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+
+                map.push_str(&format!("- `{path}`\n"));
+            }
+
+            map.push('\n');
+        }
+
+        let (covered, total) = self
+            .registers
+            .iter()
+            .fold((0, 0), |(covered, total), register| {
+                (
+                    covered + register.covered_bits(),
+                    total + register.size() as u32,
+                )
+            });
+
+        if total != 0 {
+            map.push_str(&format!(
+                "Coverage: {covered} of {total} bits ({:.1}%)\n\n",
+                covered as f32 / total as f32 * 100.
+            ));
+        }
+
+        map.push_str("| Offset | Register | Size | Coverage | Fields |\n|-|-|-|-|-|\n");
+
+        for register in &self.registers {
+            let fields = register
+                .fields
+                .iter()
+                .map(|field| format!("`{}`", field.ident))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            map.push_str(&format!(
+                "| `{:#06x}` | [`{}`]({}) | {} | {}/{} | {} |\n",
+                register.offset,
+                register.ident,
+                register.ident,
+                register.size(),
+                register.covered_bits(),
+                register.size(),
+                fields,
+            ));
+        }
+
+        for cluster in &self.clusters {
+            map.push_str(&format!(
+                "| `{:#06x}` | [`{}`]({}) | {} | | *cluster* |\n",
+                cluster.offset.unwrap_or(0),
+                cluster.ident,
+                cluster.ident,
+                cluster.domain_size() * 8,
+            ));
+        }
+
+        for register in &self.registers {
+            map.push_str(&format!("\n## `{}`\n\n", register.ident));
+
+            let uncovered = register.uncovered_ranges();
+
+            if !uncovered.is_empty() {
+                let ranges = uncovered
+                    .iter()
+                    .map(|range| {
+                        if range.len() == 1 {
+                            format!("`{}`", range.start)
+                        } else {
+                            format!("`{}:{}`", range.end - 1, range.start)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                let policy = match register.args.reserved.as_deref() {
+                    Some(ReservedPolicy::Keep) => " (kept)",
+                    Some(ReservedPolicy::Zero) => " (written as zero)",
+                    Some(ReservedPolicy::One) => " (written as one)",
+                    None => "",
+                };
+
+                map.push_str(&format!("Uncovered bits: {ranges}{policy}\n\n"));
+            }
+
+            map.push_str("| Bits | Field | Access | Reset | Variants |\n|-|-|-|-|-|\n");
+
+            for field in &register.fields {
+                let bits = if field.width() == 1 {
+                    format!("{}", field.offset)
+                } else {
+                    format!("{}:{}", field.offset + field.width() - 1, field.offset)
+                };
+
+                let access = match &field.access {
+                    Access::Read(_) => "r",
+                    Access::Write(_) => "w",
+                    Access::ReadWrite { read: _, write: _ } => "rw",
+                };
+
+                let reset = field
+                    .args
+                    .reset
+                    .as_ref()
+                    .map(|reset| format!("`{}`", reset.to_token_stream()))
+                    .unwrap_or_default();
+
+                let variants = match field
+                    .read_schema()
+                    .or(field.write_schema())
+                    .map(|schema| &schema.numericity)
+                {
+                    Some(Numericity::Enumerated { variants }) => variants
+                        .iter()
+                        .map(|variant| match &variant.range {
+                            Some(range) => {
+                                format!("`{}` = {}..={}", variant.ident, range.start, range.end - 1)
+                            }
+                            None => format!("`{}` = {}", variant.ident, variant.bits),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    _ => String::new(),
+                };
+
+                map.push_str(&format!(
+                    "| {bits} | `{}` | {access} | {reset} | {variants} |\n",
+                    field.ident
+                ));
+            }
+        }
+
+        map
+    }
+
+    fn maybe_generate_power_domain(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+        let power_domain = self.args.power_domain.as_ref()?;
+
+        Some(quote_spanned! { span =>
+            /// The state enabling the power domain this block resides in.
+            ///
+            /// An entitlement to this state is required to [`attach`](Block::attach) the block.
+            pub type PowerDomain = #power_domain;
+
+            const _: () = assert!(
+                <PowerDomain as ::proto_hal::stasis::Freeze>::REACHABLE,
+                "the power domain of this block is unreachable, as the state enabling it can never be entered",
+            );
+        })
+    }
+
+    /// Generate the depth of this block, inherited by its registers,
+    /// which follows the blocks providing its entitlements, and
+    /// whether the block can be reached through its entitlements.
+    fn generate_depth(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        // clusters are attached with their parent block
+        if self.offset.is_some() {
+            return quote_spanned! { span =>
+                const DEPTH: usize = super::DEPTH;
+                #[allow(dead_code)]
+                const REACHABLE: bool = super::REACHABLE;
+            };
+        }
+
+        // entitlements within the block cannot be
+        // provided before the block is attached
+        let providers = self
+            .entitlements
+            .iter()
+            .filter(|path| {
+                path.segments
+                    .first()
+                    .is_some_and(|segment| segment.ident == "super")
+            })
+            .collect::<Vec<_>>();
+
+        quote_spanned! { span =>
+            const DEPTH: usize = ::proto_hal::macro_utils::depth(0, &[#(<#providers as ::proto_hal::stasis::Freeze>::DEPTH,)*]);
+
+            /// Whether this block can be reached, which requires
+            /// all of its entitlements to be reachable.
+            ///
+            /// *Note: This is unused by blocks without resolvable fields.*
+            #[allow(dead_code)]
+            const REACHABLE: bool = true #(&& <#providers as ::proto_hal::stasis::Freeze>::REACHABLE)*;
+        }
+    }
+
+    fn maybe_generate_non_secure_attribution(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+        let non_secure = self.args.non_secure.as_ref()?;
+
+        let entitlements = non_secure
+            .entitlements
+            .elems
+            .iter()
+            .map(|path| quote_spanned! { span => ::proto_hal::stasis::Entitlement<#path> })
+            .collect::<Vec<_>>();
+
+        Some(quote_spanned! { span =>
+            /// This block, relinquished to the non-secure world.
+            ///
+            /// The entitlements to the states attributing the block as
+            /// non-secure are held, so the attribution cannot be revoked
+            /// until the block is [reclaimed](NonSecure::reclaim).
+            pub struct NonSecure {
+                entitlements: (#(#entitlements,)*),
+            }
+
+            impl Reset {
+                /// Relinquish this block to the non-secure world,
+                /// where it is accessed at [`NON_SECURE_BASE_ADDR`].
+                ///
+                /// The required entitlements serve as proof that
+                /// the block is attributed as non-secure.
+                pub fn attribute_non_secure(self, entitlements: (#(#entitlements,)*)) -> NonSecure {
+                    NonSecure { entitlements }
+                }
+            }
+
+            impl NonSecure {
+                /// Reclaim this block from the non-secure world,
+                /// yielding the entitlements it was attributed with.
+                ///
+                /// # Safety
+                ///
+                /// The non-secure world must no longer access the block,
+                /// and must have returned it to its reset state.
+                pub unsafe fn reclaim(self) -> (Reset, (#(#entitlements,)*)) {
+                    (Reset::conjure(), self.entitlements)
+                }
+            }
+        })
+    }
+
+    /// The fields of this block which are part of its configuration,
+    /// with the name each is configured by.
+    fn config_fields(&self) -> impl Iterator<Item = (&Register, &Field, &Ident)> {
+        self.registers.iter().flat_map(|register| {
+            register.fields.iter().filter_map(move |field| {
+                let config = field.args.config.as_ref()?;
+
+                Some((
+                    register,
+                    field,
+                    config.name.as_ref().unwrap_or(&field.ident),
+                ))
+            })
+        })
+    }
+
+    /// Generate the configuration of this block, which sets
+    /// the configured fields of many registers in one call.
+    fn maybe_generate_config(
+        &self,
+        generics: &[&Ident],
+        bounds: &[TokenStream2],
+        entitlements: &[Path],
+    ) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let fields = self.config_fields().collect::<Vec<_>>();
+
+        if fields.is_empty() {
+            return None;
+        }
+
+        let names = fields.iter().map(|(.., name)| name).collect::<Vec<_>>();
+        let tys = fields
+            .iter()
+            .map(|(register, field, _)| {
+                let register_ident = &register.ident;
+                let field_ident = &field.ident;
+
+                if field
+                    .write_schema()
+                    .is_some_and(|schema| schema.numericity.is_enumerated())
+                {
+                    quote_spanned! { span => #register_ident::#field_ident::WriteVariant }
+                } else {
+                    field.value_ty().to_token_stream()
+                }
+            })
+            .collect::<Vec<_>>();
+        let setter_docs = fields
+            .iter()
+            .map(|(register, field, _)| format!("Set `{}::{}`.", register.ident, field.ident));
+        let assertions = fields.iter().map(|(register, field, _)| {
+            let register_ident = &register.ident;
+            let field_ident = &field.ident;
+            let raw_value = field.value_to_raw(&parse_quote! { value });
+
+            field.maybe_generate_constraint_assertion(
+                &quote_spanned! { span => #register_ident::#field_ident },
+                &raw_value,
+            )
+        });
+
+        let mut applications = TokenStream2::new();
+
+        for register in &self.registers {
+            let (register_names, writes) = fields
+                .iter()
+                .filter(|(candidate, ..)| candidate.ident == register.ident)
+                .map(|(_, field, name)| {
+                    let field_ident = &field.ident;
+
+                    let write = if field
+                        .write_schema()
+                        .is_some_and(|schema| schema.numericity.is_enumerated())
+                    {
+                        quote_spanned! { span => w.#field_ident().variant(value) }
+                    } else {
+                        quote_spanned! { span => w.#field_ident(value) }
+                    };
+
+                    (name, write)
+                })
+                .unzip::<_, _, Vec<&Ident>, Vec<_>>();
+
+            if register_names.is_empty() {
+                continue;
+            }
+
+            let register_ident = &register.ident;
+
+            applications.extend(quote_spanned! { span =>
+                if #(self.#register_names.is_some())||* {
+                    // SAFETY: the block is borrowed exclusively
+                    unsafe {
+                        #register_ident::modify(|_, w| {
+                            #(
+                                if let Some(value) = self.#register_names {
+                                    #writes;
+                                }
+                            )*
+
+                            w
+                        });
+                    }
+                }
+            });
+        }
+
+        Some(quote_spanned! { span =>
+            /// A configuration of this block, built by setting
+            /// fields and applied with [`Config::apply`].
+            ///
+            /// Fields which are not set are left unchanged.
+            #[derive(Clone, Copy, Default)]
+            pub struct Config {
+                #(
+                    #names: Option<#tys>,
+                )*
+            }
+
+            impl Config {
+                #(
+                    #[doc = #setter_docs]
+                    pub fn #names(mut self, value: #tys) -> Self {
+                        #assertions
+
+                        self.#names = Some(value);
+                        self
+                    }
+                )*
+
+                /// Apply this configuration to the block,
+                /// with one read-modify-write per register.
+                pub fn apply<#(#generics,)*>(self, _block: &mut Block<#(#generics,)* #(#entitlements,)*>)
+                where
+                    #(#bounds)*
+                {
+                    #applications
+                }
+            }
+        })
+    }
+
+    /// Generate a constructor capturing the current states of
+    /// the block (i.e. as configured by a bootloader), rather
+    /// than assuming the reset state.
+    fn generate_current_state(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        let (stateful_registers, stateless_registers) = self
+            .registers
+            .iter()
+            .partition::<Vec<_>, _>(|register| register.is_resolvable());
+
+        // the states of banked registers cannot be read
+        // without knowledge of the selected bank
+        let stateful_registers = stateful_registers
+            .into_iter()
+            .filter(|register| register.args.bank.is_none())
+            .collect::<Vec<_>>();
+
+        let stateful_register_idents = stateful_registers
+            .iter()
+            .map(|register| &register.ident)
+            .collect::<Vec<_>>();
+
+        let stateless_register_idents = stateless_registers
+            .iter()
+            .map(|register| &register.ident)
+            .collect::<Vec<_>>();
+
+        let stateless_register_tys = stateless_registers
+            .iter()
+            .map(|register| {
+                let ident = &register.ident;
+
+                if register.args.bank.is_some() {
+                    quote_spanned! { span => ::proto_hal::stasis::Banked<#ident::Register, #ident::Bank> }
+                } else {
+                    quote_spanned! { span => #ident::Register }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let cluster_idents = self
+            .clusters
+            .iter()
+            .map(|cluster| &cluster.ident)
+            .collect::<Vec<_>>();
+
+        quote_spanned! { span =>
+            /// The states of the resolvable fields of this block,
+            /// captured at runtime rather than assumed to be reset.
+            ///
+            /// *Note: Banked registers with resolvable fields are not
+            /// captured, as their states cannot be read without knowledge
+            /// of the selected bank.*
+            pub struct Current {
+                // Stateful registers.
+                #(
+                    pub #stateful_register_idents: #stateful_register_idents::DynState,
+                )*
+
+                // Stateless registers.
+                #(
+                    pub #stateless_register_idents: #stateless_register_tys,
+                )*
+
+                // Clusters.
+                #(
+                    pub #cluster_idents: #cluster_idents::Current,
+                )*
+
+                /// The raw values of the stateful registers, as read.
+                pub raw: Raw,
+            }
+
+            /// The raw values of the stateful registers of this block.
+            #[derive(Clone, Copy, PartialEq, Eq)]
+            pub struct Raw {
+                #(
+                    pub #stateful_register_idents: u32,
+                )*
+            }
+
+            impl Current {
+                /// Capture the current states of this block (i.e. after
+                /// a bootloader configured it).
+                ///
+                /// The states are tracked at runtime, and can be converted
+                /// into state tokens with `DynState::into_typed`.
+                ///
+                /// # Safety
+                ///
+                /// Do not create multiple instances of this block, nor
+                /// any instance with [`Reset::conjure`].
+                pub unsafe fn from_current_state() -> Self {
+                    // each register is read once, so the states
+                    // agree with the raw values
+                    let raw = Raw {
+                        #(
+                            #stateful_register_idents: #stateful_register_idents::read_raw(),
+                        )*
+                    };
+
+                    Self {
+                        #(
+                            #stateful_register_idents: #stateful_register_idents::DynState::from_raw(raw.#stateful_register_idents),
+                        )*
+
+                        #(
+                            #stateless_register_idents: ::core::mem::transmute::<(), #stateless_register_tys>(()),
+                        )*
+
+                        #(
+                            #cluster_idents: #cluster_idents::Current::from_current_state(),
+                        )*
+
+                        raw,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generate ordered accessors for linked register pairs.
+    fn generate_pair_accessors(&self) -> TokenStream2 {
+        let span = self.args.span();
+        let mut body = TokenStream2::new();
+
+        fn resolvable(register: &Register) -> impl Iterator<Item = &Field> {
+            register.fields.iter().filter(|field| field.is_resolvable())
+        }
+
+        for low in &self.registers {
+            let Some(pair) = &low.args.pair else {
+                continue;
+            };
+
+            // validated
+            let high = self
+                .registers
+                .iter()
+                .find(|register| register.ident == pair.high)
+                .unwrap();
+
+            let low_ident = &low.ident;
+            let high_ident = &high.ident;
+
+            let (first, second) = if pair.high_first {
+                (high_ident, low_ident)
+            } else {
+                (low_ident, high_ident)
+            };
+
+            let order_doc = format!("\n*Note: `{first}` is accessed before `{second}`.*");
+
+            // the safe accessors are generic over the states of the
+            // registers, which are distinguished by the word they hold
+            let state_ty = |prefix: &str, field: &Field| {
+                let ty = format_ident!(
+                    "{prefix}{}",
+                    inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string())
+                );
+
+                quote_spanned! { span => #ty }
+            };
+            let low_state_ty = |field: &Field| state_ty("Low", field);
+            let high_state_ty = |field: &Field| state_ty("High", field);
+
+            let low_tys = resolvable(low).map(low_state_ty).collect::<Vec<_>>();
+            let high_tys = resolvable(high).map(high_state_ty).collect::<Vec<_>>();
+            let low_state_bounds = resolvable(low).zip(&low_tys).map(|(field, ty)| {
+                let ident = &field.ident;
+
+                quote_spanned! { span => #ty: #low_ident::#ident::State }
+            });
+            let high_state_bounds = resolvable(high).zip(&high_tys).map(|(field, ty)| {
+                let ident = &field.ident;
+
+                quote_spanned! { span => #ty: #high_ident::#ident::State }
+            });
+            let state_bounds = low_state_bounds
+                .chain(high_state_bounds)
+                .collect::<Vec<_>>();
+
+            let is_unresolvable_and = |register: &Register, f: fn(&Field) -> bool| {
+                register
+                    .fields
+                    .iter()
+                    .any(|field| !field.is_resolvable() && f(field))
+            };
+
+            if low.fields.iter().any(|field| field.access.is_read())
+                && high.fields.iter().any(|field| field.access.is_read())
+            {
+                let read_ident = format_ident!("read_{low_ident}_{high_ident}");
+                let unchecked_read_ident = format_ident!("read_{low_ident}_{high_ident}_unchecked");
+                let read_doc = format!(
+                    "Read `{low_ident}` (low word) and `{high_ident}` (high word) together."
+                );
+
+                body.extend(quote_spanned! { span =>
+                    #[doc = #read_doc]
+                    #[doc = #order_doc]
+                    ///
+                    /// # Safety
+                    ///
+                    /// See the unsafe `read` interface of each register.
+                    pub unsafe fn #unchecked_read_ident() -> (#low_ident::UnsafeReader, #high_ident::UnsafeReader) {
+                        let #first = #first::read();
+                        ::core::sync::atomic::compiler_fence(::core::sync::atomic::Ordering::SeqCst);
+                        let #second = #second::read();
+
+                        (#low_ident, #high_ident)
+                    }
+                });
+
+                // the registers are only read safely through their tokens
+                if is_unresolvable_and(low, |field| field.access.is_read())
+                    && is_unresolvable_and(high, |field| field.access.is_read())
+                {
+                    body.extend(quote_spanned! { span =>
+                        #[doc = #read_doc]
+                        #[doc = #order_doc]
+                        pub fn #read_ident<#(#low_tys,)* #(#high_tys,)*>(
+                            #low_ident: &#low_ident::Register<#(#low_tys,)*>,
+                            #high_ident: &#high_ident::Register<#(#high_tys,)*>,
+                        ) -> (#low_ident::Reader, #high_ident::Reader)
+                        where
+                            #(
+                                #state_bounds,
+                            )*
+                        {
+                            let #first = #first.read();
+                            ::core::sync::atomic::compiler_fence(::core::sync::atomic::Ordering::SeqCst);
+                            let #second = #second.read();
+
+                            (#low_ident, #high_ident)
+                        }
+                    });
+                }
+            }
+
+            if low.fields.iter().any(|field| field.access.is_write())
+                && high.fields.iter().any(|field| field.access.is_write())
+            {
+                let write_ident = format_ident!("write_{low_ident}_{high_ident}");
+                let unchecked_write_ident =
+                    format_ident!("write_{low_ident}_{high_ident}_unchecked");
+                let write_doc = format!(
+                    "Write `{low_ident}` (low word) and `{high_ident}` (high word) together."
+                );
+
+                body.extend(quote_spanned! { span =>
+                    #[doc = #write_doc]
+                    #[doc = #order_doc]
+                    ///
+                    /// # Safety
+                    ///
+                    /// See the unsafe `write` interface of each register.
+                    pub unsafe fn #unchecked_write_ident(
+                        #low_ident: impl FnOnce(&mut #low_ident::UnsafeWriter) -> &mut #low_ident::UnsafeWriter,
+                        #high_ident: impl FnOnce(&mut #high_ident::UnsafeWriter) -> &mut #high_ident::UnsafeWriter,
+                    ) {
+                        #first::write(#first);
+                        ::core::sync::atomic::compiler_fence(::core::sync::atomic::Ordering::SeqCst);
+                        #second::write(#second);
+                    }
+                });
+
+                // the registers are only written safely through their tokens
+                if is_unresolvable_and(low, |field| field.access.is_write())
+                    && is_unresolvable_and(high, |field| field.access.is_write())
+                {
+                    let view_bounds = low
+                        .generate_qualified_view_bounds(Some(low_ident), low_state_ty)
+                        .into_iter()
+                        .chain(
+                            high.generate_qualified_view_bounds(Some(high_ident), high_state_ty),
+                        );
+
+                    body.extend(quote_spanned! { span =>
+                        #[doc = #write_doc]
+                        #[doc = #order_doc]
+                        ///
+                        /// Resolvable fields are written with the states held
+                        /// by the registers, so they are left unchanged.
+                        pub fn #write_ident<#(#low_tys,)* #(#high_tys,)*>(
+                            #low_ident: (&#low_ident::Register<#(#low_tys,)*>, impl FnOnce(&mut #low_ident::Writer) -> &mut #low_ident::Writer),
+                            #high_ident: (&#high_ident::Register<#(#high_tys,)*>, impl FnOnce(&mut #high_ident::Writer) -> &mut #high_ident::Writer),
+                        )
+                        where
+                            #(
+                                #state_bounds,
+                            )*
+                            #(
+                                #view_bounds,
+                            )*
+                        {
+                            #first.0.write(#first.1);
+                            ::core::sync::atomic::compiler_fence(::core::sync::atomic::Ordering::SeqCst);
+                            #second.0.write(#second.1);
+                        }
+                    });
+                }
+            }
+        }
+
+        body
+    }
+
+    /// Generate `core::fmt::Debug` and/or `defmt::Format` implementations
+    /// for variant enums, state types, and register readers.
+    fn maybe_generate_format_impls(&self) -> Option<TokenStream2> {
+        if !self.args.debug && !self.args.defmt {
+            return None;
+        }
+
+        let span = self.args.span();
+        let mut body = TokenStream2::new();
+
+        let mut implement = |generics: Option<TokenStream2>,
+                             ty: TokenStream2,
+                             debug: TokenStream2,
+                             defmt: TokenStream2| {
+            if self.args.debug {
+                body.extend(quote_spanned! { span =>
+                    #[allow(deprecated)]
+                    impl #generics ::core::fmt::Debug for #ty {
+                        fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                            #debug
+                        }
+                    }
+                });
+            }
+
+            if self.args.defmt {
+                body.extend(quote_spanned! { span =>
+                    #[allow(deprecated)]
+                    impl #generics ::defmt::Format for #ty {
+                        fn format(&self, f: ::defmt::Formatter) {
+                            #defmt
+                        }
+                    }
+                });
+            }
+        };
+
+        for register in &self.registers {
+            let register_ident = &register.ident;
+
+            for field in &register.fields {
+                let field_ident = &field.ident;
+
+                for (enum_ident, variants) in field.variant_enums() {
+                    let (debug_arms, defmt_arms) = variants
+                        .iter()
+                        .map(|variant| {
+                            let variant_ident = &variant.ident;
+                            let variant_name = variant_ident.to_string();
+
+                            if variant.range.is_some() {
+                                let fmt = format!("{variant_name}({{}})");
+
+                                (
+                                    quote_spanned! { span => Self::#variant_ident(value) => write!(f, #fmt, value) },
+                                    quote_spanned! { span => Self::#variant_ident(value) => ::defmt::write!(f, #fmt, value) },
+                                )
+                            } else {
+                                (
+                                    quote_spanned! { span => Self::#variant_ident => f.write_str(#variant_name) },
+                                    quote_spanned! { span => Self::#variant_ident => ::defmt::write!(f, #variant_name) },
+                                )
+                            }
+                        })
+                        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+                    implement(
+                        None,
+                        quote_spanned! { span => #register_ident::#field_ident::#enum_ident },
+                        quote_spanned! { span =>
+                            match self {
+                                #(
+                                    #debug_arms,
+                                )*
+                            }
+                        },
+                        quote_spanned! { span =>
+                            match self {
+                                #(
+                                    #defmt_arms,
+                                )*
+                            }
+                        },
+                    );
+                }
+
+                for variant in field.states().into_iter().flatten() {
+                    let variant_ident = &variant.ident;
+                    let variant_name = variant_ident.to_string();
+                    let (impl_generics, ty_generics) = variant.generics();
+
+                    let (debug, defmt) = if variant.range.is_some() {
+                        let fmt = format!("{variant_name}<{{}}>");
+
+                        (
+                            quote_spanned! { span => write!(f, #fmt, V) },
+                            quote_spanned! { span => ::defmt::write!(f, #fmt, V) },
+                        )
+                    } else {
+                        (
+                            quote_spanned! { span => f.write_str(#variant_name) },
+                            quote_spanned! { span => ::defmt::write!(f, #variant_name) },
+                        )
+                    };
+
+                    implement(
+                        impl_generics,
+                        quote_spanned! { span => #register_ident::#field_ident::#variant_ident #ty_generics },
+                        debug,
+                        defmt,
+                    );
+                }
+            }
+
+            let readable_fields = register
+                .fields
+                .iter()
+                .filter(|field| field.access.is_read())
+                .collect::<Vec<_>>();
+
+            // `Reader` contains unresolvable fields, `UnsafeReader` contains all fields
+            for (reader_ident, fields) in [
+                (
+                    format_ident!("Reader"),
+                    readable_fields
+                        .iter()
+                        .filter(|field| !field.is_resolvable())
+                        .copied()
+                        .collect::<Vec<_>>(),
+                ),
+                (format_ident!("UnsafeReader"), readable_fields.clone()),
+            ] {
+                if fields.is_empty() {
+                    continue;
                 }
-                (schema_args, register_args) => {
-                    let msg = "only one module annotation is permitted";
 
-                    for span in [
-                        schema_args.map(|args| args.span()),
-                        register_args.map(|args| args.span()),
-                    ]
-                    .into_iter()
-                    .flatten()
+                let field_idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+                let field_names = field_idents
+                    .iter()
+                    .map(|ident| ident.to_string())
+                    .collect::<Vec<_>>();
+                let reader_name = format!("{register_ident}::{reader_ident}");
+
+                // numeric values are formatted as raw integers since
+                // arbitrary width integers do not implement `Format`
+                let defmt_values = fields.iter().map(|field| {
+                    let ident = &field.ident;
+
+                    if field
+                        .read_schema()
+                        .is_some_and(|schema| schema.numericity.is_numeric())
                     {
-                        errors.push(syn::Error::new(span, msg));
+                        field.value_to_raw(&parse_quote! { self.#ident() })
+                    } else {
+                        quote_spanned! { span => self.#ident() }
                     }
-                }
+                });
+                let defmt_fmt = format!(
+                    "{reader_name} {{{{ {} }}}}",
+                    field_names
+                        .iter()
+                        .map(|name| format!("{name}: {{}}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+
+                implement(
+                    None,
+                    quote_spanned! { span => #register_ident::#reader_ident },
+                    quote_spanned! { span =>
+                        f.debug_struct(#reader_name)
+                            #(
+                                .field(#field_names, &self.#field_idents())
+                            )*
+                            .finish()
+                    },
+                    quote_spanned! { span =>
+                        ::defmt::write!(f, #defmt_fmt, #(#defmt_values),*)
+                    },
+                );
             }
         }
 
-        errors.coalesce()?;
-
-        Ok(block)
+        Some(body)
     }
 }
 
-impl Validator<BlockSpec> for Block {
-    type Error = syn::Error;
+impl ToTokens for Block {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let ident = &self.ident;
 
-    fn validate(spec: BlockSpec) -> Result<Self, Self::Error> {
-        let mut errors = SynErrorCombinator::new();
+        let span = self.args.span();
 
-        for register in &spec.registers {
-            if register.args.offset.is_none() && !spec.args.auto_increment {
-                errors.push(syn::Error::new(
-                    register.args.span(),
-                    "register offset must be specified. to infer offsets, use `auto_increment`",
-                ));
+        let base_addr = if let Some(offset) = self.offset {
+            quote_spanned! { span =>
+                /// The address of this cluster.
+                #[inline(always)]
+                pub fn base_addr() -> u32 {
+                    super::base_addr() + #offset
+                }
             }
-        }
+        } else if self.args.dynamic {
+            quote_spanned! { span =>
+                /// The address of the instance of this block.
+                static BASE_ADDR: ::core::sync::atomic::AtomicU32 = ::core::sync::atomic::AtomicU32::new(0);
 
-        for slice in spec.registers.windows(2) {
-            let lhs = slice.first().unwrap();
-            let rhs = slice.last().unwrap();
-            if lhs.offset + 4 > rhs.offset {
-                let msg = format!(
-                    "register domains overlapping. {} {{ domain: {}..{} }}, {} {{ domain: {}..{} }}",
-                    lhs.ident, lhs.offset, lhs.offset + 4,
-                    rhs.ident, rhs.offset, rhs.offset + 4,
-                );
+                /// Whether an instance of this block exists, which
+                /// owns [`BASE_ADDR`] for as long as it exists.
+                static CLAIMED: ::proto_hal::macro_utils::critical_section::Mutex<::core::cell::Cell<bool>> =
+                    ::proto_hal::macro_utils::critical_section::Mutex::new(::core::cell::Cell::new(false));
+
+                /// The address of this block, as provided to [`Reset::new_at`].
+                #[inline(always)]
+                pub fn base_addr() -> u32 {
+                    BASE_ADDR.load(::core::sync::atomic::Ordering::Relaxed)
+                }
 
-                errors.push(syn::Error::new(spec.args.span(), msg));
+                impl Reset {
+                    /// Conjure an instance of this block in reset state,
+                    /// located at the provided address, only if no other
+                    /// instance of this block exists.
+                    ///
+                    /// The instance owns the address until it is
+                    /// relinquished, so the address of a live instance
+                    /// is never changed.
+                    ///
+                    /// # Safety
+                    ///
+                    /// In addition to the requirements of [`Reset::conjure`],
+                    /// `addr` must be the address of the underlying hardware.
+                    pub unsafe fn new_at(addr: u32) -> Option<Self> {
+                        ::proto_hal::macro_utils::critical_section::with(|cs| {
+                            if CLAIMED.borrow(cs).replace(true) {
+                                None
+                            } else {
+                                BASE_ADDR.store(addr, ::core::sync::atomic::Ordering::Relaxed);
+
+                                Some(Self::conjure())
+                            }
+                        })
+                    }
+
+                    /// Relinquish this instance, so the block may
+                    /// be located elsewhere with [`Reset::new_at`].
+                    pub fn relinquish(self) {
+                        ::proto_hal::macro_utils::critical_section::with(|cs| {
+                            CLAIMED.borrow(cs).set(false);
+                        });
+                    }
+                }
             }
-        }
+        } else {
+            let base_addr = self.base_addr.unwrap_or(0);
 
-        errors.coalesce()?;
+            let (non_secure, alias) = match &self.args.non_secure {
+                Some(non_secure) => {
+                    let non_secure_base_addr = non_secure.base_addr;
 
-        Ok(Self { spec })
-    }
-}
+                    (
+                        Some(quote_spanned! { span =>
+                            /// The address of the non-secure alias of this block.
+                            pub const NON_SECURE_BASE_ADDR: u32 = #non_secure_base_addr;
+                        }),
+                        quote_spanned! { span =>
+                            if ::proto_hal::macro_utils::NON_SECURE {
+                                NON_SECURE_BASE_ADDR
+                            } else {
+                                BASE_ADDR
+                            }
+                        },
+                    )
+                }
+                None => (None, quote_spanned! { span => BASE_ADDR }),
+            };
 
-impl ToTokens for Block {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let ident = &self.ident;
-        let base_addr = self.base_addr;
+            let (remaps, alias) = if self.args.remap.is_empty() {
+                (None, alias)
+            } else {
+                let base_addr_idents = self
+                    .args
+                    .remap
+                    .iter()
+                    .map(|remap| {
+                        format_ident!(
+                            "{}_BASE_ADDR",
+                            inflector::cases::screamingsnakecase::to_screaming_snake_case(
+                                &remap.name.to_string()
+                            )
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let base_addrs = self.args.remap.iter().map(|remap| remap.base_addr);
+                let states = self
+                    .args
+                    .remap
+                    .iter()
+                    .map(|remap| &remap.state)
+                    .collect::<Vec<_>>();
+                // validated
+                let default_state = self.args.default_mapping.as_ref().unwrap();
+                let const_docs = states.iter().map(|state| {
+                    format!(
+                        "The address of this block while `{}`.",
+                        state.to_token_stream().to_string().replace(' ', "")
+                    )
+                });
 
-        let span = self.args.span();
+                (
+                    Some(quote_spanned! { span =>
+                        #(
+                            #[doc = #const_docs]
+                            pub const #base_addr_idents: u32 = #base_addrs;
+
+                            const _: () = assert!(#base_addr_idents & 0b11 == 0, "mapping is not word aligned");
+                        )*
+
+                        /// Entitlements to the states mapping this block,
+                        /// which locate the block while held by it.
+                        pub trait Mapped {
+                            /// The address of this block in the mapping.
+                            const BASE_ADDR: u32;
+                        }
+
+                        impl Mapped for ::proto_hal::stasis::Entitlement<#default_state> {
+                            const BASE_ADDR: u32 = BASE_ADDR;
+                        }
+
+                        #(
+                            impl Mapped for ::proto_hal::stasis::Entitlement<#states> {
+                                const BASE_ADDR: u32 = #base_addr_idents;
+                            }
+                        )*
+
+                        /// The address of the mapping held by the block.
+                        static MAPPED_BASE_ADDR: ::core::sync::atomic::AtomicU32 = ::core::sync::atomic::AtomicU32::new(BASE_ADDR);
+                    }),
+                    quote_spanned! { span =>
+                        MAPPED_BASE_ADDR.load(::core::sync::atomic::Ordering::Relaxed)
+                    },
+                )
+            };
+
+            let size = self.domain_size();
+
+            // the address of remapped blocks is only known at runtime,
+            // otherwise it is const unless remapped by proto-hal
+            let base_addr_fn = if remaps.is_some() {
+                quote_spanned! { span =>
+                    /// The address of this block in its current mapping.
+                    ///
+                    /// *Note: With the `remap` feature of proto-hal, this is
+                    /// further remapped by `proto_hal::remap!`.*
+                    #[inline(always)]
+                    pub fn base_addr() -> u32 {
+                        ::proto_hal::macro_utils::remap(#alias)
+                    }
+                }
+            } else {
+                quote_spanned! { span =>
+                    ::proto_hal::__base_addr! {
+                        /// The address of this block.
+                        ///
+                        /// *Note: With the `remap` feature of proto-hal, this is
+                        /// [`BASE_ADDR`] as remapped by `proto_hal::remap!`, and
+                        /// is not `const`.*
+                        => #alias
+                    }
+                }
+            };
+
+            quote_spanned! { span =>
+                /// The address of this block as described.
+                pub const BASE_ADDR: u32 = #base_addr;
+
+                /// The size (in bytes) of the address space occupied by this block.
+                pub const SIZE: u32 = #size;
+
+                /// Whether this block has been taken by a set of peripherals,
+                /// only to be accessed by `proto_hal::peripherals!`.
+                #[doc(hidden)]
+                pub static __CLAIM: ::proto_hal::macro_utils::Claim = ::proto_hal::macro_utils::Claim::new();
+
+                const _: () = assert!(BASE_ADDR & 0b11 == 0, "block is not word aligned");
+
+                #non_secure
+
+                #remaps
+
+                #base_addr_fn
+            }
+        };
 
         let (stateful_registers, stateless_registers) = self
             .registers
@@ -215,6 +2255,35 @@ impl ToTokens for Block {
             .map(|register| &register.ident)
             .collect::<Vec<_>>();
 
+        // banked registers are held behind their bank selection
+        let banked = |register: &Register, ty: TokenStream2| {
+            if register.args.bank.is_some() {
+                let ident = &register.ident;
+
+                quote_spanned! { span => ::proto_hal::stasis::Banked<#ty, #ident::Bank> }
+            } else {
+                ty
+            }
+        };
+
+        let stateless_register_field_tys = stateless_registers
+            .iter()
+            .map(|register| {
+                let ident = &register.ident;
+
+                banked(register, quote_spanned! { span => #ident::Register })
+            })
+            .collect::<Vec<_>>();
+
+        let stateful_register_reset_tys = stateful_registers
+            .iter()
+            .map(|register| {
+                let ident = &register.ident;
+
+                banked(register, quote_spanned! { span => #ident::Reset })
+            })
+            .collect::<Vec<_>>();
+
         let stateful_register_tys = stateful_registers
             .iter()
             .map(|register| {
@@ -225,6 +2294,23 @@ impl ToTokens for Block {
             })
             .collect::<Vec<_>>();
 
+        let cluster_idents = self
+            .clusters
+            .iter()
+            .map(|cluster| &cluster.ident)
+            .collect::<Vec<_>>();
+
+        let cluster_tys = self
+            .clusters
+            .iter()
+            .map(|cluster| {
+                Ident::new(
+                    &inflector::cases::pascalcase::to_pascal_case(&cluster.ident.to_string()),
+                    Span::call_site(),
+                )
+            })
+            .collect::<Vec<_>>();
+
         let entitlement_idents = (0..self.entitlements.len())
             .map(|i| format_ident!("entitlement{}", i))
             .collect::<Vec<_>>();
@@ -242,29 +2328,92 @@ impl ToTokens for Block {
             })
             .collect::<Vec<Path>>();
 
-        let register_bodies = self
-            .registers
+        // remapped blocks hold the entitlement to their mapping,
+        // as proof of the address they are located at
+        let (mapping_ty, mapping_field, mapping_move, reset_mapping_ty, mapping_bound) =
+            if self.args.remap.is_empty() {
+                (None, None, None, None, None)
+            } else {
+                (
+                    Some(format_ident!("Mapping")),
+                    Some(quote_spanned! { span =>
+                        /// The entitlement to the state mapping this block.
+                        pub mapping: Mapping,
+                    }),
+                    Some(quote_spanned! { span => mapping: self.mapping, }),
+                    Some(quote_spanned! { span => ::proto_hal::stasis::Unsatisfied, }),
+                    Some(quote_spanned! { span => Mapping: Mapped, }),
+                )
+            };
+        let mapping_param = mapping_ty
+            .as_ref()
+            .map(|ty| quote_spanned! { span => #ty, });
+        let mapping_yield = mapping_ty
+            .as_ref()
+            .map(|_| quote_spanned! { span => self.mapping, });
+
+        // warnings are emitted outside of the register so they are not allowed
+        let register_bodies = self.registers.iter().map(|register| {
+            let reserved_warning = self
+                .args
+                .warn_reserved
+                .then(|| register.maybe_generate_reserved_warning())
+                .flatten();
+            let reachability_warnings = self
+                .args
+                .warn_unreachable
+                .then(|| register.generate_reachability_warnings());
+
+            quote_spanned! { span =>
+                #register
+                #reserved_warning
+                #reachability_warnings
+            }
+        });
+
+        let cluster_bodies = self
+            .clusters
             .iter()
-            .map(|register| quote_spanned! { span => #register });
+            .map(|cluster| quote_spanned! { span => #cluster });
+
+        let facts = &self.facts;
+        let triggers = &self.triggers;
 
         let mut body = quote_spanned! { span =>
             #(
                 #register_bodies
             )*
 
-            /// The address of this block.
-            const BASE_ADDR: u32 = #base_addr;
+            #(
+                #cluster_bodies
+            )*
+
+            #(
+                #facts
+            )*
+
+            #(
+                #triggers
+            )*
+
+            #base_addr
 
             /// A register block. This type gates
             /// access to the registers it encapsulates.
             ///
-            /// Register members can be directly moved out of this struct
-            /// or modified in place with accessor methods.
+            /// Register and cluster members can be directly moved out of
+            /// this struct or modified in place with accessor methods.
             pub struct Block<
                 #(
                     #stateful_register_tys,
                 )*
 
+                #(
+                    #cluster_tys,
+                )*
+
+                #mapping_param
+
                 #(
                     #entitlement_tys,
                 )*
@@ -276,9 +2425,16 @@ impl ToTokens for Block {
 
                 // Stateless registers.
                 #(
-                    pub #stateless_register_idents: #stateless_register_idents::Register,
+                    pub #stateless_register_idents: #stateless_register_field_tys,
                 )*
 
+                // Clusters.
+                #(
+                    pub #cluster_idents: #cluster_tys,
+                )*
+
+                #mapping_field
+
                 #(
                     /// This entitlement is required to
                     /// use this block in any way.
@@ -288,9 +2444,15 @@ impl ToTokens for Block {
 
             pub type Reset = Block<
                 #(
-                    #stateful_register_idents::Reset,
+                    #stateful_register_reset_tys,
                 )*
 
+                #(
+                    #cluster_idents::Reset,
+                )*
+
+                #reset_mapping_ty
+
                 #(
                     #reset_entitlement_tys,
                 )*
@@ -312,6 +2474,94 @@ impl ToTokens for Block {
             }
         };
 
+        if let Some(default_state) = &self.args.default_mapping {
+            // this block in reset state, held by `mapping`
+            let mapped = |mapping: TokenStream2| {
+                quote_spanned! { span =>
+                    Block<
+                        #(#stateful_register_reset_tys,)*
+                        #(#cluster_idents::Reset,)*
+                        #mapping,
+                        #(#reset_entitlement_tys,)*
+                    >
+                }
+            };
+
+            let generic_ty = mapped(quote_spanned! { span => Mapping });
+            let default_ty =
+                mapped(quote_spanned! { span => ::proto_hal::stasis::Entitlement<#default_state> });
+            let gate_idents = self
+                .args
+                .remap
+                .iter()
+                .map(|remap| format_ident!("remap_{}", remap.name));
+            let gate_docs = self.args.remap.iter().map(|remap| {
+                format!(
+                    "Locate this block at [`{}_BASE_ADDR`].",
+                    inflector::cases::screamingsnakecase::to_screaming_snake_case(
+                        &remap.name.to_string()
+                    )
+                )
+            });
+            let states = self
+                .args
+                .remap
+                .iter()
+                .map(|remap| &remap.state)
+                .collect::<Vec<_>>();
+            let gate_tys = states.iter().map(|state| {
+                mapped(quote_spanned! { span => ::proto_hal::stasis::Entitlement<#state> })
+            });
+
+            body.extend(quote_spanned! { span =>
+                impl Reset {
+                    fn map<Mapping: Mapped>(self, mapping: Mapping) -> #generic_ty {
+                        MAPPED_BASE_ADDR.store(Mapping::BASE_ADDR, ::core::sync::atomic::Ordering::Relaxed);
+
+                        Block {
+                            #(
+                                #stateful_register_idents: self.#stateful_register_idents,
+                            )*
+
+                            #(
+                                #stateless_register_idents: self.#stateless_register_idents,
+                            )*
+
+                            #(
+                                #cluster_idents: self.#cluster_idents,
+                            )*
+
+                            mapping,
+
+                            #(
+                                #entitlement_idents: self.#entitlement_idents,
+                            )*
+                        }
+                    }
+
+                    /// Locate this block at [`BASE_ADDR`].
+                    ///
+                    /// The entitlement serves as proof of the active mapping,
+                    /// and is held by the block so the mapping cannot change
+                    /// for as long as the block is in use.
+                    pub fn map_default(self, mapping: ::proto_hal::stasis::Entitlement<#default_state>) -> #default_ty {
+                        self.map(mapping)
+                    }
+
+                    #(
+                        #[doc = #gate_docs]
+                        ///
+                        /// The entitlement serves as proof of the active mapping,
+                        /// and is held by the block so the mapping cannot change
+                        /// for as long as the block is in use.
+                        pub fn #gate_idents(self, mapping: ::proto_hal::stasis::Entitlement<#states>) -> #gate_tys {
+                            self.map(mapping)
+                        }
+                    )*
+                }
+            });
+        }
+
         let entitlements = self
             .entitlements
             .iter()
@@ -322,6 +2572,25 @@ impl ToTokens for Block {
             })
             .collect::<Vec<Path>>();
 
+        let generics = stateful_register_tys
+            .iter()
+            .chain(cluster_tys.iter())
+            .chain(mapping_ty.iter())
+            .collect::<Vec<_>>();
+        let bounds = mapping_bound.iter().cloned().collect::<Vec<_>>();
+
+        if let Some(embedded_hal) = &self.args.embedded_hal {
+            body.extend(embedded_hal.generate(
+                span,
+                &self.registers,
+                &generics,
+                &bounds,
+                &entitlements,
+            ));
+        }
+
+        body.extend(self.maybe_generate_config(&generics, &bounds, &entitlements));
+
         for (i, (ident, ty)) in stateful_register_idents
             .iter()
             .zip(stateful_register_tys.iter())
@@ -334,12 +2603,13 @@ impl ToTokens for Block {
             let next_register_tys = stateful_register_tys.get(i + 1..).unwrap();
 
             body.extend(quote_spanned! { span =>
-                impl<#(#stateful_register_tys,)*> Block<#(#stateful_register_tys,)* #(#entitlements,)*>
+                impl<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param> Block<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param #(#entitlements,)*>
                 where
                     #ty: ::proto_hal::macro_utils::AsBuilder,
+                    #mapping_bound
                 {
                     /// Access this register for in place modification.
-                    pub fn #ident<R, B>(self, f: impl FnOnce(#ty::Builder) -> B) -> Block<#(#prev_register_tys,)* R, #(#next_register_tys,)* #(#entitlements,)*>
+                    pub fn #ident<R, B>(self, f: impl FnOnce(#ty::Builder) -> B) -> Block<#(#prev_register_tys,)* R, #(#next_register_tys,)* #(#cluster_tys,)* #mapping_param #(#entitlements,)*>
                     where
                         B: ::proto_hal::macro_utils::AsRegister<Register = R>,
                     {
@@ -358,6 +2628,56 @@ impl ToTokens for Block {
                                 #stateless_register_idents: self.#stateless_register_idents,
                             )*
 
+                            #(
+                                #cluster_idents: self.#cluster_idents,
+                            )*
+
+                            #mapping_move
+
+                            #(
+                                #entitlement_idents: self.#entitlement_idents,
+                            )*
+                        }
+                    }
+                }
+            });
+        }
+
+        for (i, (ident, ty)) in cluster_idents.iter().zip(cluster_tys.iter()).enumerate() {
+            let prev_cluster_idents = cluster_idents.get(..i).unwrap();
+            let next_cluster_idents = cluster_idents.get(i + 1..).unwrap();
+
+            let prev_cluster_tys = cluster_tys.get(..i).unwrap();
+            let next_cluster_tys = cluster_tys.get(i + 1..).unwrap();
+
+            body.extend(quote_spanned! { span =>
+                impl<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param> Block<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param #(#entitlements,)*>
+                where
+                    #mapping_bound
+                {
+                    /// Access this cluster for in place modification.
+                    pub fn #ident<C>(self, f: impl FnOnce(#ty) -> C) -> Block<#(#stateful_register_tys,)* #(#prev_cluster_tys,)* C, #(#next_cluster_tys,)* #mapping_param #(#entitlements,)*> {
+                        Block {
+                            #(
+                                #stateful_register_idents: self.#stateful_register_idents,
+                            )*
+
+                            #(
+                                #stateless_register_idents: self.#stateless_register_idents,
+                            )*
+
+                            #(
+                                #prev_cluster_idents: self.#prev_cluster_idents,
+                            )*
+
+                            #ident: f(self.#ident),
+
+                            #(
+                                #next_cluster_idents: self.#next_cluster_idents,
+                            )*
+
+                            #mapping_move
+
                             #(
                                 #entitlement_idents: self.#entitlement_idents,
                             )*
@@ -367,11 +2687,89 @@ impl ToTokens for Block {
             });
         }
 
+        let reset_register_idents = self
+            .registers
+            .iter()
+            .filter(|register| register.is_writable())
+            .map(|register| &register.ident);
+
+        body.extend(quote_spanned! { span =>
+            /// Write the reset values of all writable fields within
+            /// this block, preserving those whose reset is unknown.
+            pub(super) unsafe fn write_resets() {
+                #(
+                    #reset_register_idents::write_release();
+                )*
+
+                #(
+                    #cluster_idents::write_resets();
+                )*
+            }
+        });
+
+        // clusters are released with their parent block
+        if self.offset.is_none() {
+            body.extend(quote_spanned! { span =>
+                impl<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param> Block<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param #(#entitlements,)*>
+                where
+                    #mapping_bound
+                {
+                    /// Return this block to its reset state, writing the
+                    /// reset values of all writable fields, and yield the
+                    /// entitlements it was attached to, followed by its
+                    /// mapping (if remapped).
+                    ///
+                    /// The entitlements include the state enabling the
+                    /// block (i.e. the clock enable of the block), so the
+                    /// frozen enable can be released and disabled after
+                    /// tear-down.
+                    ///
+                    /// *Note: Fields without a described reset (or register
+                    /// reset value) hold their values.*
+                    pub fn release(self) -> (Reset, (#(#entitlements,)* #mapping_param)) {
+                        // SAFETY: the block is owned, and all fields are
+                        // returned to their reset values
+                        let reset = unsafe {
+                            write_resets();
+
+                            Reset::conjure()
+                        };
+
+                        (reset, (#(self.#entitlement_idents,)* #mapping_yield))
+                    }
+                }
+            });
+        }
+
+        body.extend(self.generate_pair_accessors());
+        body.extend(self.generate_depth());
+        body.extend(self.maybe_generate_power_domain());
+        body.extend(self.entitlement_groups.maybe_generate_traits());
+        body.extend(self.maybe_generate_non_secure_attribution());
+        body.extend(self.generate_current_state());
+        body.extend(self.generate_introspection());
+        body.extend(self.maybe_generate_format_impls());
+        body.extend(self.generate_plugin_invocations());
+
+        // the power domain is the last entitlement of the block
+        if let (Some(_), Some(domain_ident)) = (&self.args.power_domain, entitlement_idents.last())
+        {
+            body.extend(quote_spanned! { span =>
+                impl<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param> Block<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param #(#entitlements,)*> {
+                    /// The token of the power domain this block resides in,
+                    /// held for as long as the block is attached.
+                    pub fn power_domain(&self) -> &::proto_hal::stasis::Entitlement<PowerDomain> {
+                        &self.#domain_ident
+                    }
+                }
+            });
+        }
+
         if !self.entitlements.is_empty() {
             body.extend(quote_spanned! { span =>
-                impl<#(#stateful_register_tys,)*> Block<#(#stateful_register_tys,)* #(#reset_entitlement_tys,)*> {
+                impl<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param> Block<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param #(#reset_entitlement_tys,)*> {
                     /// Attach to required entitlements, enabling usage of this block.
-                    pub fn attach(self, #(#entitlement_idents: #entitlements,)*) -> Block<#(#stateful_register_tys,)* #(#entitlements,)*> {
+                    pub fn attach(self, #(#entitlement_idents: #entitlements,)*) -> Block<#(#stateful_register_tys,)* #(#cluster_tys,)* #mapping_param #(#entitlements,)*> {
                         Block {
                             #(
                                 #stateful_register_idents: self.#stateful_register_idents,
@@ -381,6 +2779,12 @@ impl ToTokens for Block {
                                 #stateless_register_idents: self.#stateless_register_idents,
                             )*
 
+                            #(
+                                #cluster_idents: self.#cluster_idents,
+                            )*
+
+                            #mapping_move
+
                             #(
                                 #entitlement_idents,
                             )*
@@ -392,10 +2796,39 @@ impl ToTokens for Block {
 
         let vis = &self.vis;
 
+        let register_map = self.register_map();
+
+        body.extend(quote_spanned! { span =>
+            /// The register map of this block, in markdown.
+            pub const REGISTER_MAP: &str = #register_map;
+        });
+
         tokens.extend(if self.args.erase_mod {
             body
+        } else if let Some(instances) = self.args.instances {
+            // each instance resides in a module of its own, so it
+            // owns the address it is located at, as any other block
+            let instance_idents = (0..instances).map(|i| format_ident!("instance{}", i));
+
+            quote_spanned! { span =>
+                #[doc = #register_map]
+                #vis mod #ident {
+                    // paths relative to the instances resolve
+                    // as if the instance were the block
+                    #[allow(unused_imports)]
+                    use super::*;
+
+                    #(
+                        #[doc = #register_map]
+                        pub mod #instance_idents {
+                            #body
+                        }
+                    )*
+                }
+            }
         } else {
             quote_spanned! { span =>
+                #[doc = #register_map]
                 #vis mod #ident {
                     #body
                 }
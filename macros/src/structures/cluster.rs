@@ -0,0 +1,20 @@
+use darling::FromMeta;
+
+use crate::utils::{AllowArgs, PathArray, RegisterOffset};
+
+use super::Args;
+
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct ClusterArgs {
+    pub offset: Option<RegisterOffset>,
+    pub entitlements: PathArray,
+    pub allow: AllowArgs,
+
+    #[darling(default)]
+    pub auto_increment: bool,
+}
+
+impl Args for ClusterArgs {
+    const NAME: &str = "cluster";
+}
@@ -0,0 +1,251 @@
+use darling::FromMeta;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote_spanned;
+use syn::{Ident, Path};
+
+use crate::utils::SynErrorCombinator;
+
+use super::{field::Field, register::Register, schema::Numericity};
+
+/// Roles a block can fulfill, for which `embedded-hal`
+/// trait implementations are generated.
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct EmbeddedHalArgs {
+    pub spi: Option<SpiArgs>,
+}
+
+/// The fields (`register::field`) of a block operating as an SPI bus.
+#[derive(Debug, Clone, FromMeta)]
+pub struct SpiArgs {
+    /// The 8-bit data field, written to transmit and read to receive.
+    pub data: Path,
+    /// Set when the data field can accept a new word.
+    pub tx_ready: Path,
+    /// Set when the data field holds a received word.
+    pub rx_ready: Path,
+    /// Set while a transfer is in progress.
+    pub busy: Option<Path>,
+}
+
+/// A field referenced by a role, located within the block.
+struct FieldRef<'a> {
+    register: &'a Ident,
+    field: &'a Field,
+}
+
+impl<'a> FieldRef<'a> {
+    fn find(path: &Path, registers: &'a [Register]) -> syn::Result<Self> {
+        let [register, field] = path.segments.iter().collect::<Vec<_>>()[..] else {
+            Err(syn::Error::new_spanned(
+                path,
+                "expected a path of the form `register::field`",
+            ))?
+        };
+
+        let register = registers
+            .iter()
+            .find(|candidate| candidate.ident == register.ident)
+            .ok_or(syn::Error::new_spanned(register, "register does not exist"))?;
+
+        let field = register
+            .fields
+            .iter()
+            .find(|candidate| candidate.ident == field.ident)
+            .ok_or(syn::Error::new_spanned(field, "field does not exist"))?;
+
+        Ok(Self {
+            register: &register.ident,
+            field,
+        })
+    }
+
+    fn require(
+        self,
+        path: &Path,
+        width: u8,
+        read: bool,
+        write: bool,
+        msg: &str,
+    ) -> syn::Result<Self> {
+        let numeric = self
+            .field
+            .read_schema()
+            .or(self.field.write_schema())
+            .is_some_and(|schema| matches!(schema.numericity, Numericity::Numeric));
+
+        if !numeric
+            || self.field.width() != width
+            || (read && !self.field.access.is_read())
+            || (write && !self.field.access.is_write())
+        {
+            Err(syn::Error::new_spanned(path, msg))?
+        }
+
+        Ok(self)
+    }
+}
+
+impl EmbeddedHalArgs {
+    pub fn validate(&self, registers: &[Register]) -> syn::Result<()> {
+        let mut errors = SynErrorCombinator::new();
+
+        if let Some(spi) = &self.spi {
+            errors.maybe(|| spi.fields(registers).map(|_| ()));
+        }
+
+        errors.coalesce()
+    }
+
+    /// Generate the trait implementations for the block type
+    /// with the provided generics and entitlements.
+    pub fn generate(
+        &self,
+        span: Span,
+        registers: &[Register],
+        generics: &[&Ident],
+        bounds: &[TokenStream2],
+        entitlements: &[Path],
+    ) -> TokenStream2 {
+        let mut body = TokenStream2::new();
+
+        // roles are validated ahead of time
+        if let Some(Ok(fields)) = self.spi.as_ref().map(|spi| spi.fields(registers)) {
+            body.extend(fields.generate(span, generics, bounds, entitlements));
+        }
+
+        if body.is_empty() {
+            return body;
+        }
+
+        quote_spanned! { span =>
+            ::proto_hal::__embedded_hal! {
+                #body
+            }
+        }
+    }
+}
+
+struct SpiFields<'a> {
+    data: FieldRef<'a>,
+    tx_ready: FieldRef<'a>,
+    rx_ready: FieldRef<'a>,
+    busy: Option<FieldRef<'a>>,
+}
+
+impl SpiArgs {
+    fn fields<'a>(&self, registers: &'a [Register]) -> syn::Result<SpiFields<'a>> {
+        let flag = |path| {
+            FieldRef::find(path, registers)?.require(
+                path,
+                1,
+                true,
+                false,
+                "SPI flags must be readable single-bit numeric fields",
+            )
+        };
+
+        Ok(SpiFields {
+            data: FieldRef::find(&self.data, registers)?.require(
+                &self.data,
+                8,
+                true,
+                true,
+                "SPI data must be a readable and writable 8-bit numeric field",
+            )?,
+            tx_ready: flag(&self.tx_ready)?,
+            rx_ready: flag(&self.rx_ready)?,
+            busy: self.busy.as_ref().map(flag).transpose()?,
+        })
+    }
+}
+
+impl SpiFields<'_> {
+    fn generate(
+        &self,
+        span: Span,
+        generics: &[&Ident],
+        bounds: &[TokenStream2],
+        entitlements: &[Path],
+    ) -> TokenStream2 {
+        let (data_register, data_field) = (self.data.register, &self.data.field.ident);
+        let (tx_register, tx_field) = (self.tx_ready.register, &self.tx_ready.field.ident);
+        let (rx_register, rx_field) = (self.rx_ready.register, &self.rx_ready.field.ident);
+
+        let flush = self.busy.as_ref().map(|busy| {
+            let (busy_register, busy_field) = (busy.register, &busy.field.ident);
+
+            quote_spanned! { span =>
+                // SAFETY: the block is owned
+                while unsafe { #busy_register::read() }.#busy_field() {}
+            }
+        });
+
+        quote_spanned! { span =>
+            /// Transmit a word and receive the word clocked in.
+            fn spi_exchange(word: u8) -> u8 {
+                // SAFETY: the block is owned by the caller
+                unsafe {
+                    while !#tx_register::read().#tx_field() {}
+                    #data_register::write(|w| w.#data_field(word));
+                    while !#rx_register::read().#rx_field() {}
+                    #data_register::read().#data_field()
+                }
+            }
+
+            impl<#(#generics,)*> ::proto_hal::embedded_hal::spi::ErrorType for Block<#(#generics,)* #(#entitlements,)*>
+            where
+                #(#bounds)*
+            {
+                type Error = ::core::convert::Infallible;
+            }
+
+            impl<#(#generics,)*> ::proto_hal::embedded_hal::spi::SpiBus<u8> for Block<#(#generics,)* #(#entitlements,)*>
+            where
+                #(#bounds)*
+            {
+                fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+                    for word in words {
+                        *word = spi_exchange(0);
+                    }
+
+                    Ok(())
+                }
+
+                fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+                    for word in words {
+                        spi_exchange(*word);
+                    }
+
+                    Ok(())
+                }
+
+                fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+                    for i in 0..read.len().max(write.len()) {
+                        let word = spi_exchange(write.get(i).copied().unwrap_or(0));
+
+                        if let Some(slot) = read.get_mut(i) {
+                            *slot = word;
+                        }
+                    }
+
+                    Ok(())
+                }
+
+                fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+                    for word in words {
+                        *word = spi_exchange(*word);
+                    }
+
+                    Ok(())
+                }
+
+                fn flush(&mut self) -> Result<(), Self::Error> {
+                    #flush
+
+                    Ok(())
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use darling::FromMeta;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use syn::{
+    parse::Parser as _, punctuated::Punctuated, Attribute, Expr, ExprPath, Ident, Item, Meta, Path,
+    Token,
+};
+
+use crate::utils::{extract_items_from, PathArray};
+
+use super::Args;
+
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct EntitlementGroupArgs {
+    pub entitlements: PathArray,
+}
+
+impl Args for EntitlementGroupArgs {
+    const NAME: &str = "entitlement_group";
+}
+
+/// Named sets of entitlements defined within a block.
+///
+/// Any `entitlements` list within the block (or its clusters)
+/// may refer to a group by name, which expands to the members
+/// of the group. Member paths are interpreted relative to where
+/// the group is used, not where it is defined.
+///
+/// Each group defined by a block is also a marker trait of the
+/// `entitlement_groups` module of the block (i.e. `Clocked` for
+/// `clocked`), implemented by the members of the group, so generic
+/// code can bound on the group. The module is a sibling of the
+/// registers, so the members are interpreted as from a register.
+///
+/// *Note: Groups with members local to a register
+/// (i.e. `field::Variant`) have no trait.*
+#[derive(Debug, Clone, Default)]
+pub struct EntitlementGroups {
+    groups: HashMap<Ident, Vec<Path>>,
+    /// The groups defined by the block (as opposed to its
+    /// parent), in order of definition.
+    local: Vec<Ident>,
+}
+
+impl EntitlementGroups {
+    /// Collect the groups defined by the provided items, in addition to
+    /// the groups already known (i.e. inherited from a parent block).
+    ///
+    /// Groups may refer to groups defined before them.
+    pub fn extend_from<'a>(&mut self, items: impl Iterator<Item = &'a Item>) -> syn::Result<()> {
+        for item in items {
+            let Item::Mod(module) = item else {
+                continue;
+            };
+
+            let Some(args) = EntitlementGroupArgs::get(module.attrs.iter())? else {
+                continue;
+            };
+
+            if !extract_items_from(module)?.is_empty() {
+                Err(syn::Error::new_spanned(
+                    module,
+                    "entitlement groups cannot contain items",
+                ))?
+            }
+
+            if self.groups.contains_key(&module.ident) {
+                Err(syn::Error::new_spanned(
+                    &module.ident,
+                    "entitlement group exists already",
+                ))?
+            }
+
+            let members = self.expand(args.entitlements.elems.iter().cloned());
+
+            if members.is_empty() {
+                Err(syn::Error::new(
+                    args.span(),
+                    "entitlement groups must contain at least one entitlement",
+                ))?
+            }
+
+            self.groups.insert(module.ident.clone(), members);
+            self.local.push(module.ident.clone());
+        }
+
+        Ok(())
+    }
+
+    /// The groups visible to a cluster of the block,
+    /// which are not defined by the cluster.
+    pub fn inherited(&self) -> Self {
+        Self {
+            groups: self.groups.clone(),
+            local: Vec::new(),
+        }
+    }
+
+    /// Whether the block defines no entitlement groups.
+    pub fn is_empty(&self) -> bool {
+        self.local.is_empty()
+    }
+
+    /// Generate the marker traits of the groups defined by the block.
+    pub fn maybe_generate_traits(&self) -> Option<TokenStream2> {
+        let traits = self
+            .local
+            .iter()
+            .filter_map(|ident| {
+                let members = &self.groups[ident];
+
+                // local members are not visible outside of their register
+                if !members.iter().all(|path| {
+                    path.segments
+                        .first()
+                        .is_some_and(|segment| segment.ident == "super")
+                }) {
+                    return None;
+                }
+
+                let trait_ident = Ident::new(
+                    &inflector::cases::pascalcase::to_pascal_case(&ident.to_string()),
+                    Span::call_site(),
+                );
+                let doc = format!(
+                    "States which satisfy the entitlement group `{ident}`, one of which is required wherever the group is listed."
+                );
+
+                Some(quote! {
+                    #[doc = #doc]
+                    pub trait #trait_ident: ::proto_hal::stasis::Freeze {}
+
+                    #(
+                        impl #trait_ident for #members {}
+                    )*
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if traits.is_empty() {
+            return None;
+        }
+
+        Some(quote! {
+            /// Marker traits of the entitlement groups of this block.
+            pub mod entitlement_groups {
+                #(#traits)*
+            }
+        })
+    }
+
+    /// Replace any references to groups with the members of the group.
+    pub fn expand(&self, paths: impl Iterator<Item = Path>) -> Vec<Path> {
+        paths
+            .flat_map(|path| {
+                path.get_ident()
+                    .and_then(|ident| self.groups.get(ident))
+                    .cloned()
+                    .unwrap_or_else(|| vec![path])
+            })
+            .collect()
+    }
+
+    /// Expand group references in the `entitlements` lists of
+    /// the attributes of the provided items (recursively).
+    pub fn expand_items(&self, items: &[Item]) -> syn::Result<Vec<Item>> {
+        let mut items = items.to_vec();
+
+        if !self.groups.is_empty() {
+            for item in &mut items {
+                self.expand_item(item)?;
+            }
+        }
+
+        Ok(items)
+    }
+
+    fn expand_item(&self, item: &mut Item) -> syn::Result<()> {
+        match item {
+            Item::Mod(module) => {
+                self.expand_attrs(&mut module.attrs)?;
+
+                if let Some((_, items)) = &mut module.content {
+                    for item in items {
+                        self.expand_item(item)?;
+                    }
+                }
+            }
+            Item::Struct(s) => self.expand_attrs(&mut s.attrs)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn expand_attrs(&self, attrs: &mut [Attribute]) -> syn::Result<()> {
+        for attr in attrs {
+            if attr.path().is_ident(EntitlementGroupArgs::NAME) {
+                continue;
+            }
+
+            if let Meta::List(list) = &mut attr.meta {
+                self.expand_meta_list(list)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn expand_meta_list(&self, list: &mut syn::MetaList) -> syn::Result<()> {
+        // attributes which are not lists of metas are not ours to expand
+        let Ok(mut metas) =
+            Punctuated::<Meta, Token![,]>::parse_terminated.parse2(list.tokens.clone())
+        else {
+            return Ok(());
+        };
+
+        for meta in &mut metas {
+            match meta {
+                Meta::NameValue(name_value) if name_value.path.is_ident("entitlements") => {
+                    if let Expr::Array(array) = &mut name_value.value {
+                        let paths = array
+                            .elems
+                            .iter()
+                            .map(|expr| match expr {
+                                Expr::Path(path) => Ok(path.path.clone()),
+                                _ => Err(syn::Error::new_spanned(expr, "expected path")),
+                            })
+                            .collect::<syn::Result<Vec<_>>>()?;
+
+                        array.elems = self
+                            .expand(paths.into_iter())
+                            .into_iter()
+                            .map(|path| {
+                                Expr::Path(ExprPath {
+                                    attrs: Vec::new(),
+                                    qself: None,
+                                    path,
+                                })
+                            })
+                            .collect();
+                    }
+                }
+                Meta::List(list) => self.expand_meta_list(list)?,
+                _ => {}
+            }
+        }
+
+        list.tokens = metas.into_token_stream();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use darling::FromMeta;
+use quote::{quote_spanned, ToTokens};
+use syn::{Ident, ItemMod, Path};
+
+use crate::utils::{extract_items_from, Spanned};
+
+use super::{
+    register::{validate_read_variant, Register},
+    Args,
+};
+
+/// An invariant which is not held by a register (i.e. option bytes,
+/// boot pins, package variant), represented by a token which can
+/// participate in entitlements (`fact::Fact`).
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default, from_word = || Ok(Self::default()))]
+pub struct FactArgs {
+    /// A variant of a readable field (`register::field::Variant`)
+    /// which, when read, indicates the fact holds.
+    pub probe: Option<Path>,
+}
+
+impl Args for FactArgs {
+    const NAME: &str = "fact";
+}
+
+#[derive(Debug)]
+pub struct Fact {
+    pub args: Spanned<FactArgs>,
+    pub ident: Ident,
+}
+
+impl Fact {
+    pub fn parse(module: &ItemMod, args: Spanned<FactArgs>) -> syn::Result<Self> {
+        if !extract_items_from(module)?.is_empty() {
+            Err(syn::Error::new_spanned(
+                module,
+                "facts cannot contain items",
+            ))?
+        }
+
+        Ok(Self {
+            args,
+            ident: module.ident.clone(),
+        })
+    }
+
+    /// Validate the probe of this fact refers to a variant
+    /// of a readable field within the provided registers.
+    pub fn validate(&self, registers: &[Register]) -> syn::Result<()> {
+        let Some(probe) = &self.args.probe else {
+            return Ok(());
+        };
+
+        validate_read_variant(registers, probe)
+    }
+}
+
+impl ToTokens for Fact {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = self.args.span();
+        let ident = &self.ident;
+
+        // probes are validated ahead of time
+        let probe = self.args.probe.as_ref().map(|probe| {
+            let segments = probe.segments.iter().collect::<Vec<_>>();
+            let (register, field, variant) = (
+                &segments[0].ident,
+                &segments[1].ident,
+                &segments[2].ident,
+            );
+
+            quote_spanned! { span =>
+                /// Produce the token of this fact only if it is
+                /// determined to hold at runtime.
+                pub fn probe() -> Option<Self> {
+                    // SAFETY: the field is only read
+                    let value = unsafe { super::#register::read() }.#field();
+
+                    (value == super::#register::#field::ReadVariant::#variant).then_some(Self { _sealed: () })
+                }
+            }
+        });
+
+        tokens.extend(quote_spanned! { span =>
+            pub mod #ident {
+                /// The token of this fact.
+                pub struct Fact {
+                    _sealed: (),
+                }
+
+                impl ::proto_hal::stasis::Freeze for Fact {}
+
+                impl Fact {
+                    /// Conjure the token of this fact.
+                    ///
+                    /// # Safety
+                    ///
+                    /// The fact must hold for the lifetime
+                    /// of the program.
+                    pub unsafe fn conjure() -> Self {
+                        Self { _sealed: () }
+                    }
+
+                    #probe
+                }
+            }
+        });
+    }
+}
@@ -1,14 +1,19 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+};
 
 use darling::{util::SpannedValue, FromMeta};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote, quote_spanned, ToTokens};
-use syn::{Expr, Ident, Item};
-use tiva::Validator;
+use syn::{parse_quote, Expr, ExprLit, ExprRange, Ident, Item, Lit, Path};
 
 use crate::{
-    access::{Access, AccessArgs},
-    utils::{FieldOffset, Spanned, SynErrorCombinator, Width},
+    access::{Access, AccessArgs, Read, Write, WriteEffect},
+    utils::{
+        parse_expr_range, sorted_paths, AllowArgs, FieldOffset, PathArray, Spanned,
+        SynErrorCombinator, Validator, Width,
+    },
 };
 
 use super::{
@@ -17,6 +22,57 @@ use super::{
     Args,
 };
 
+/// Constraints on values written to numeric fields.
+///
+/// Safe writers panic on values violating the constraints,
+/// fallible `try_` writers are generated alongside them.
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct ConstraintArgs {
+    /// The range of permitted values.
+    pub range: Option<ExprRange>,
+    /// Values must be a multiple of this value.
+    pub multiple_of: Option<u32>,
+    /// Values must not be zero.
+    #[darling(default)]
+    pub non_zero: bool,
+}
+
+/// The unit numeric field values are expressed in.
+///
+/// Values are `raw * scale`, where the scale is either
+/// provided directly or `2^-fractional` for fixed-point
+/// values (i.e. `fractional = 15, signed` for q1.15).
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct UnitArgs {
+    pub scale: Option<f64>,
+    pub fractional: Option<u8>,
+    /// The raw value is two's complement.
+    pub signed: bool,
+    /// The symbol of the unit (i.e. `"°C"`).
+    pub symbol: Option<String>,
+}
+
+impl UnitArgs {
+    pub fn scale(&self) -> f64 {
+        match (self.scale, self.fractional) {
+            (Some(scale), _) => scale,
+            (None, Some(fractional)) => 2f64.powi(-(fractional as i32)),
+            (None, None) => 1.,
+        }
+    }
+}
+
+/// Marks a field as part of the configuration of its block
+/// (`Config`), optionally under another name (i.e.
+/// `config(name = prescaler)`).
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default, from_word = || Ok(Self::default()))]
+pub struct ConfigArgs {
+    pub name: Option<Ident>,
+}
+
 #[derive(Debug, Clone, Default, FromMeta)]
 pub struct FieldArgs {
     pub offset: Option<FieldOffset>,
@@ -25,6 +81,39 @@ pub struct FieldArgs {
     pub read: Option<SpannedValue<AccessArgs>>,
     pub write: Option<SpannedValue<AccessArgs>>,
     pub reset: Option<Expr>,
+    pub constraints: Option<SpannedValue<ConstraintArgs>>,
+    #[darling(default)]
+    pub allow: AllowArgs,
+    /// The width of the access (in bits) the field must be
+    /// written with alone (i.e. the low byte of a data register
+    /// triggering byte transfers).
+    pub access_size: Option<SpannedValue<Width>>,
+    pub unit: Option<SpannedValue<UnitArgs>>,
+    /// The field is deprecated, with a note on
+    /// migration (i.e. `"use \`foo\` instead"`).
+    pub deprecated: Option<String>,
+    /// The hardware may change the value of the field on its own
+    /// (i.e. a status written by software and updated by hardware),
+    /// so the written value is not tracked.
+    #[darling(default)]
+    pub volatile: bool,
+    /// The field is read and written by value rather than resolved
+    /// with type-states. Required of numeric fields which are read
+    /// and written, as their values are far too many to be tracked
+    /// with type-states.
+    #[darling(default)]
+    pub by_value: bool,
+    pub config: Option<SpannedValue<ConfigArgs>>,
+    /// Fields of the same register which must be written in a
+    /// separate, earlier access when changed together with this
+    /// field (i.e. `write_after = [en]` for a configuration which
+    /// must not change in the same access as the enable).
+    pub write_after: Option<SpannedValue<PathArray>>,
+    /// The field is a flag (i.e. the pending status of an interrupt
+    /// `#[source]`), generating raw accessors to query it
+    /// (`is_<field>_set`) and clear it (`clear_<field>`).
+    #[darling(default)]
+    pub flag: bool,
 
     #[darling(default)]
     pub auto_increment: bool,
@@ -67,6 +156,8 @@ pub enum Resolvability {
 pub struct FieldSpec {
     pub args: Spanned<FieldArgs>,
     pub ident: Ident,
+    /// The doc comments of this field as described.
+    pub docs: Vec<String>,
     pub offset: FieldOffset,
     pub access: Access,
 
@@ -92,6 +183,7 @@ impl Deref for Field {
 impl FieldSpec {
     pub fn parse<'a>(
         ident: Ident,
+        docs: Vec<String>,
         offset: FieldOffset,
         schemas: &HashMap<Ident, Schema>,
         mut args: Spanned<FieldArgs>,
@@ -139,7 +231,7 @@ impl FieldSpec {
             "fields must be readable or writable",
         ))?;
 
-        Self::new(args, ident, offset, access)
+        Self::new(args, ident, docs, offset, access)
     }
 }
 
@@ -147,6 +239,7 @@ impl FieldSpec {
     pub fn new(
         args: Spanned<FieldArgs>,
         ident: Ident,
+        docs: Vec<String>,
         offset: FieldOffset,
         access: Access,
     ) -> Result<Self, syn::Error> {
@@ -156,6 +249,7 @@ impl FieldSpec {
         Ok(Self {
             args,
             ident,
+            docs,
             offset,
             access,
 
@@ -172,6 +266,199 @@ impl FieldSpec {
         self.width
     }
 
+    /// The schema of this field when read (if readable).
+    pub fn read_schema(&self) -> Option<&Schema> {
+        match &self.access {
+            Access::Read(read) | Access::ReadWrite { read, write: _ } => Some(&read.schema),
+            Access::Write(_) => None,
+        }
+    }
+
+    /// The schema of this field when written (if writable).
+    pub fn write_schema(&self) -> Option<&Schema> {
+        match &self.access {
+            Access::Write(write) | Access::ReadWrite { read: _, write } => Some(&write.schema),
+            Access::Read(_) => None,
+        }
+    }
+
+    /// The states of other fields which entitle access to this field.
+    pub fn access_entitlements(&self) -> HashSet<&Path> {
+        match &self.access {
+            Access::Read(read) => read.entitlements.iter().collect(),
+            Access::Write(write) => write.entitlements.iter().collect(),
+            Access::ReadWrite { read, write } => {
+                read.entitlements.union(&write.entitlements).collect()
+            }
+        }
+    }
+
+    /// The states of other fields which entitle writing this field (if any).
+    pub fn write_entitlements(&self) -> Option<&HashSet<Path>> {
+        match &self.access {
+            Access::Write(write) | Access::ReadWrite { read: _, write } => {
+                Some(&write.entitlements).filter(|entitlements| !entitlements.is_empty())
+            }
+            Access::Read(_) => None,
+        }
+    }
+
+    /// The parameter of writer methods requiring an entitlement to
+    /// a state which entitles writing this field (if any).
+    pub fn write_entitlement_param(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+        let ident = &self.ident;
+
+        self.write_entitlements()?;
+
+        Some(quote_spanned! { span =>
+            _entitlement: &::proto_hal::stasis::Entitlement<impl #ident::WriteMode>,
+        })
+    }
+
+    /// The field whose disjoint states entitle this field and the
+    /// other field, such that the two can never be accessed at the
+    /// same time (i.e. the same bits interpreted per mode), if any.
+    pub fn exclusive_selector<'a>(&'a self, other: &Self) -> Option<&'a Ident> {
+        let lhs = self.access_entitlements();
+        let rhs = other.access_entitlements();
+
+        fn states_of<'a>(entitlements: &HashSet<&'a Path>, field: &Ident) -> HashSet<&'a Path> {
+            entitlements
+                .iter()
+                .filter(|path| entitled_field(path) == Some(field))
+                .copied()
+                .collect()
+        }
+
+        lhs.iter()
+            .filter_map(|path| entitled_field(path))
+            .find(|field| {
+                let rhs_states = states_of(&rhs, field);
+
+                !rhs_states.is_empty() && states_of(&lhs, field).is_disjoint(&rhs_states)
+            })
+    }
+
+    /// The states of the field `selector` of the same register
+    /// which entitle access to this field, by identifier.
+    pub fn selecting_states<'a>(&'a self, selector: &Ident) -> HashSet<&'a Ident> {
+        self.access_entitlements()
+            .into_iter()
+            .filter_map(|path| match path.segments.iter().collect::<Vec<_>>()[..] {
+                [field, state] if &field.ident == selector => Some(&state.ident),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The raw (unshifted) reset value of this field, if the reset
+    /// is a variant of the field or an integer literal.
+    pub fn reset_bits(&self) -> Option<u32> {
+        match self.args.reset.as_ref()? {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => int.base10_parse().ok(),
+            Expr::Path(path) => {
+                let ident = path.path.get_ident()?;
+
+                [self.read_schema(), self.write_schema()]
+                    .into_iter()
+                    .flatten()
+                    .find_map(|schema| match &schema.numericity {
+                        Numericity::Enumerated { variants } => variants
+                            .iter()
+                            .find(|variant| &variant.ident == ident)
+                            .map(|variant| variant.bits),
+                        Numericity::Numeric => None,
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    /// The fields of the same register which must be written
+    /// before this field, by path as described.
+    pub fn write_after(&self) -> impl Iterator<Item = &Path> {
+        self.args
+            .write_after
+            .iter()
+            .flat_map(|write_after| write_after.elems.iter())
+    }
+
+    /// The bits occupied by this field within the register.
+    pub fn mask(&self) -> u32 {
+        (u32::MAX >> (32 - self.width)) << self.offset
+    }
+
+    /// The value written to clear this field when it is a flag
+    /// (i.e. the pending status of an interrupt), as dictated by
+    /// its write effect, or the sole variant of its write schema.
+    pub fn flag_clear_bits(&self) -> Option<u32> {
+        if self.width != 1 {
+            return None;
+        }
+
+        match self.access.write_effect() {
+            Some(WriteEffect::OneToClear) => Some(1),
+            Some(WriteEffect::ZeroToClear) => Some(0),
+            _ => match &self.write_schema()?.numericity {
+                Numericity::Enumerated { variants } => match variants.as_slice() {
+                    [variant] if variant.range.is_none() => Some(variant.bits),
+                    _ => None,
+                },
+                Numericity::Numeric => None,
+            },
+        }
+    }
+
+    /// The type used to represent numeric values of this field.
+    pub fn value_ty(&self) -> Path {
+        let ident = format_ident!("u{}", self.width);
+
+        match self.width {
+            1 => parse_quote! { bool },
+            8 | 16 | 32 => parse_quote! { #ident },
+            _ => parse_quote! { ::proto_hal::macro_utils::arbitrary_int::#ident },
+        }
+    }
+
+    /// The `RegisterValue` method used to extract numeric values of this field.
+    pub fn value_getter(&self) -> Ident {
+        match self.width {
+            1 => format_ident!("bool"),
+            _ => format_ident!("u{}", self.width),
+        }
+    }
+
+    /// Convert an expression of this field's numeric value type
+    /// to its raw (unshifted) representation.
+    pub fn value_to_raw(&self, value: &Expr) -> TokenStream2 {
+        match self.width {
+            1 | 8 | 16 | 32 => quote! { (#value as u32) },
+            _ => quote! { (#value.value() as u32) },
+        }
+    }
+
+    /// Assert that a raw (unshifted) value written to this field,
+    /// declared at `path`, satisfies the constraints of the field (if any).
+    ///
+    /// *Note: This is enforced in all builds, as values violating
+    /// the constraints must never reach the hardware through safe writers.*
+    pub fn maybe_generate_constraint_assertion(
+        &self,
+        path: &TokenStream2,
+        raw: &TokenStream2,
+    ) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        self.args.constraints.as_ref().map(|_| {
+            quote_spanned! { span =>
+                assert!(#path::check(#raw).is_ok(), "value violates field constraints");
+            }
+        })
+    }
+
     fn compute_width(access: &Access) -> Width {
         match access {
             Access::Read(read) => read.schema.width,
@@ -222,10 +509,39 @@ impl FieldSpec {
         This is NOT to be confused with statefulness. Fields
         can be stateful and unresolvable. Some peripheral states
         simply may be too dynamic to be tracked statically.
+
+        Numeric fields are one such case, their state space
+        is far too large to be represented with type-states,
+        so they are read and written by value instead. This
+        must be opted into with `by_value`, so descriptions
+        do not lose their type-states unknowingly.
+
+        Fields with access effects (i.e. clear on read/write)
+        are another, as their state changes out from under
+        any type-state representing it. The same goes for
+        fields with variants the hardware enters on its own.
         */
 
         Ok(if let Access::ReadWrite { read, write } = access {
-            if read.schema == write.schema {
+            let hardware = matches!(
+                &read.schema.numericity,
+                Numericity::Enumerated { variants } if variants.iter().any(|variant| variant.args.hardware)
+            );
+
+            let trackable = !args.volatile
+                && !hardware
+                && read.schema == write.schema
+                && read.effect.is_none()
+                && write.effect.is_none();
+
+            if trackable && read.schema.numericity.is_numeric() && !args.by_value {
+                Err(syn::Error::new(
+                    args.span(),
+                    "numeric fields which are read and written cannot be resolved, specify `by_value` to read and write the field by value",
+                ))?
+            }
+
+            if trackable && !args.by_value {
                 Resolvability::Resolvable {
                     reset: args.reset.clone().ok_or(syn::Error::new(
                         args.span(),
@@ -241,6 +557,14 @@ impl FieldSpec {
     }
 }
 
+/// The field a state entitling access is of.
+fn entitled_field(path: &Path) -> Option<&Ident> {
+    path.segments
+        .iter()
+        .nth_back(1)
+        .map(|segment| &segment.ident)
+}
+
 impl Validator<FieldSpec> for Field {
     type Error = syn::Error;
 
@@ -264,6 +588,148 @@ impl Validator<FieldSpec> for Field {
             errors.push(Self::Error::new(spec.args.span(), msg));
         }
 
+        if let Some(constraints) = &spec.args.constraints {
+            if !spec
+                .write_schema()
+                .is_some_and(|schema| schema.numericity.is_numeric())
+            {
+                errors.push(syn::Error::new(
+                    constraints.span(),
+                    "constraints can only be applied to writable numeric fields",
+                ));
+            }
+
+            if let Some(range) = &constraints.range {
+                match parse_expr_range(range) {
+                    Ok(parsed) if parsed.is_empty() || parsed.end as u64 > 1u64 << spec.width => {
+                        errors.push(syn::Error::new_spanned(
+                            range,
+                            format!(
+                                "range must be non-empty and within the field domain of {} bits",
+                                spec.width
+                            ),
+                        ));
+                    }
+                    Ok(_) => {}
+                    Err(e) => errors.push(e),
+                }
+            }
+
+            if constraints.multiple_of == Some(0) {
+                errors.push(syn::Error::new(
+                    constraints.span(),
+                    "values cannot be multiples of zero",
+                ));
+            }
+        }
+
+        if spec.args.flag {
+            if spec.width != 1 {
+                errors.push(syn::Error::new(
+                    spec.args.span(),
+                    "flags must be single-bit fields",
+                ));
+            } else if !spec.access.is_read() && spec.flag_clear_bits().is_none() {
+                errors.push(syn::Error::new(
+                    spec.args.span(),
+                    "flags must be readable, or writable with a clearing value dictated by a write effect or the sole variant of the write schema",
+                ));
+            }
+        }
+
+        if let Some(config) = &spec.args.config {
+            if spec.is_resolvable() {
+                errors.push(syn::Error::new(
+                    config.span(),
+                    "resolvable fields cannot be configured, as their states are tracked by type",
+                ));
+            } else if !matches!(spec.access, Access::ReadWrite { .. })
+                || spec.access.write_effect().is_some()
+                || spec.write_entitlements().is_some()
+            {
+                errors.push(syn::Error::new(
+                    config.span(),
+                    "configured fields must be readable and writable, without write effects or entitlements",
+                ));
+            }
+        }
+
+        if let Some(Expr::Path(reset)) = &spec.args.reset {
+            let is_range = [spec.read_schema(), spec.write_schema()]
+                .into_iter()
+                .flatten()
+                .any(|schema| match &schema.numericity {
+                    Numericity::Enumerated { variants } => variants.iter().any(|variant| {
+                        variant.range.is_some() && reset.path.is_ident(&variant.ident)
+                    }),
+                    Numericity::Numeric => false,
+                });
+
+            if is_range {
+                errors.push(syn::Error::new_spanned(
+                    reset,
+                    "fields cannot reset to range variants",
+                ));
+            }
+        }
+
+        if spec.args.volatile && !matches!(spec.access, Access::ReadWrite { .. }) {
+            errors.push(syn::Error::new(
+                spec.args.span(),
+                "volatile fields must be readable and writable",
+            ));
+        }
+
+        if spec.args.by_value && !matches!(spec.access, Access::ReadWrite { .. }) {
+            errors.push(syn::Error::new(
+                spec.args.span(),
+                "fields accessed by value must be readable and writable",
+            ));
+        }
+
+        if let Some(unit) = &spec.args.unit {
+            if ![spec.read_schema(), spec.write_schema()]
+                .into_iter()
+                .flatten()
+                .all(|schema| schema.numericity.is_numeric())
+            {
+                errors.push(syn::Error::new(
+                    unit.span(),
+                    "units can only be applied to numeric fields",
+                ));
+            }
+
+            if unit.scale.is_some() && unit.fractional.is_some() {
+                errors.push(syn::Error::new(
+                    unit.span(),
+                    "unit scale is inferred from fractional bits",
+                ));
+            }
+
+            if unit
+                .fractional
+                .is_some_and(|fractional| fractional > spec.width)
+            {
+                errors.push(syn::Error::new(
+                    unit.span(),
+                    format!(
+                        "fractional bits exceed the field width of {} bits",
+                        spec.width
+                    ),
+                ));
+            }
+
+            if unit
+                .scale
+                .is_some_and(|scale| scale == 0. || !scale.is_finite())
+            {
+                errors.push(syn::Error::new(
+                    unit.span(),
+                    "unit scale must be finite and non-zero",
+                ));
+            }
+        }
+
         errors.coalesce()?;
 
         Ok(Self { spec })
@@ -271,7 +737,9 @@ impl Validator<FieldSpec> for Field {
 }
 
 impl Field {
-    fn maybe_generate_state_bodies(&self) -> Option<TokenStream2> {
+    /// The variants of this field which are represented
+    /// as type-states (if any).
+    pub fn states(&self) -> Option<&Vec<Variant>> {
         if !self.is_resolvable() {
             return None;
         };
@@ -280,15 +748,59 @@ impl Field {
         // the schema that represents the resolvable aspect of the
         // field must be from read access, as the value the field
         // holds must represent the state to be resolved
-        let schema = match &self.access {
-            Access::Read(read) | Access::ReadWrite { read, write: _ } => &read.schema,
-            _ => return None,
+        let Numericity::Enumerated { variants } = &self.read_schema()?.numericity else {
+            return None;
         };
 
-        let Numericity::Enumerated { variants } = &schema.numericity else {
+        Some(variants)
+    }
+
+    /// The variant enums generated for this field, by identifier.
+    ///
+    /// If the field has one schema, a single `Variant` enum is generated.
+    /// If the field has differing read and write schemas, a `ReadVariant`
+    /// and `WriteVariant` enum is generated.
+    pub fn variant_enums(&self) -> Vec<(Ident, &Vec<Variant>)> {
+        let span = self.args.span();
+
+        let schemas = match &self.access {
+            Access::Read(Read { schema, .. }) | Access::Write(Write { schema, .. }) => {
+                vec![(Ident::new("Variant", span), schema)]
+            }
+            Access::ReadWrite { read, write } => {
+                if read.schema == write.schema {
+                    vec![(Ident::new("Variant", span), &read.schema)]
+                } else {
+                    vec![
+                        (Ident::new("ReadVariant", span), &read.schema),
+                        (Ident::new("WriteVariant", span), &write.schema),
+                    ]
+                }
+            }
+        };
+
+        // TODO: fields with an enumerated and a numeric schema
+        // currently generate no variant enums
+        let mut variant_enums = Vec::new();
+
+        for (ident, schema) in schemas {
+            let Numericity::Enumerated { variants } = &schema.numericity else {
+                return Vec::new();
+            };
+
+            variant_enums.push((ident, variants));
+        }
+
+        variant_enums
+    }
+
+    fn maybe_generate_state_bodies(&self) -> Option<TokenStream2> {
+        if !self.is_resolvable() {
             return None;
         };
 
+        let variants = self.states()?;
+
         let span = self.args.span();
 
         // NOTE: the variant renders to a state implementation
@@ -301,6 +813,41 @@ impl Field {
         })
     }
 
+    /// Generate whether the states of this field can be reached,
+    /// which requires the states entitling writes of this field
+    /// from other registers to be reachable.
+    fn maybe_generate_reachability(&self) -> Option<TokenStream2> {
+        if !self.is_resolvable() {
+            return None;
+        }
+
+        let span = self.args.span();
+
+        let write_entitlements = self.write_entitlements().map(sorted_paths);
+
+        // entitlements within the register are always reachable,
+        // as its fields are reachable with the register
+        let reachable = match write_entitlements {
+            Some(paths)
+                if paths.iter().all(|path| {
+                    path.segments
+                        .first()
+                        .is_some_and(|segment| segment.ident == "super")
+                }) =>
+            {
+                quote_spanned! { span =>
+                    super::REACHABLE && (#(<super::#paths as ::proto_hal::stasis::Freeze>::REACHABLE)||*)
+                }
+            }
+            _ => quote_spanned! { span => super::REACHABLE },
+        };
+
+        Some(quote_spanned! { span =>
+            /// Whether the states of this field can be reached.
+            const REACHABLE: bool = #reachable;
+        })
+    }
+
     fn generate_offset_const(&self) -> TokenStream2 {
         let span = self.args.span();
 
@@ -321,6 +868,170 @@ impl Field {
         }
     }
 
+    fn maybe_generate_assertions(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let variant_enums = self.variant_enums();
+
+        if variant_enums.is_empty() {
+            return None;
+        }
+
+        let assertions = variant_enums.iter().map(|(ident, variants)| {
+            // range variants are bounded by the schema
+            let variant_idents = variants
+                .iter()
+                .filter(|variant| variant.range.is_none())
+                .map(|variant| &variant.ident);
+
+            quote_spanned! { span =>
+                #(
+                    assert!(
+                        (#ident::#variant_idents.bits() as u64) >> WIDTH == 0,
+                        "variant exceeds the field width"
+                    );
+                )*
+            }
+        });
+
+        Some(quote_spanned! { span =>
+            const _: () = {
+                #(
+                    #assertions
+                )*
+            };
+        })
+    }
+
+    fn maybe_generate_constraint_check(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let constraints = self.args.constraints.as_ref()?;
+
+        let mut checks = TokenStream2::new();
+
+        if let Some(range) = &constraints.range {
+            // validated
+            let range = parse_expr_range(range).unwrap();
+            let (start, end) = (range.start, range.end as u64);
+
+            if start > 0 {
+                checks.extend(quote_spanned! { span =>
+                    if value < #start {
+                        return Err(::proto_hal::macro_utils::ConstraintViolation::OutOfRange);
+                    }
+                });
+            }
+
+            if end < 1u64 << self.width {
+                let end = end as u32;
+
+                checks.extend(quote_spanned! { span =>
+                    if value >= #end {
+                        return Err(::proto_hal::macro_utils::ConstraintViolation::OutOfRange);
+                    }
+                });
+            }
+        }
+
+        if let Some(multiple) = constraints.multiple_of {
+            checks.extend(quote_spanned! { span =>
+                if value % #multiple != 0 {
+                    return Err(::proto_hal::macro_utils::ConstraintViolation::NotMultiple);
+                }
+            });
+        }
+
+        if constraints.non_zero {
+            checks.extend(quote_spanned! { span =>
+                if value == 0 {
+                    return Err(::proto_hal::macro_utils::ConstraintViolation::Zero);
+                }
+            });
+        }
+
+        Some(quote_spanned! { span =>
+            /// Check a raw value against the constraints of this field.
+            ///
+            /// *Note: This can be used in const contexts to check values at compile time.*
+            pub const fn check(value: u32) -> Result<(), ::proto_hal::macro_utils::ConstraintViolation> {
+                #checks
+
+                Ok(())
+            }
+        })
+    }
+
+    fn maybe_generate_quantity(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let unit = self.args.unit.as_ref()?;
+
+        let scale = unit.scale() as f32;
+        // values are converted to raw values in `f64`, which
+        // represents every raw value exactly
+        let precise_scale = unit.scale();
+        let width = self.width as u32;
+        let symbol = unit.symbol.as_deref().unwrap_or_default();
+
+        let (to_value, from_value) = if unit.signed {
+            (
+                quote_spanned! { span =>
+                    // sign extend
+                    ((self.0 << (32 - #width)) as i32 >> (32 - #width)) as f32
+                },
+                quote_spanned! { span =>
+                    (::proto_hal::macro_utils::round(value as f64 / #precise_scale) as i32) as u32
+                },
+            )
+        } else {
+            (
+                quote_spanned! { span => self.0 as f32 },
+                quote_spanned! { span =>
+                    ::proto_hal::macro_utils::round(value as f64 / #precise_scale) as u32
+                },
+            )
+        };
+
+        Some(quote_spanned! { span =>
+            /// The value a raw value of this field represents per unit.
+            pub const SCALE: f32 = #scale;
+
+            /// The symbol of the unit this field is expressed in.
+            pub const SYMBOL: &str = #symbol;
+
+            /// A value of this field, in its unit.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct Quantity(u32);
+
+            impl Quantity {
+                /// Create a quantity from the raw value of the field.
+                ///
+                /// *Note: Bits exceeding the field width are discarded.*
+                pub const fn from_raw(raw: u32) -> Self {
+                    Self(raw & ::proto_hal::macro_utils::mask(0, WIDTH))
+                }
+
+                /// Create the quantity nearest to `value`.
+                ///
+                /// *Note: Values outside the field domain saturate or wrap.*
+                pub fn from_f32(value: f32) -> Self {
+                    Self::from_raw(#from_value)
+                }
+
+                /// The raw value of the field.
+                pub const fn raw(self) -> u32 {
+                    self.0
+                }
+
+                /// The value in the unit of the field.
+                pub fn to_f32(self) -> f32 {
+                    #to_value * SCALE
+                }
+            }
+        })
+    }
+
     fn maybe_generate_resets(&self) -> Option<TokenStream2> {
         let span = self.args.span();
 
@@ -336,7 +1047,7 @@ impl Field {
         match &schema.numericity {
             Numericity::Enumerated { variants: _ } => Some(quote_spanned! { span =>
                 pub type Reset = #reset;
-                pub const RESET: u32 = Reset::RAW as u32;
+                pub const RESET: u32 = Reset::RAW.bits();
             }),
             Numericity::Numeric => todo!(),
         }
@@ -350,11 +1061,69 @@ impl Field {
                 .iter()
                 .map(|variant| variant.ident.clone())
                 .collect::<Vec<_>>();
-            let variant_bits = variants
+
+            // range variants carry the value they hold,
+            // so the enum cannot be a plain discriminant
+            let is_ranged = variants.iter().any(|variant| variant.range.is_some());
+
+            let variant_decls = variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let bits = variant.bits;
+
+                if variant.range.is_some() {
+                    quote_spanned! { span => #variant_ident(u32) }
+                } else if is_ranged {
+                    quote_spanned! { span => #variant_ident }
+                } else {
+                    quote_spanned! { span => #variant_ident = #bits }
+                }
+            });
+
+            let repr = (!is_ranged).then(|| quote_spanned! { span => #[repr(u32)] });
+
+            let variant_patterns = variants
                 .iter()
-                .map(|variant| variant.bits)
+                .map(|variant| {
+                    let bits = variant.bits;
+
+                    if variant.range.is_some() {
+                        let last = variant.last_bits();
+
+                        quote_spanned! { span => #bits..=#last }
+                    } else {
+                        quote_spanned! { span => #bits }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let variant_values = variants
+                .iter()
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+
+                    if variant.range.is_some() {
+                        quote_spanned! { span => Self::#variant_ident(bits) }
+                    } else {
+                        quote_spanned! { span => Self::#variant_ident }
+                    }
+                })
                 .collect::<Vec<_>>();
 
+            let variant_bits = variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                let bits = variant.bits;
+
+                // values of range variants are constructed freely,
+                // so they are confined to the field
+                if variant.range.is_some() {
+                    quote_spanned! { span =>
+                        Self::#variant_ident(bits) => bits & ::proto_hal::macro_utils::mask(0, WIDTH)
+                    }
+                } else {
+                    quote_spanned! { span => Self::#variant_ident => #bits }
+                }
+            });
+
             let is_variant_idents = variants.iter().map(|variant| {
                 format_ident!(
                     "is_{}",
@@ -363,10 +1132,11 @@ impl Field {
             });
 
             quote_spanned! { span =>
-                #[repr(u32)]
+                #[derive(Clone, Copy, PartialEq, Eq)]
+                #repr
                 pub enum #ident {
                     #(
-                        #variant_idents = #variant_bits,
+                        #variant_decls,
                     )*
                 }
 
@@ -374,94 +1144,98 @@ impl Field {
                     pub unsafe fn from_bits(bits: u32) -> Self {
                         match bits {
                             #(
-                                #variant_bits => Self::#variant_idents,
+                                #variant_patterns => #variant_values,
                             )*
                             _ => ::core::hint::unreachable_unchecked(),
                         }
                     }
 
+                    /// The variant corresponding to `bits`, if any.
+                    pub fn try_from_bits(bits: u32) -> Result<Self, ::proto_hal::macro_utils::InvalidValue> {
+                        match bits {
+                            #(
+                                #variant_patterns => Ok(#variant_values),
+                            )*
+                            _ => Err(::proto_hal::macro_utils::InvalidValue(bits)),
+                        }
+                    }
+
+                    /// The raw value of the variant.
+                    pub const fn bits(self) -> u32 {
+                        match self {
+                            #(
+                                #variant_bits,
+                            )*
+                        }
+                    }
+
                     #(
                         pub fn #is_variant_idents(&self) -> bool {
-                            matches!(self, Self::#variant_idents)
+                            matches!(self, Self::#variant_idents { .. })
                         }
                     )*
                 }
             }
         };
 
-        // TODO: there must be a better way to do this
-        match &self.access {
-            Access::Read(read) => {
-                let Numericity::Enumerated { variants } = &read.schema.numericity else {
-                    return None;
-                };
+        let variant_enums = self.variant_enums();
 
-                let variant_enum = variant_enum(Ident::new("Variant", span), variants);
+        if variant_enums.is_empty() {
+            return None;
+        }
 
-                Some(quote_spanned! { span =>
-                    pub type ReadVariant = Variant;
-                    pub type WriteVariant = Variant;
-                    #variant_enum
-                })
+        // a single enum serves both directions
+        let aliases = (variant_enums.len() == 1).then(|| {
+            quote_spanned! { span =>
+                pub type ReadVariant = Variant;
+                pub type WriteVariant = Variant;
             }
-            Access::Write(write) => {
-                let Numericity::Enumerated { variants } = &write.schema.numericity else {
-                    return None;
-                };
+        });
 
-                let variant_enum = variant_enum(Ident::new("Variant", span), variants);
+        let variant_enums = variant_enums
+            .into_iter()
+            .map(|(ident, variants)| variant_enum(ident, variants));
 
-                Some(quote_spanned! { span =>
-                    pub type ReadVariant = Variant;
-                    pub type WriteVariant = Variant;
-                    #variant_enum
-                })
-            }
-            Access::ReadWrite { read, write } => {
-                if read.schema == write.schema {
-                    let Numericity::Enumerated { variants } = &read.schema.numericity else {
-                        return None;
-                    };
+        Some(quote_spanned! { span =>
+            #aliases
+            #(
+                #variant_enums
+            )*
+        })
+    }
 
-                    let variant_enum = variant_enum(Ident::new("Variant", span), variants);
+    /// Notes describing this field and its states, to be
+    /// attached to diagnostics of the traits bounding them.
+    fn generate_diagnostic_notes(&self) -> Vec<String> {
+        // diagnostic notes are format strings
+        let escape = |s: &str| s.replace('{', "{{").replace('}', "}}");
 
-                    Some(quote_spanned! { span =>
-                        pub type ReadVariant = Variant;
-                        pub type WriteVariant = Variant;
-                        #variant_enum
-                    })
-                } else {
-                    let read_variant_enum = if let Numericity::Enumerated {
-                        variants: read_variants,
-                    } = &read.schema.numericity
-                    {
-                        Some(variant_enum(Ident::new("ReadVariant", span), read_variants))
-                    } else {
-                        return None;
-                    };
-                    let write_variant_enum = if let Numericity::Enumerated {
-                        variants: write_variants,
-                    } = &write.schema.numericity
-                    {
-                        Some(variant_enum(
-                            Ident::new("WriteVariant", span),
-                            write_variants,
-                        ))
-                    } else {
-                        return None;
-                    };
+        let mut notes = Vec::new();
 
-                    if let (None, None) = (&read_variant_enum, &write_variant_enum) {
-                        return None;
-                    }
+        if !self.docs.is_empty() {
+            notes.push(format!(
+                "`{}`: {}",
+                self.ident,
+                escape(&self.docs.join(" "))
+            ));
+        }
 
-                    Some(quote_spanned! { span =>
-                        #read_variant_enum
-                        #write_variant_enum
-                    })
-                }
-            }
+        if let Some(Numericity::Enumerated { variants }) =
+            self.read_schema().map(|schema| &schema.numericity)
+        {
+            let states = variants
+                .iter()
+                .map(|variant| match variant.docs.first() {
+                    Some(doc) => format!("`{}` ({})", variant.ident, escape(doc)),
+                    None => format!("`{}`", variant.ident),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            notes.push(format!("the states of `{}` are: {states}", self.ident));
         }
+
+        notes
     }
 
     fn maybe_generate_state_trait(&self) -> Option<TokenStream2> {
@@ -483,6 +1257,11 @@ impl Field {
                     .map(|variant| variant.ident.clone())
                     .collect::<Vec<_>>();
 
+                let (variant_impl_generics, variant_ty_generics) = variants
+                    .iter()
+                    .map(|variant| variant.generics())
+                    .unzip::<_, _, Vec<_>, Vec<_>>();
+
                 let conversion_methods = if self.access.is_write() {
                     let into_func_idents = variant_idents.iter().map(|ident| {
                         format_ident!(
@@ -509,19 +1288,21 @@ Consider using register accessors when performing state transitions.";
                         {
                             // SAFETY: assumes the proc macro implementation is sound
                             // and that the peripheral description is accurate
-                            let mut reg_value = unsafe { core::ptr::read_volatile((super::super::BASE_ADDR + super::OFFSET) as *const u32) };
+                            let mut reg_value = unsafe { super::read_raw() };
 
                             // i.e.
                             // 0000 0000 0000 0000 0111 1111 1100 0000
                             const MASK: u32 = (0xffff_ffff >> (32 - (WIDTH as u32))) << (OFFSET as u32);
 
+                            // fields which clear when written must be written inert
+                            reg_value = (reg_value & !super::INERT_ZEROS) | super::INERT_ONES;
                             reg_value &= !MASK;
-                            reg_value |= (S::RAW as u32) << (OFFSET as u32);
+                            reg_value |= S::RAW.bits() << (OFFSET as u32);
 
                             // SAFETY: assumes the proc macro implementation is sound
                             // and that the peripheral description is accurate
                             unsafe {
-                                core::ptr::write_volatile((super::super::BASE_ADDR + super::OFFSET) as *mut u32, reg_value);
+                                super::rewrite_raw(reg_value);
                             }
 
                             // SAFETY:
@@ -530,9 +1311,22 @@ Consider using register accessors when performing state transitions.";
                             unsafe { S::conjure() }
                         }
 
+                        /// Convert this state into a new state within a critical
+                        /// section, yielding the new state and the variant of
+                        /// the previous state.
+                        #[doc = #warning_msg]
+                        fn swap<S>(self) -> (S, ReadVariant)
+                        where
+                            S: State,
+                        {
+                            let previous = Self::RAW;
+
+                            (::proto_hal::macro_utils::guarded(|| self.into_state()), previous)
+                        }
+
                         #(
                             #[doc = #into_func_docs]
-                            fn #into_func_idents(self) -> #variant_idents
+                            fn #into_func_idents #variant_impl_generics(self) -> #variant_idents #variant_ty_generics
                             {
                                 self.into_state()
                             }
@@ -542,7 +1336,20 @@ Consider using register accessors when performing state transitions.";
                     None
                 };
 
+                let assume_docs = format!(
+                    "Assume the `{}` field is in the state `S` without touching hardware.",
+                    self.ident
+                );
+
+                let message = format!("`{{Self}}` is not a state of the `{}` field", self.ident);
+                let notes = self.generate_diagnostic_notes();
+
                 Some(quote_spanned! { span =>
+                    #[diagnostic::on_unimplemented(
+                        message = #message,
+                        label = "not a state of this field",
+                        #(note = #notes,)*
+                    )]
                     pub trait State: ::proto_hal::stasis::Freeze {
                         const RAW: ReadVariant;
 
@@ -550,12 +1357,209 @@ Consider using register accessors when performing state transitions.";
 
                         #conversion_methods
                     }
+
+                    #[doc = #assume_docs]
+                    ///
+                    /// Useful for re-entering tracked state after the field
+                    /// was manipulated by untracked means (i.e. a bootloader).
+                    ///
+                    /// # Safety
+                    ///
+                    /// 1. The field must actually be in the state `S`.
+                    /// 2. No other token of this field's state may exist, as the
+                    ///    produced token claims ownership of the field.
+                    pub unsafe fn assume<S: State>() -> S {
+                        S::conjure()
+                    }
+
+                    /// Identifies the state of this field among
+                    /// the states held by its register.
+                    pub struct Held;
                 })
             }
             Numericity::Numeric => todo!(),
         }
     }
 
+    fn maybe_generate_state_reads(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        if !self.is_resolvable() {
+            return None;
+        }
+
+        let Some(Numericity::Enumerated { variants }) =
+            self.read_schema().map(|schema| &schema.numericity)
+        else {
+            return None;
+        };
+
+        let variant_idents = variants
+            .iter()
+            .map(|variant| &variant.ident)
+            .collect::<Vec<_>>();
+
+        // the values of range variants are only known at runtime,
+        // so their tokens cannot be produced from a read
+        let state_read = variants.iter().all(|variant| variant.range.is_none()).then(|| {
+            quote_spanned! { span =>
+                /// A state token of this field, as determined at runtime.
+                pub enum States {
+                    #(
+                        #variant_idents(#variant_idents),
+                    )*
+                }
+
+                /// Read the field, producing the token of its current state.
+                ///
+                /// # Safety
+                ///
+                /// No other token of this field's state may exist, as the
+                /// produced token claims ownership of the field.
+                pub unsafe fn read_state() -> States {
+                    let bits = (super::read_raw() >> OFFSET) & (u32::MAX >> (32 - WIDTH as u32));
+
+                    match ReadVariant::from_bits(bits) {
+                        #(
+                            ReadVariant::#variant_idents => States::#variant_idents(#variant_idents::conjure()),
+                        )*
+                    }
+                }
+            }
+        });
+
+        Some(quote_spanned! { span =>
+            #state_read
+
+            /// Read the field, producing the state token `S` only
+            /// if the field is currently in that state.
+            ///
+            /// # Safety
+            ///
+            /// No other token of this field's state may exist, as the
+            /// produced token claims ownership of the field.
+            pub unsafe fn read_as<S: State>() -> Option<S> {
+                let bits = (super::read_raw() >> OFFSET) & (u32::MAX >> (32 - WIDTH as u32));
+
+                (bits == S::RAW.bits()).then(|| S::conjure())
+            }
+        })
+    }
+
+    /// Generate polling of fields with variants the hardware enters
+    /// on its own (such fields are unresolvable, as the hardware would
+    /// invalidate held state tokens).
+    fn maybe_generate_polling(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let Some(Numericity::Enumerated { variants }) =
+            self.read_schema().map(|schema| &schema.numericity)
+        else {
+            return None;
+        };
+
+        if !variants.iter().any(|variant| variant.args.hardware)
+            || self.access.read_effect().is_some()
+        {
+            return None;
+        }
+
+        Some(quote_spanned! { span =>
+            /// The field did not enter the awaited variant in time.
+            pub struct Timeout;
+
+            impl From<Timeout> for ::proto_hal::error::Error {
+                fn from(_: Timeout) -> Self {
+                    Self::Timeout
+                }
+            }
+
+            /// Poll the field until the hardware enters `variant`,
+            /// or fail after `polls` reads.
+            pub fn poll_until(variant: ReadVariant, polls: u32) -> Result<(), Timeout> {
+                for _ in 0..polls {
+                    // SAFETY: the field has no read effects
+                    let bits = (unsafe { super::read_raw() } >> OFFSET) & (u32::MAX >> (32 - WIDTH as u32));
+
+                    if bits == variant.bits() {
+                        return Ok(());
+                    }
+                }
+
+                Err(Timeout)
+            }
+        })
+    }
+
+    fn maybe_generate_mode_trait(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let entitlements = self.access_entitlements();
+
+        if entitlements.is_empty() {
+            return None;
+        }
+
+        let entitlements = sorted_paths(entitlements);
+
+        let message = format!("the `{}` field is not accessible in `{{Self}}`", self.ident);
+        let notes = self.generate_diagnostic_notes();
+
+        Some(quote_spanned! { span =>
+            /// States which entitle access to this field,
+            /// i.e. the modes in which the field is valid.
+            #[diagnostic::on_unimplemented(
+                message = #message,
+                #(note = #notes,)*
+            )]
+            pub trait Mode {}
+
+            #(
+                impl Mode for super::#entitlements {}
+            )*
+
+            /// Whether a state selects this field as the valid view of
+            /// bits it shares with other fields, which is the case
+            /// exactly when the state implements [`Mode`].
+            pub trait View {
+                const ACTIVE: bool;
+            }
+
+            impl<S: Mode> View for S {
+                const ACTIVE: bool = true;
+            }
+        })
+    }
+
+    fn maybe_generate_write_mode_trait(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let entitlements = sorted_paths(self.write_entitlements()?);
+
+        let message = format!("the `{}` field is not writable in `{{Self}}`", self.ident);
+
+        Some(quote_spanned! { span =>
+            /// States which entitle writing this field. Writing the
+            /// field requires an entitlement to one of these states.
+            #[diagnostic::on_unimplemented(message = #message)]
+            pub trait WriteMode: ::proto_hal::stasis::Freeze {}
+
+            #(
+                impl WriteMode for super::#entitlements {}
+            )*
+        })
+    }
+
+    /// The deprecation attribute of items representing this field (if deprecated).
+    pub fn deprecation(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+        let note = self.args.deprecated.as_ref()?;
+
+        Some(quote_spanned! { span =>
+            #[deprecated(note = #note)]
+        })
+    }
+
     fn generate_module_docs(&self) -> TokenStream2 {
         let span = self.args.span();
 
@@ -569,6 +1573,10 @@ Consider using register accessors when performing state transitions.";
 
         let resolvability_doc = if self.is_resolvable() {
             "- Type: resolvable"
+        } else if self.args.volatile {
+            "- Type: unresolvable (volatile)"
+        } else if self.args.by_value {
+            "- Type: unresolvable (by value)"
         } else {
             "- Type: unresolvable"
         };
@@ -599,16 +1607,30 @@ impl ToTokens for Field {
         let mut body = TokenStream2::new();
 
         body.extend(self.maybe_generate_state_bodies());
+        body.extend(self.maybe_generate_reachability());
         body.extend(self.generate_offset_const());
         body.extend(self.generate_width_const());
+        body.extend(self.maybe_generate_constraint_check());
+        body.extend(self.maybe_generate_quantity());
         body.extend(self.maybe_generate_resets());
         body.extend(self.maybe_generate_variant_enum());
+        body.extend(self.maybe_generate_assertions());
         body.extend(self.maybe_generate_state_trait());
+        body.extend(self.maybe_generate_state_reads());
+        body.extend(self.maybe_generate_polling());
+        body.extend(self.maybe_generate_mode_trait());
+        body.extend(self.maybe_generate_write_mode_trait());
 
+        let user_docs = &self.docs;
         let docs = self.generate_module_docs();
+        let deprecation = self.deprecation();
 
         tokens.extend(quote_spanned! { span =>
+            #(
+                #[doc = #user_docs]
+            )*
             #docs
+            #deprecation
             pub mod #ident {
                 #body
             }
@@ -2,9 +2,8 @@ use std::{collections::HashMap, ops::Range};
 
 use darling::FromMeta;
 use syn::{ExprRange, Ident, Item};
-use tiva::Validator;
 
-use crate::utils::{parse_expr_range, FieldOffset, Spanned, SynErrorCombinator};
+use crate::utils::{parse_expr_range, FieldOffset, Spanned, SynErrorCombinator, Validator};
 
 use super::{
     field::{Field, FieldArgs, FieldSpec},
@@ -15,6 +14,10 @@ use super::{
 #[derive(Debug, Clone, FromMeta)]
 pub struct FieldArrayArgs {
     pub range: ExprRange,
+    /// The distance (in bits) between the offsets of
+    /// consecutive fields. Defaults to the field width.
+    #[darling(default)]
+    pub stride: Option<u8>,
 
     #[darling(flatten)]
     pub field: FieldArgs,
@@ -28,11 +31,13 @@ impl Args for FieldArrayArgs {
 pub struct FieldArray {
     pub inherited: Field,
     pub range: Range<u32>,
+    pub stride: Option<u8>,
 }
 
 impl FieldArray {
     pub fn parse<'a>(
         ident: Ident,
+        docs: Vec<String>,
         offset: FieldOffset,
         schemas: &HashMap<Ident, Schema>,
         args: Spanned<FieldArrayArgs>,
@@ -42,6 +47,7 @@ impl FieldArray {
         // but merely the structure of a field
         let pseudo_field = Field::validate(FieldSpec::parse(
             ident,
+            docs,
             offset,
             schemas,
             args.field.clone().with_span(args.span()),
@@ -50,9 +56,19 @@ impl FieldArray {
 
         let range = parse_expr_range(&args.range)?;
 
+        if let Some(stride) = args.stride {
+            if stride < pseudo_field.width() {
+                Err(syn::Error::new(
+                    args.span(),
+                    "field array stride must be at least the field width",
+                ))?
+            }
+        }
+
         Ok(Self {
             inherited: pseudo_field,
             range,
+            stride: args.stride,
         })
     }
 }
@@ -62,6 +78,42 @@ impl FieldArray {
         self.range.clone().count() as _
     }
 
+    pub fn stride(&self) -> FieldOffset {
+        self.stride.unwrap_or(self.inherited.width())
+    }
+
+    /// The position of the placeholder replaced by the index
+    /// of each field (the last `X` of the identifier).
+    fn placeholder(&self) -> syn::Result<usize> {
+        self.inherited
+            .ident
+            .to_string()
+            .rfind('X')
+            .ok_or(syn::Error::new(
+                self.inherited.ident.span(),
+                "field array module ident must contain an 'X' to indicate replacement location",
+            ))
+    }
+
+    /// The identifier of the field at `index`, i.e. `mode3` for `modeX`.
+    pub fn element_ident(&self, index: u32) -> syn::Result<Ident> {
+        let pos = self.placeholder()?;
+        let mut s = self.inherited.ident.to_string();
+        s.replace_range(pos..pos + 1, &index.to_string());
+
+        Ok(Ident::new(&s, self.inherited.ident.span()))
+    }
+
+    /// The name of the array as a whole, i.e. `mode` for `modeX`.
+    pub fn name(&self) -> String {
+        // validated
+        let pos = self.placeholder().unwrap();
+        let mut s = self.inherited.ident.to_string();
+        s.remove(pos);
+
+        s.trim_matches('_').to_string()
+    }
+
     pub fn to_fields(&self) -> syn::Result<Vec<Field>> {
         let mut errors = SynErrorCombinator::new();
         let mut fields = Vec::new();
@@ -69,29 +121,20 @@ impl FieldArray {
         let inherited = &self.inherited;
 
         let mut offset = inherited.offset;
-
-        let replace_pos = inherited
-            .ident
-            .to_string()
-            .rfind("X")
-            .ok_or(syn::Error::new(
-                inherited.ident.span(),
-                "field array module ident must contain an 'X' to indicate replacement location",
-            ))?;
+        let stride = self.stride();
 
         // generate fields
         for i in self.range.clone() {
-            let mut s = inherited.ident.to_string();
-            s.replace_range(replace_pos..replace_pos + 1, &i.to_string());
-            let ident = Ident::new(&s, inherited.ident.span());
+            let ident = self.element_ident(i)?;
 
             let args = inherited.args.clone();
+            let docs = inherited.docs.clone();
             let access = inherited.access.clone();
 
-            let get_field = || Field::validate(FieldSpec::new(args, ident, offset, access)?);
+            let get_field = || Field::validate(FieldSpec::new(args, ident, docs, offset, access)?);
 
             errors.maybe_then(get_field(), |field| {
-                offset += field.width();
+                offset += stride;
 
                 fields.push(field);
             });
@@ -1,16 +1,105 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use quote::{quote, ToTokens};
+use darling::FromMeta;
+use quote::{format_ident, quote, ToTokens};
 use syn::{
-    parse2, spanned::Spanned, Attribute, Ident, Index, ItemEnum, Meta, Visibility,
+    parse2, spanned::Spanned, Attribute, Ident, Index, ItemEnum, Meta, Path, Visibility,
 };
 
-use crate::utils::SynErrorCombinator;
+use crate::utils::{PathArray, SynErrorCombinator};
+
+use super::Args;
+
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct InterruptsArgs {
+    /// Generate a waker for each interrupt, woken every time
+    /// the interrupt fires. Requires the `async` feature.
+    #[darling(default)]
+    pub wakers: bool,
+    /// The runtime crate providing the `interrupt` attribute
+    /// (i.e. `riscv_rt`). By default, `cortex_m_rt`.
+    pub runtime: Option<Path>,
+    /// Generate the vector table as a module, for projects which
+    /// do not use a runtime crate (i.e. custom startup code).
+    pub vector_table: Option<VectorTableArgs>,
+}
+
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default, from_word = || Ok(Self::default()))]
+pub struct VectorTableArgs {
+    /// The symbol of the handler interrupts without a handler
+    /// are bound to. By default, a handler which spins forever.
+    pub default_handler: Option<Ident>,
+}
+
+impl Args for InterruptsArgs {
+    const NAME: &str = "interrupts";
+}
+
+/// Binds the handler of an interrupt at runtime,
+/// requiring the provided entitlements to do so.
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct BindArgs {
+    pub entitlements: PathArray,
+}
+
+impl Args for BindArgs {
+    const NAME: &str = "bind";
+}
+
+/// Links an interrupt to the `flag` fields of its source
+/// (`block::register::field`), generating helpers to query
+/// and clear the pending status of the interrupt.
+///
+/// The status field must be readable without read effects,
+/// and the clear field must be writable with a clearing value
+/// dictated by its write effect or the sole variant of its
+/// write schema.
+#[derive(Debug, Clone, FromMeta)]
+pub struct SourceArgs {
+    /// The field indicating the interrupt is pending.
+    pub status: Path,
+    /// The field which is written to clear the status.
+    pub clear: Option<Path>,
+}
+
+impl Args for SourceArgs {
+    const NAME: &str = "source";
+}
+
+impl SourceArgs {
+    /// Split the path of a field into the path
+    /// of its register and the field identifier.
+    fn split(path: &Path) -> syn::Result<(Path, Ident)> {
+        let mut register = path.clone();
+
+        let field = register
+            .segments
+            .pop()
+            .filter(|_| !register.segments.is_empty())
+            .ok_or(syn::Error::new_spanned(
+                path,
+                "expected a path of the form `register::field`",
+            ))?
+            .into_value()
+            .ident;
+
+        // drop the trailing separator
+        let last = register.segments.pop().unwrap().into_value();
+        register.segments.push(last);
+
+        Ok((register, field))
+    }
+}
 
 struct Vector {
     attrs: Vec<Attribute>,
     ident: Ident,
     position: Index,
+    bind: Option<BindArgs>,
+    source: Option<SourceArgs>,
 }
 
 impl Vector {
@@ -35,12 +124,14 @@ impl ToTokens for Vector {
 }
 
 pub struct InterruptsSpec {
+    args: InterruptsArgs,
     attrs: Vec<Attribute>,
-    vectors: HashMap<u32, Vector>,
+    /// Vectors keyed (and ordered) by position.
+    vectors: BTreeMap<u32, Vector>,
 }
 
 impl InterruptsSpec {
-    pub fn parse(e: &ItemEnum) -> syn::Result<Self> {
+    pub fn parse(args: InterruptsArgs, e: &ItemEnum) -> syn::Result<Self> {
         let mut errors = SynErrorCombinator::new();
 
         if !matches!(e.vis, Visibility::Public(_)) {
@@ -51,8 +142,9 @@ impl InterruptsSpec {
         }
 
         let mut interrupts = Self {
+            args,
             attrs: e.attrs.clone(),
-            vectors: HashMap::new(),
+            vectors: BTreeMap::new(),
         };
 
         let mut position = 0;
@@ -78,12 +170,33 @@ impl InterruptsSpec {
                     position = i.index;
                 }
 
+                let bind = BindArgs::get(variant.attrs.iter())?.map(|args| (*args).clone());
+                let source = SourceArgs::get(variant.attrs.iter())?.map(|args| (*args).clone());
+
+                if let Some(source) = &source {
+                    SourceArgs::split(&source.status)?;
+
+                    if let Some(clear) = &source.clear {
+                        SourceArgs::split(clear)?;
+                    }
+                }
+
                 interrupts.vectors.insert(
                     position,
                     Vector {
-                        attrs: variant.attrs.clone(),
+                        attrs: variant
+                            .attrs
+                            .iter()
+                            .filter(|attr| {
+                                !attr.path().is_ident(BindArgs::NAME)
+                                    && !attr.path().is_ident(SourceArgs::NAME)
+                            })
+                            .cloned()
+                            .collect(),
                         ident: variant.ident.clone(),
                         position: Index::from(position as usize),
+                        bind,
+                        source,
                     },
                 );
 
@@ -120,17 +233,22 @@ impl ToTokens for InterruptsSpec {
             .map(|vector| &vector.ident)
             .collect::<Vec<_>>();
 
-        let vector_funcs = self.vectors.values().map(|vector| {
-            let ident = &vector.ident;
-            let cfgs = vector.cfgs();
+        // bound vectors are defined locally
+        let vector_funcs = self
+            .vectors
+            .values()
+            .filter(|vector| vector.bind.is_none())
+            .map(|vector| {
+                let ident = &vector.ident;
+                let cfgs = vector.cfgs();
 
-            quote! {
-                #(
-                    #cfgs
-                )*
-                fn #ident();
-            }
-        });
+                quote! {
+                    #(
+                        #cfgs
+                    )*
+                    fn #ident();
+                }
+            });
 
         let vector_ident_strings = vector_idents.iter().map(|ident| ident.to_string());
 
@@ -142,54 +260,136 @@ impl ToTokens for InterruptsSpec {
             }
         };
 
+        // vectors wake the waker of their interrupt before
+        // invoking the handler, so vectors which are not bound
+        // are entered through a shim which does so
+        let wake_shim_ident = |vector: &Vector| {
+            (self.args.wakers && vector.bind.is_none())
+                .then(|| format_ident!("__proto_hal_wake_{}", vector.ident))
+        };
+
         let table_length = (self.vectors.keys().max().unwrap() + 1) as usize;
-        let table_entries = (0..table_length as u32).map(|position| {
-            if let Some(vector) = self.vectors.get(&position) {
-                let ident = &vector.ident;
-                let cfgs = vector.cfgs();
+        let table_entries = |scope: proc_macro2::TokenStream| {
+            (0..table_length as u32).map(move |position| {
+                if let Some(vector) = self.vectors.get(&position) {
+                    let ident = wake_shim_ident(vector).unwrap_or(vector.ident.clone());
+                    let cfgs = vector.cfgs();
+
+                    let anti_cfgs = vector
+                        .cfgs()
+                        .map(|cfg| {
+                            let meta: Meta = cfg.parse_args().unwrap(); // valid #[cfg(...)] attrs have corect args
+
+                            quote! {
+                                #meta
+                            }
+                        })
+                        .collect::<Vec<_>>();
+
+                    let mut result = quote! {
+                        #(
+                            #cfgs
+                        )*
+                        ::proto_hal::interrupt::Vector::handler(#scope #ident),
+                    };
+
+                    if !anti_cfgs.is_empty() {
+                        result.extend(quote! {
+                            #[cfg(not(any(#(#anti_cfgs),*)))]
+                            ::proto_hal::interrupt::Vector::reserved(),
+                        });
+                    }
+
+                    result
+                } else {
+                    quote! {
+                        ::proto_hal::interrupt::Vector::reserved(),
+                    }
+                }
+            })
+        };
+
+        let table = match &self.args.vector_table {
+            Some(vector_table) => {
+                let table_entries = table_entries(quote! { super:: });
+
+                let (default_handler, default_handler_fn) = match &vector_table.default_handler {
+                    Some(default_handler) => (default_handler.to_string(), None),
+                    None => (
+                        "__proto_hal_default_handler".to_string(),
+                        Some(quote! {
+                            #[doc(hidden)]
+                            #[no_mangle]
+                            unsafe extern "C" fn __proto_hal_default_handler() {
+                                loop {
+                                    ::core::hint::spin_loop();
+                                }
+                            }
+                        }),
+                    ),
+                };
 
-                let anti_cfgs = vector
-                    .cfgs()
-                    .map(|cfg| {
-                        let meta: Meta = cfg.parse_args().unwrap(); // valid #[cfg(...)] attrs have corect args
+                // vectors with bound handlers are defined locally, and
+                // weak aliases are only emitted for bare-metal (ELF)
+                // targets, so host builds (e.g. with `testing`) are unaffected
+                let aliases = self
+                    .vectors
+                    .values()
+                    .filter(|vector| vector.bind.is_none())
+                    .map(|vector| {
+                        let cfgs = vector.cfgs();
+                        let weak = format!(".weak {}", vector.ident);
+                        let set = format!(".set {}, {default_handler}", vector.ident);
 
                         quote! {
-                            #meta
+                            #(
+                                #cfgs
+                            )*
+                            #[cfg(target_os = "none")]
+                            ::core::arch::global_asm!(#weak, #set);
                         }
-                    })
-                    .collect::<Vec<_>>();
-
-                let mut result = quote! {
-                    #(
-                        #cfgs
-                    )*
-                    ::proto_hal::interrupt::Vector::handler(#ident),
-                };
-
-                if !anti_cfgs.is_empty() {
-                    result.extend(quote! {
-                        #[cfg(not(any(#(#anti_cfgs),*)))]
-                        ::proto_hal::interrupt::Vector::reserved(),
                     });
-                }
 
-                result
-            } else {
                 quote! {
-                    ::proto_hal::interrupt::Vector::reserved(),
+                    /// The interrupt vector table, for startup code which
+                    /// does not rely on a runtime crate to provide one.
+                    ///
+                    /// Interrupts are handled by defining a function of the
+                    /// same name with `#[no_mangle] extern "C"`. Interrupts
+                    /// without a handler are weakly bound to the default handler
+                    /// (on bare-metal targets only).
+                    ///
+                    /// *Note: The core exceptions (i.e. reset) are not included.*
+                    pub mod vector_table {
+                        /// The interrupt vectors, indexed by position.
+                        pub static INTERRUPTS: [::proto_hal::interrupt::Vector; #table_length] = [
+                            #(
+                                #table_entries
+                            )*
+                        ];
+
+                        #(
+                            #aliases
+                        )*
+
+                        #default_handler_fn
+                    }
                 }
             }
-        });
+            None => {
+                let table_entries = table_entries(quote! {});
 
-        let table = quote! {
-            #[doc(hidden)]
-            #[cfg_attr(target_arch = "arm", link_section = ".vector_table.interrupts")]
-            #[no_mangle]
-            pub static __INTERRUPTS: [::proto_hal::interrupt::Vector; #table_length] = [
-                #(
-                    #table_entries
-                )*
-            ];
+                quote! {
+                    #[doc(hidden)]
+                    #[cfg_attr(target_arch = "arm", link_section = ".vector_table.interrupts")]
+                    #[no_mangle]
+                    pub static __INTERRUPTS: [::proto_hal::interrupt::Vector; #table_length] = [
+                        #(
+                            #table_entries
+                        )*
+                    ];
+                }
+            }
         };
 
         let build_export = quote! {
@@ -200,11 +400,204 @@ impl ToTokens for InterruptsSpec {
             ];
         };
 
+        let wakers = self.args.wakers.then(|| {
+            let waker_entries = (0..table_length).map(|_| {
+                quote! {
+                    ::proto_hal::interrupt::InterruptWaker::new(),
+                }
+            });
+
+            let wake_shims = self.vectors.values().filter_map(|vector| {
+                let shim_ident = wake_shim_ident(vector)?;
+                let ident = &vector.ident;
+                let position = &vector.position;
+                let cfgs = vector.cfgs();
+
+                Some(quote! {
+                    #(
+                        #cfgs
+                    )*
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    unsafe extern "C" fn #shim_ident() {
+                        WAKERS[#position].wake();
+                        #ident();
+                    }
+                })
+            });
+
+            quote! {
+                #(
+                    #wake_shims
+                )*
+
+                /// Waker slots for each interrupt, indexed by vector position.
+                ///
+                /// The waker of an interrupt is woken every time the interrupt
+                /// fires, before its handler is invoked.
+                pub static WAKERS: [::proto_hal::interrupt::InterruptWaker; #table_length] = [
+                    #(
+                        #waker_entries
+                    )*
+                ];
+
+                impl interrupt {
+                    /// The waker slot associated with this interrupt.
+                    pub fn waker(self) -> &'static ::proto_hal::interrupt::InterruptWaker {
+                        &WAKERS[self as usize]
+                    }
+                }
+            }
+        });
+
+        let bound_vectors = self
+            .vectors
+            .values()
+            .filter_map(|vector| Some((vector, vector.bind.as_ref()?)))
+            .collect::<Vec<_>>();
+
+        let handlers = (!bound_vectors.is_empty()).then(|| {
+            let handler_entries = (0..table_length).map(|_| {
+                quote! {
+                    ::proto_hal::interrupt::Handler::new(),
+                }
+            });
+
+            let shims = bound_vectors.iter().map(|(vector, bind)| {
+                let ident = &vector.ident;
+                let position = &vector.position;
+                let cfgs = vector.cfgs().collect::<Vec<_>>();
+
+                let bind_ident = format_ident!(
+                    "bind_{}",
+                    inflector::cases::snakecase::to_snake_case(&ident.to_string())
+                );
+                let bind_doc = format!("Bind a handler to the `{ident}` interrupt.");
+                let entitlements = bind.entitlements.elems.iter();
+                let wake = self.args.wakers.then(|| quote! { WAKERS[#position].wake(); });
+
+                quote! {
+                    #(
+                        #cfgs
+                    )*
+                    #[doc(hidden)]
+                    #[no_mangle]
+                    unsafe extern "C" fn #ident() {
+                        #wake
+                        HANDLERS[#position].call();
+                    }
+
+                    #(
+                        #cfgs
+                    )*
+                    #[doc = #bind_doc]
+                    ///
+                    /// The required entitlements serve as proof that
+                    /// the interrupt source has been configured.
+                    ///
+                    /// *Note: Binding a new handler replaces the previous one.*
+                    pub fn #bind_ident(handler: fn(), #(_: ::proto_hal::stasis::Entitlement<#entitlements>,)*) {
+                        HANDLERS[#position].bind(handler);
+                    }
+                }
+            });
+
+            quote! {
+                /// Handler slots for each interrupt, indexed by vector position.
+                static HANDLERS: [::proto_hal::interrupt::Handler; #table_length] = [
+                    #(
+                        #handler_entries
+                    )*
+                ];
+
+                #(
+                    #shims
+                )*
+            }
+        });
+
+        let sources = self.vectors.values().filter_map(|vector| {
+            let source = vector.source.as_ref()?;
+            let cfgs = vector.cfgs().collect::<Vec<_>>();
+
+            let snake = inflector::cases::snakecase::to_snake_case(&vector.ident.to_string());
+            let is_pending_ident = format_ident!("is_{snake}_pending");
+            let is_pending_doc = format!(
+                "Whether the `{}` interrupt is pending, as indicated by its source.",
+                vector.ident
+            );
+
+            // paths are validated ahead of time
+            let (status_register, status_field) = SourceArgs::split(&source.status).ok()?;
+
+            let clear = source
+                .clear
+                .as_ref()
+                .and_then(|clear| SourceArgs::split(clear).ok())
+                .map(|(clear_register, clear_field)| {
+                    let clear_pending_ident = format_ident!("clear_{snake}_pending");
+                    let clear_pending_doc = format!(
+                        "Clear the pending status of the `{}` interrupt at its source.",
+                        vector.ident
+                    );
+
+                    // the clear accessor only exists if the field is
+                    // writable and its clearing value is known
+                    let clear_ident =
+                        format_ident!("clear_{}", clear_field, span = clear_field.span());
+
+                    quote! {
+                        #(
+                            #cfgs
+                        )*
+                        #[doc = #clear_pending_doc]
+                        ///
+                        /// # Safety
+                        ///
+                        /// The register containing the clear field must
+                        /// not be accessed concurrently.
+                        pub unsafe fn #clear_pending_ident() {
+                            #clear_register::#clear_ident();
+                        }
+                    }
+                });
+
+            // the status accessor only exists if the field is a readable
+            // flag, and is only safe if reading it has no effect
+            let is_set_ident = format_ident!("is_{}_set", status_field, span = status_field.span());
+
+            Some(quote! {
+                #(
+                    #cfgs
+                )*
+                #[doc = #is_pending_doc]
+                pub fn #is_pending_ident() -> bool {
+                    #status_register::#is_set_ident()
+                }
+
+                #clear
+            })
+        });
+
+        // projects with their own vector table need not use a runtime crate
+        let runtime = match (&self.args.runtime, &self.args.vector_table) {
+            (Some(runtime), _) => Some(runtime.clone()),
+            (None, Some(_)) => None,
+            (None, None) => Some(syn::parse_quote! { ::cortex_m_rt }),
+        }
+        .map(|runtime| quote! { pub use #runtime::interrupt; });
+
         tokens.extend(quote! {
-            pub use ::cortex_m_rt::interrupt;
+            #runtime
             #enum_
             #functions
             #table
+            #wakers
+            #handlers
+
+            #(
+                #sources
+            )*
 
             #build_export
         });
@@ -1,16 +1,18 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{
+    collections::HashMap,
+    ops::{Deref, Range},
+};
 
-use darling::{util::SpannedValue, FromMeta};
+use darling::{ast::NestedMeta, util::SpannedValue, FromMeta};
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote_spanned, ToTokens};
-use syn::{parse_quote, spanned::Spanned as _, Expr, Ident, Index, Item, Path};
-use tiva::Validator;
+use syn::{parse_quote, spanned::Spanned as _, Expr, ExprArray, Ident, Item, Meta, Path};
 
 use crate::{
-    access::{Access, AccessArgs},
+    access::{Access, AccessArgs, ReadEffect, WriteEffect},
     utils::{
-        extract_items_from, require_module, FieldOffset, RegisterOffset, Spanned,
-        SynErrorCombinator, Width,
+        docs_of, extract_items_from, require_module, sorted_paths, warning, AllowArgs, FieldOffset,
+        PathArray, RegisterOffset, Spanned, SynErrorCombinator, Validator, Width,
     },
 };
 
@@ -18,16 +20,362 @@ use super::{
     field::{Field, FieldArgs, FieldSpec},
     field_array::{FieldArray, FieldArrayArgs},
     schema::{Numericity, Schema, SchemaArgs, SchemaSpec},
+    variant::Variant,
     Args,
 };
 
+/// Hardware facilities which allow bits of a register
+/// to be modified without a read-modify-write.
+#[derive(Debug, Clone, FromMeta)]
+pub enum AtomicStrategy {
+    /// Bits are set and cleared by writing ones to
+    /// companion registers (e.g. GPIO BSRR/BRR).
+    SetClear(SetClearArgs),
+    /// Bits are individually written through the
+    /// Cortex-M peripheral bit-band alias region.
+    ///
+    /// *Note: The register must reside within the peripheral
+    /// bit-band region, and its writable fields must be a
+    /// single bit wide.*
+    BitBand,
+    /// The read-modify-write is performed with exclusive
+    /// load/store instructions (LDREX/STREX), retrying
+    /// if another access intervenes.
+    ///
+    /// *Note: The bus of the register must support exclusive
+    /// accesses, which is not guaranteed for peripherals.*
+    Exclusive,
+    /// The register is only accessed from a single execution
+    /// context, so read-modify-writes need not be protected.
+    ///
+    /// The tokens of the register are not `Sync`, so they
+    /// cannot be shared between contexts.
+    SingleContext,
+}
+
+/// The execution contexts the tokens of a register
+/// may be moved or shared between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub enum Context {
+    /// The tokens may be moved and shared between contexts.
+    Shared,
+    /// The tokens may be moved between contexts, but not shared.
+    Movable,
+    /// The tokens are bound to the context they were created
+    /// in (i.e. registers local to each core).
+    Local,
+}
+
+/// How the reserved bits of a register (bits not
+/// covered by any field) are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+pub enum ReservedPolicy {
+    /// Reserved bits are written as their last-read
+    /// value, so every write reads the register first.
+    Keep,
+    /// Reserved bits are written as zero.
+    Zero,
+    /// Reserved bits are written as one.
+    One,
+}
+
+#[derive(Debug, Clone, FromMeta)]
+pub struct SetClearArgs {
+    /// The offset of the register which sets bits.
+    pub set: RegisterOffset,
+    /// The offset of the register which clears bits.
+    pub clear: RegisterOffset,
+}
+
+/// Links a register (the low word) with another
+/// register (the high word) which must be accessed
+/// together in a defined order (e.g. RTC TR/DR).
+#[derive(Debug, Clone, FromMeta)]
+pub struct PairArgs {
+    /// The register holding the high word.
+    pub high: Ident,
+    /// Access the high word first. By default,
+    /// the low word is accessed first.
+    #[darling(default)]
+    pub high_first: bool,
+}
+
+/// Repeated access of a data register (i.e. a FIFO).
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default, from_word = || Ok(Self::default()))]
+pub struct StreamArgs {
+    /// The variant of a readable field (`register::field::Variant`)
+    /// to wait for before each value is read (i.e. `csr::rrdy::Ready`).
+    pub read_ready: Option<Path>,
+    /// The variant of a readable field (`register::field::Variant`)
+    /// to wait for before each value is written (i.e. `sr::txe::Empty`).
+    pub write_ready: Option<Path>,
+}
+
+impl StreamArgs {
+    /// Validate the ready conditions of the stream refer to variants
+    /// of readable fields within the provided registers.
+    pub fn validate(&self, registers: &[Register]) -> syn::Result<()> {
+        let mut errors = SynErrorCombinator::new();
+
+        for ready in [&self.read_ready, &self.write_ready].into_iter().flatten() {
+            errors.maybe(|| validate_read_variant(registers, ready));
+        }
+
+        errors.coalesce()
+    }
+}
+
+/// Validate a path of the form `register::field::Variant` refers
+/// to a variant of a readable enumerated field within the provided
+/// registers.
+pub fn validate_read_variant(registers: &[Register], path: &Path) -> syn::Result<()> {
+    let [register, field, variant] = path.segments.iter().collect::<Vec<_>>()[..] else {
+        Err(syn::Error::new_spanned(
+            path,
+            "expected a path of the form `register::field::Variant`",
+        ))?
+    };
+
+    let register = registers
+        .iter()
+        .find(|candidate| candidate.ident == register.ident)
+        .ok_or(syn::Error::new_spanned(register, "register does not exist"))?;
+
+    let field = register
+        .fields
+        .iter()
+        .find(|candidate| candidate.ident == field.ident)
+        .ok_or(syn::Error::new_spanned(field, "field does not exist"))?;
+
+    let Some(Numericity::Enumerated { variants }) =
+        field.read_schema().map(|schema| &schema.numericity)
+    else {
+        Err(syn::Error::new_spanned(
+            path,
+            "expected a variant of a readable enumerated field",
+        ))?
+    };
+
+    variants
+        .iter()
+        .find(|candidate| candidate.ident == variant.ident)
+        .ok_or(syn::Error::new_spanned(variant, "variant does not exist"))?
+        .require_discrete(path)
+}
+
+/// Validate the bank of a register refers to a state of a
+/// resolvable field of an unbanked register within the provided
+/// registers.
+pub fn validate_bank(registers: &[Register], path: &Path) -> syn::Result<()> {
+    let [register, field, variant] = path.segments.iter().collect::<Vec<_>>()[..] else {
+        Err(syn::Error::new_spanned(
+            path,
+            "expected a path of the form `register::field::Variant`",
+        ))?
+    };
+
+    let register = registers
+        .iter()
+        .find(|candidate| candidate.ident == register.ident)
+        .ok_or(syn::Error::new_spanned(register, "register does not exist"))?;
+
+    if register.args.bank.is_some() {
+        Err(syn::Error::new_spanned(
+            path,
+            "bank selectors cannot themselves be banked",
+        ))?
+    }
+
+    let field = register
+        .fields
+        .iter()
+        .find(|candidate| candidate.ident == field.ident)
+        .ok_or(syn::Error::new_spanned(field, "field does not exist"))?;
+
+    let Some(states) = field.states() else {
+        Err(syn::Error::new_spanned(
+            path,
+            "expected a state of a resolvable field",
+        ))?
+    };
+
+    states
+        .iter()
+        .find(|candidate| candidate.ident == variant.ident)
+        .ok_or(syn::Error::new_spanned(variant, "state does not exist"))?
+        .require_discrete(path)
+}
+
+/// An entry of a preset, either a variant of an enumerated
+/// field (`field::Variant`) or a value of a numeric field
+/// (`field = value`).
+#[derive(Debug, Clone)]
+pub enum PresetEntry {
+    Variant(Path),
+    Value { field: Ident, value: Expr },
+}
+
+impl PresetEntry {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Variant(path) => path.span(),
+            Self::Value { field, value } => field.span().join(value.span()).unwrap_or(field.span()),
+        }
+    }
+}
+
+/// Named configurations of writable fields,
+/// i.e. `name = [field::Variant, field = 0x0683, ..]`.
+#[derive(Debug, Clone, Default)]
+pub struct Presets {
+    pub elems: Vec<(Ident, Vec<PresetEntry>)>,
+}
+
+impl FromMeta for Presets {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        Ok(Self {
+            elems: items
+                .iter()
+                .map(|item| match item {
+                    NestedMeta::Meta(meta @ Meta::NameValue(name_value)) => Ok((
+                        name_value
+                            .path
+                            .require_ident()
+                            .map_err(darling::Error::from)?
+                            .clone(),
+                        ExprArray::from_meta(meta)?
+                            .elems
+                            .into_iter()
+                            .map(|expr| match expr {
+                                Expr::Path(path) => Ok(PresetEntry::Variant(path.path)),
+                                Expr::Assign(assign) => match *assign.left {
+                                    Expr::Path(path) => Ok(PresetEntry::Value {
+                                        field: path
+                                            .path
+                                            .require_ident()
+                                            .map_err(darling::Error::from)?
+                                            .clone(),
+                                        value: *assign.right,
+                                    }),
+                                    left => Err(darling::Error::custom("expected a field")
+                                        .with_span(&left)),
+                                },
+                                expr => Err(darling::Error::custom(
+                                    "expected `field::Variant` or `field = value`",
+                                )
+                                .with_span(&expr)),
+                            })
+                            .collect::<Result<_, _>>()?,
+                    )),
+                    _ => Err(darling::Error::custom(
+                        "expected a preset of the form `name = [field::Variant, field = value, ..]`",
+                    )
+                    .with_span(item)),
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// The steps of a sequence, and optionally the
+/// steps which relock it (`name(steps = [..], relock = [..])`).
+#[derive(Debug, Clone, Default, FromMeta)]
+pub struct SequenceArgs {
+    pub steps: PathArray,
+    #[darling(default)]
+    pub relock: PathArray,
+}
+
+/// Named sequences of variants of writable fields (`field::Variant`),
+/// either `name = [..]` or `name(steps = [..], relock = [..])`.
+#[derive(Debug, Clone, Default)]
+pub struct Sequences {
+    pub elems: Vec<(Ident, SequenceArgs)>,
+}
+
+impl FromMeta for Sequences {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        Ok(Self {
+            elems: items
+                .iter()
+                .map(|item| match item {
+                    NestedMeta::Meta(meta @ Meta::NameValue(name_value)) => Ok((
+                        name_value
+                            .path
+                            .require_ident()
+                            .map_err(darling::Error::from)?
+                            .clone(),
+                        SequenceArgs {
+                            steps: PathArray::from_meta(meta)?,
+                            relock: PathArray::default(),
+                        },
+                    )),
+                    NestedMeta::Meta(meta @ Meta::List(list)) => Ok((
+                        list.path
+                            .require_ident()
+                            .map_err(darling::Error::from)?
+                            .clone(),
+                        SequenceArgs::from_meta(meta)?,
+                    )),
+                    _ => Err(darling::Error::custom(
+                        "expected a sequence of the form `name = [field::Variant, ..]`",
+                    )
+                    .with_span(item)),
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Default, FromMeta)]
 #[darling(default)]
 pub struct RegisterArgs {
     pub offset: Option<RegisterOffset>,
+    pub size: Option<SpannedValue<Width>>,
 
     #[darling(default)]
     pub auto_increment: bool,
+    #[darling(default)]
+    pub asynchronous: bool,
+    pub atomic: Option<AtomicStrategy>,
+    pub pair: Option<PairArgs>,
+    /// Named sequences of values which must be written to the
+    /// register in order (e.g. FLASH KEYR, IWDG KR unlock keys).
+    ///
+    /// Performing a sequence consumes the register, yielding a
+    /// token which may be required as a write entitlement by
+    /// other fields, and which is relocked to yield the register.
+    pub sequences: Sequences,
+    /// Named configurations of the entire register,
+    /// applied in a single write. Enumerated fields are
+    /// set to variants, numeric fields to values.
+    pub presets: Presets,
+    pub stream: Option<StreamArgs>,
+    /// The state of a resolvable field (`register::field::Variant`)
+    /// which selects the bank exposing this register (i.e. banks of
+    /// registers sharing an address, switched by bank select bits).
+    pub bank: Option<Path>,
+    /// Read the register back after every write, invoking the
+    /// handler installed with `write_mismatch_handler!` if the
+    /// stable fields do not hold the written values.
+    ///
+    /// *Note: Without the `verify-writes` feature of proto-hal,
+    /// no handler is installed, so mismatches panic instead.*
+    #[darling(default)]
+    pub verify: bool,
+    /// How reserved bits are written, as dictated by the
+    /// reference manual (i.e. `reserved(keep)`).
+    pub reserved: Option<SpannedValue<ReservedPolicy>>,
+    /// The execution contexts the tokens of this register may be
+    /// moved or shared between (i.e. `context(local)`). By default,
+    /// tokens are shared unless the register is accessed from a
+    /// single context, in which case they are movable.
+    pub context: Option<SpannedValue<Context>>,
+    pub allow: AllowArgs,
+    /// The reset value of the entire register, checked
+    /// against the resets of the fields.
+    pub reset_value: Option<SpannedValue<u32>>,
 
     // field args to inherit
     pub width: Option<SpannedValue<Width>>,
@@ -97,6 +445,7 @@ pub struct RegisterSpec {
     pub ident: Ident,
     pub offset: RegisterOffset,
     pub fields: Vec<Field>,
+    pub arrays: Vec<FieldArray>,
 }
 
 #[derive(Debug)]
@@ -127,6 +476,7 @@ impl RegisterSpec {
             ident,
             offset,
             fields: Vec::new(),
+            arrays: Vec::new(),
         };
 
         let mut field_offset = 0 as FieldOffset;
@@ -163,6 +513,7 @@ impl RegisterSpec {
 
                         let field = Field::validate(FieldSpec::parse(
                             module.ident.clone(),
+                            docs_of(&module.attrs),
                             field_args.offset.unwrap_or(field_offset),
                             schemas,
                             field_args,
@@ -179,6 +530,7 @@ impl RegisterSpec {
 
                         let field_array = FieldArray::parse(
                             module.ident.clone(),
+                            docs_of(&module.attrs),
                             field_array_args.field.offset.unwrap_or(field_offset),
                             schemas,
                             field_array_args,
@@ -186,8 +538,9 @@ impl RegisterSpec {
                         )?;
 
                         field_offset = field_array.inherited.offset
-                            + field_array.inherited.width() * field_array.count() as FieldOffset;
+                            + field_array.stride() * field_array.count() as FieldOffset;
                         register.fields.extend(field_array.to_fields()?);
+                        register.arrays.push(field_array);
 
                         Ok(())
                     }
@@ -222,770 +575,4470 @@ impl RegisterSpec {
     pub fn is_resolvable(&self) -> bool {
         self.fields.iter().any(|field| field.is_resolvable())
     }
-}
-
-impl Validator<RegisterSpec> for Register {
-    type Error = syn::Error;
-
-    fn validate(spec: RegisterSpec) -> Result<Self, Self::Error> {
-        let mut errors = SynErrorCombinator::new();
 
-        for field in &spec.fields {
-            if field.args.offset.is_none() && !spec.args.auto_increment {
-                errors.push(syn::Error::new(
-                    field.args.span(),
-                    "field offset must be specified. to infer offsets, use `auto_increment`",
-                ));
-            }
-        }
+    /// The width of this register in bits.
+    pub fn size(&self) -> Width {
+        self.args.size.as_deref().copied().unwrap_or(32)
+    }
 
-        for slice in spec.fields.windows(2) {
-            let lhs = slice.first().unwrap();
-            let rhs = slice.last().unwrap();
-            if lhs.offset + lhs.width() > rhs.offset {
-                let msg = format!(
-                    "{} {{ domain: {}..{} }}, {} {{ domain: {}..{} }}",
-                    lhs.ident,
-                    lhs.offset,
-                    lhs.offset + lhs.width(),
-                    rhs.ident,
-                    rhs.offset,
-                    rhs.offset + rhs.width(),
-                );
+    /// The number of bytes occupied by this register.
+    pub fn byte_size(&self) -> RegisterOffset {
+        self.size() as RegisterOffset / 8
+    }
 
-                let mut e = syn::Error::new(
-                    spec.args.span(),
-                    format!("field domains overlapping or unordered. {msg}"),
-                );
+    /// The number of bits of this register covered by fields.
+    pub fn covered_bits(&self) -> u32 {
+        self.coverage_mask().count_ones()
+    }
 
-                e.combine(syn::Error::new(
-                    lhs.ident.span(),
-                    format!(
-                        "field '{}' is overlapping or out of order with '{}'. {}",
-                        lhs.ident, rhs.ident, msg,
-                    ),
-                ));
+    /// The bit ranges of this register not covered by any field
+    /// (i.e. reserved bits, or bits missed when describing it).
+    pub fn uncovered_ranges(&self) -> Vec<Range<FieldOffset>> {
+        let mask = self.coverage_mask();
+        let mut ranges = Vec::<Range<FieldOffset>>::new();
 
-                e.combine(syn::Error::new(
-                    rhs.ident.span(),
-                    format!(
-                        "field '{}' is overlapping or out of order with '{}'. {}",
-                        rhs.ident, lhs.ident, msg,
-                    ),
-                ));
+        for bit in 0..self.size() {
+            if mask & (1 << bit) != 0 {
+                continue;
+            }
 
-                errors.push(e);
+            match ranges.last_mut() {
+                Some(range) if range.end == bit => range.end += 1,
+                _ => ranges.push(bit..bit + 1),
             }
         }
 
-        errors.coalesce()?;
-
-        Ok(Self { spec })
+        ranges
     }
-}
-
-pub enum AccessMarker {
-    Read,
-    Write,
-}
 
-enum Filter {
-    Resolvable,
-    Unresolvable,
-    Writable,
-    Readable,
-    Numeric(AccessMarker),
-    Enumerated(AccessMarker),
-}
+    /// The execution contexts the tokens of this register
+    /// may be moved or shared between.
+    ///
+    /// *Note: Registers accessed from a single context are never
+    /// shared, as their read-modify-writes are not protected.*
+    pub fn context(&self) -> Context {
+        match (self.args.context.as_deref(), &self.args.atomic) {
+            (Some(context), _) => *context,
+            (None, Some(AtomicStrategy::SingleContext)) => Context::Movable,
+            (None, _) => Context::Shared,
+        }
+    }
 
-impl Filter {
-    fn retains(&self, field: &Field) -> bool {
-        match self {
-            Self::Resolvable => field.is_resolvable(),
-            Self::Unresolvable => !field.is_resolvable(),
-            Self::Writable => field.access.is_write(),
-            Self::Readable => field.access.is_read(),
-            // TODO: quite a lot of repeat code here...
-            Self::Numeric(marker) => match marker {
-                AccessMarker::Read => {
-                    let schema = match &field.access {
-                        Access::Read(read) | Access::ReadWrite { read, write: _ } => &read.schema,
-                        _ => return false,
-                    };
+    /// The bits of this register not covered by any field.
+    pub fn reserved_mask(&self) -> u32 {
+        !self.coverage_mask() & (u32::MAX >> (32 - self.size() as u32))
+    }
 
-                    schema.numericity.is_numeric()
-                }
-                AccessMarker::Write => {
-                    let schema = match &field.access {
-                        Access::Write(write) | Access::ReadWrite { read: _, write } => {
-                            &write.schema
-                        }
-                        _ => return false,
-                    };
+    fn coverage_mask(&self) -> u32 {
+        self.fields.iter().fold(0, |mask, field| {
+            mask | (u32::MAX >> (32 - field.width() as u32)) << field.offset
+        })
+    }
 
-                    schema.numericity.is_numeric()
-                }
-            },
-            Self::Enumerated(marker) => match marker {
-                AccessMarker::Read => {
-                    let schema = match &field.access {
-                        Access::Read(read) | Access::ReadWrite { read, write: _ } => &read.schema,
-                        _ => return false,
-                    };
+    /// The stage in which each field is written when fields are
+    /// changed together, fields being written in separate accesses
+    /// after all fields they must be written after.
+    ///
+    /// Yields `None` if the write ordering constraints are cyclic.
+    pub fn write_stages(&self) -> Option<Vec<usize>> {
+        let mut stages = vec![0; self.fields.len()];
+
+        // the longest chain of constraints is shorter than the number
+        // of fields, so the stages only fail to settle if cyclic
+        for _ in 0..=self.fields.len() {
+            let mut settled = true;
+
+            for (i, field) in self.fields.iter().enumerate() {
+                let stage = field
+                    .write_after()
+                    .filter_map(|path| {
+                        self.fields
+                            .iter()
+                            .position(|other| path.is_ident(&other.ident))
+                    })
+                    .map(|j| stages[j] + 1)
+                    .max()
+                    .unwrap_or(0);
 
-                    schema.numericity.is_enumerated()
+                if stage != stages[i] {
+                    stages[i] = stage;
+                    settled = false;
                 }
-                AccessMarker::Write => {
-                    let schema = match &field.access {
-                        Access::Write(write) | Access::ReadWrite { read: _, write } => {
-                            &write.schema
-                        }
-                        _ => return false,
-                    };
+            }
 
-                    schema.numericity.is_enumerated()
-                }
-            },
+            if settled {
+                return Some(stages);
+            }
         }
+
+        None
     }
-}
 
-struct FieldIter<'a, I>
-where
-    I: Iterator<Item = &'a Field>,
-{
-    iter: I,
-    filters: Vec<Filter>,
-}
+    /// Locate the field and variant written by a step
+    /// of a sequence or an entry of a preset.
+    fn written_variant(&self, path: &syn::Path) -> syn::Result<(&Field, &Variant)> {
+        let [field, variant] = path.segments.iter().collect::<Vec<_>>()[..] else {
+            Err(syn::Error::new_spanned(
+                path,
+                "expected a path of the form `field::Variant`",
+            ))?
+        };
 
-impl<'a, I> FieldIter<'a, I>
-where
-    I: Iterator<Item = &'a Field>,
-{
-    const fn new(iter: I) -> Self {
-        Self {
-            iter,
-            filters: Vec::new(),
+        let field = self
+            .fields
+            .iter()
+            .find(|candidate| candidate.ident == field.ident)
+            .ok_or(syn::Error::new_spanned(field, "field does not exist"))?;
+
+        if field.write_entitlements().is_some() {
+            Err(syn::Error::new_spanned(
+                path,
+                "fields requiring entitlements to be written cannot be written by sequences or presets",
+            ))?
         }
-    }
 
-    fn resolvable(mut self) -> Self {
-        self.filters.push(Filter::Resolvable);
+        let Some(Numericity::Enumerated { variants }) =
+            field.write_schema().map(|schema| &schema.numericity)
+        else {
+            Err(syn::Error::new_spanned(
+                path,
+                "sequence steps must write enumerated fields",
+            ))?
+        };
 
-        self
-    }
+        let variant = variants
+            .iter()
+            .find(|candidate| candidate.ident == variant.ident)
+            .ok_or(syn::Error::new_spanned(variant, "variant does not exist"))?;
 
-    fn unresolvable(mut self) -> Self {
-        self.filters.push(Filter::Unresolvable);
+        variant.require_discrete(path)?;
 
-        self
+        Ok((field, variant))
     }
 
-    fn writable(mut self) -> Self {
-        self.filters.push(Filter::Writable);
-
-        self
-    }
+    /// Locate the numeric field written by an entry of a preset.
+    fn written_value(&self, ident: &Ident) -> syn::Result<&Field> {
+        let field = self
+            .fields
+            .iter()
+            .find(|candidate| &candidate.ident == ident)
+            .ok_or(syn::Error::new(ident.span(), "field does not exist"))?;
+
+        if field.write_entitlements().is_some() {
+            Err(syn::Error::new(
+                ident.span(),
+                "fields requiring entitlements to be written cannot be written by presets",
+            ))?
+        }
 
-    fn readable(mut self) -> Self {
-        self.filters.push(Filter::Readable);
+        if !field
+            .write_schema()
+            .is_some_and(|schema| schema.numericity.is_numeric())
+        {
+            Err(syn::Error::new(
+                ident.span(),
+                "values can only be written to numeric fields, write a variant instead (`field::Variant`)",
+            ))?
+        }
 
-        self
+        Ok(field)
     }
 
-    fn numeric(mut self, access: AccessMarker) -> Self {
-        self.filters.push(Filter::Numeric(access));
-
-        self
+    /// The bits of this register which are expected to read back
+    /// as written: those of readable and writable fields without
+    /// effects, which the hardware does not change on its own.
+    pub fn verify_mask(&self) -> u32 {
+        self.fields
+            .iter()
+            .filter(|field| {
+                field.access.is_read()
+                    && field.access.is_write()
+                    && !field.args.volatile
+                    && field.access.read_effect().is_none()
+                    && field.access.write_effect().is_none()
+                    && !field
+                        .variant_enums()
+                        .iter()
+                        .any(|(_, variants)| variants.iter().any(|variant| variant.args.hardware))
+            })
+            .fold(0, |mask, field| mask | field.mask())
     }
 
-    fn enumerated(mut self, access: AccessMarker) -> Self {
-        self.filters.push(Filter::Enumerated(access));
+    /// The reset value of this register, as specified or derived
+    /// from the resets of the fields, if the reset of every field
+    /// is known.
+    pub fn reset_value(&self) -> Option<u32> {
+        if let Some(reset_value) = self.args.reset_value.as_deref() {
+            return Some(*reset_value);
+        }
 
-        self
+        self.fields
+            .iter()
+            .filter(|field| self.is_active_at_reset(field))
+            .try_fold(0, |value, field| {
+                Some(value | (field.reset_bits()? << field.offset) & field.mask())
+            })
     }
 
-    fn idents(self) -> impl Iterator<Item = &'a Ident> + use<'a, I> {
-        self.map(|field| &field.ident)
+    /// Whether this register consists of exactly one
+    /// field occupying the entire register.
+    pub fn is_single_field(&self) -> bool {
+        matches!(self.fields.as_slice(), [field] if field.width() == self.size())
     }
 
-    fn tys(self) -> impl Iterator<Item = Ident> + use<'a, I> {
-        self.map(|field| {
-            Ident::new(
-                &inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string()),
-                Span::call_site(),
-            )
-        })
+    /// The resolvable field whose state selects whether `field` is
+    /// the valid view of bits it shares with other fields (if any).
+    fn view_selector(&self, field: &Field) -> Option<&Field> {
+        self.fields
+            .iter()
+            .filter(|other| other.ident != field.ident && other.mask() & field.mask() != 0)
+            .find_map(|other| field.exclusive_selector(other))
+            .and_then(|selector| {
+                self.fields
+                    .iter()
+                    .find(|candidate| &candidate.ident == selector)
+            })
     }
-}
 
-impl<'a, I> Iterator for FieldIter<'a, I>
-where
-    I: Iterator<Item = &'a Field>,
-{
-    type Item = &'a Field;
+    /// Whether `field` is valid in the reset state of the register,
+    /// as fields sharing bits are only valid in some modes.
+    fn is_active_at_reset(&self, field: &Field) -> bool {
+        let Some(selector) = self.view_selector(field) else {
+            return true;
+        };
 
-    fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            if let Some(field) = self.iter.next() {
-                if self.filters.iter().all(|filter| filter.retains(field)) {
-                    break Some(field);
-                } else {
-                    continue;
-                }
-            } else {
-                break None;
-            }
-        }
-    }
-}
+        let Some(Expr::Path(reset)) = &selector.args.reset else {
+            return false;
+        };
 
-impl Register {
-    fn fields(&self) -> FieldIter<'_, impl Iterator<Item = &'_ Field>> {
-        FieldIter::new(self.fields.iter())
+        reset
+            .path
+            .get_ident()
+            .is_some_and(|reset| field.selecting_states(&selector.ident).contains(reset))
     }
 
-    fn generate_field_bodies(&self) -> TokenStream2 {
+    /// The type parameter of this register holding
+    /// the state of a resolvable field.
+    fn state_ty(&self, field: &Field) -> TokenStream2 {
         let span = self.args.span();
-        let field_bodies = self.fields().map(|field| quote_spanned! { span => #field });
+        let ty = Ident::new(
+            &inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string()),
+            Span::call_site(),
+        );
 
-        quote_spanned! { span =>
-            #(
-                #field_bodies
-            )*
-        }
+        quote_spanned! { span => #ty }
     }
 
-    fn generate_offset_const(&self) -> TokenStream2 {
+    /// The bits the states of resolvable `fields` occupy in a value
+    /// of this register, where `state` yields the type of the state
+    /// of a field.
+    ///
+    /// Fields sharing bits only contribute when selected by the state
+    /// of their selector, so the bits of inactive views are left clear.
+    fn generate_state_bits<'a>(
+        &self,
+        fields: impl Iterator<Item = &'a Field>,
+        state: impl Fn(&Field) -> TokenStream2,
+    ) -> Vec<TokenStream2> {
         let span = self.args.span();
-        let offset = self.offset;
 
-        quote_spanned! { span =>
-            /// The offset of this register within the block.
-            pub const OFFSET: u32 = #offset;
-        }
+        fields
+            .map(|field| {
+                let ident = &field.ident;
+                let ty = state(field);
+                let bits = quote_spanned! { span =>
+                    <#ty as #ident::State>::RAW.bits() << #ident::OFFSET
+                };
+
+                match self.view_selector(field) {
+                    Some(selector) => {
+                        let selector_ty = state(selector);
+
+                        quote_spanned! { span =>
+                            if <#selector_ty as #ident::View>::ACTIVE { #bits } else { 0 }
+                        }
+                    }
+                    None => bits,
+                }
+            })
+            .collect()
+    }
+
+    /// Bounds requiring the states of selectors to decide which
+    /// of the views they select, where `state` yields the type of
+    /// the state of a field.
+    fn generate_view_bounds(&self, state: impl Fn(&Field) -> TokenStream2) -> Vec<TokenStream2> {
+        self.generate_qualified_view_bounds(None, state)
+    }
+
+    /// Bounds requiring the states of selectors to decide which of
+    /// the views they select, from outside of the register module
+    /// (i.e. the block) when `module` is provided.
+    pub fn generate_qualified_view_bounds(
+        &self,
+        module: Option<&Ident>,
+        state: impl Fn(&Field) -> TokenStream2,
+    ) -> Vec<TokenStream2> {
+        let span = self.args.span();
+        let module = module.map(|module| quote_spanned! { span => #module:: });
+
+        self.fields
+            .iter()
+            .filter(|field| field.is_resolvable())
+            .filter_map(|field| {
+                let ident = &field.ident;
+                let selector_ty = state(self.view_selector(field)?);
+
+                Some(quote_spanned! { span => #selector_ty: #module #ident::View })
+            })
+            .collect()
+    }
+}
+
+impl Validator<RegisterSpec> for Register {
+    type Error = syn::Error;
+
+    fn validate(spec: RegisterSpec) -> Result<Self, Self::Error> {
+        let mut errors = SynErrorCombinator::new();
+
+        if let Some(size) = &spec.args.size {
+            if !matches!(**size, 8 | 16 | 32) {
+                errors.push(syn::Error::new(
+                    size.span(),
+                    "register size must be one of 8, 16, or 32",
+                ));
+            }
+        }
+
+        for field in &spec.fields {
+            if field.access.write_effect() == Some(WriteEffect::Trigger)
+                && field
+                    .write_schema()
+                    .is_some_and(|schema| !schema.numericity.is_enumerated())
+            {
+                errors.push(syn::Error::new(
+                    field.ident.span(),
+                    "trigger fields must be enumerated",
+                ));
+            }
+
+            if field.offset as u32 + field.width() as u32 > spec.size() as u32 {
+                errors.push(syn::Error::new(
+                    field.ident.span(),
+                    format!(
+                        "field '{}' {{ domain: {}..{} }} exceeds the register size of {} bits",
+                        field.ident,
+                        field.offset,
+                        field.offset + field.width(),
+                        spec.size(),
+                    ),
+                ));
+            }
+
+            if let Some(access_size) = &field.args.access_size {
+                let access_size = **access_size;
+                let span = field.args.access_size.as_ref().unwrap().span();
+
+                if !matches!(access_size, 8 | 16 | 32) || access_size > spec.size() {
+                    errors.push(syn::Error::new(
+                        span,
+                        format!(
+                            "access size must be one of 8, 16, or 32 and must not exceed the register size of {} bits",
+                            spec.size(),
+                        ),
+                    ));
+                } else if field.offset / access_size
+                    != (field.offset + field.width() - 1) / access_size
+                {
+                    errors.push(syn::Error::new(
+                        span,
+                        format!(
+                            "field '{}' {{ domain: {}..{} }} is not contained within a single aligned {access_size} bit access",
+                            field.ident,
+                            field.offset,
+                            field.offset + field.width(),
+                        ),
+                    ));
+                }
+
+                if field.is_resolvable()
+                    || !field.access.is_write()
+                    || field
+                        .write_schema()
+                        .is_some_and(|schema| schema.numericity.is_enumerated())
+                {
+                    errors.push(syn::Error::new(
+                        span,
+                        "access sizes are only supported on writable numeric fields",
+                    ));
+                }
+            }
+
+            if spec.args.stream.is_some()
+                && (spec.fields.len() != 1
+                    || [field.read_schema(), field.write_schema()]
+                        .into_iter()
+                        .flatten()
+                        .any(|schema| schema.numericity.is_enumerated()))
+            {
+                errors.push(syn::Error::new(
+                    field.ident.span(),
+                    "streamed registers must consist of a single numeric field",
+                ));
+            }
+
+            if field.args.offset.is_none() && !spec.args.auto_increment {
+                errors.push(syn::Error::new(
+                    field.args.span(),
+                    "field offset must be specified. to infer offsets, use `auto_increment`",
+                ));
+            }
+        }
+
+        if spec.args.verify {
+            if spec
+                .fields
+                .iter()
+                .any(|field| field.access.read_effect().is_some())
+            {
+                errors.push(syn::Error::new(
+                    spec.ident.span(),
+                    "registers with read effects cannot be verified, as reading back would disturb them",
+                ));
+            } else if spec.verify_mask() == 0 {
+                errors.push(syn::Error::new(
+                    spec.ident.span(),
+                    "verified registers must have at least one readable and writable field without effects",
+                ));
+            }
+        }
+
+        // a failed exclusive store retries with a fresh read,
+        // which would disturb fields with read effects
+        if matches!(spec.args.atomic, Some(AtomicStrategy::Exclusive))
+            && spec
+                .fields
+                .iter()
+                .any(|field| field.access.read_effect().is_some())
+        {
+            errors.push(syn::Error::new(
+                spec.ident.span(),
+                "registers with read effects cannot be modified with exclusive accesses, as retries read the register again",
+            ));
+        }
+
+        // bits are written one at a time, so only single-bit
+        // fields are modified atomically
+        if matches!(spec.args.atomic, Some(AtomicStrategy::BitBand)) {
+            for field in spec
+                .fields
+                .iter()
+                .filter(|field| field.access.is_write() && field.width() != 1)
+            {
+                errors.push(syn::Error::new(
+                    field.ident.span(),
+                    "fields of bit-banded registers must be a single bit wide, as wider fields would be written a bit at a time",
+                ));
+            }
+        }
+
+        // the tokens of registers accessed from a single context
+        // are never `Sync` (see `context`)
+        if let (Some(context), Some(AtomicStrategy::SingleContext)) =
+            (&spec.args.context, &spec.args.atomic)
+        {
+            if **context == Context::Shared {
+                errors.push(syn::Error::new(
+                    context.span(),
+                    "registers accessed from a single context cannot be shared between contexts",
+                ));
+            }
+        }
+
+        if let Some(reserved) = &spec.args.reserved {
+            if spec.reserved_mask() == 0 {
+                errors.push(syn::Error::new(
+                    reserved.span(),
+                    "register has no reserved bits",
+                ));
+            } else if **reserved == ReservedPolicy::Keep
+                && spec
+                    .fields
+                    .iter()
+                    .any(|field| field.access.read_effect().is_some())
+            {
+                errors.push(syn::Error::new(
+                    reserved.span(),
+                    "reserved bits of registers with read effects cannot be kept, as reading them would disturb the register",
+                ));
+            } else if **reserved == ReservedPolicy::Keep
+                && !spec.fields.iter().any(|field| field.access.is_read())
+            {
+                errors.push(syn::Error::new(
+                    reserved.span(),
+                    "reserved bits of write-only registers cannot be kept, as they cannot be read",
+                ));
+            }
+        }
+
+        if let Some(reset_value) = &spec.args.reset_value {
+            for field in &spec.fields {
+                let Some(bits) = field.reset_bits() else {
+                    continue;
+                };
+
+                let expected = (**reset_value & field.mask()) >> field.offset;
+
+                if bits != expected {
+                    errors.push(syn::Error::new(
+                        field.ident.span(),
+                        format!(
+                            "field reset {bits:#x} conflicts with the register reset value {:#x}, which resets this field to {expected:#x}",
+                            **reset_value,
+                        ),
+                    ));
+                }
+            }
+        }
+
+        for field in &spec.fields {
+            let Some(write_after) = &field.args.write_after else {
+                continue;
+            };
+
+            for path in &write_after.elems {
+                let Some(other) = spec.fields.iter().find(|other| path.is_ident(&other.ident))
+                else {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        "field does not exist in this register",
+                    ));
+
+                    continue;
+                };
+
+                if other.ident == field.ident {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        "fields cannot be written after themselves",
+                    ));
+                } else if !field.is_resolvable() || !other.is_resolvable() {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        "write ordering is only supported between resolvable fields, as only their transitions are gated",
+                    ));
+                }
+            }
+
+            if spec
+                .fields
+                .iter()
+                .any(|field| field.access.read_effect().is_some())
+            {
+                errors.push(syn::Error::new(
+                    write_after.span(),
+                    "registers with read effects cannot order writes, as ordered writes read the register",
+                ));
+            }
+
+            if let Some((ident, _)) = spec.args.presets.elems.first() {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "presets are applied in a single write, which violates the write ordering of `{}`",
+                        field.ident
+                    ),
+                ));
+            }
+        }
+
+        if spec.write_stages().is_none() {
+            errors.push(syn::Error::new(
+                spec.ident.span(),
+                "write ordering of fields is cyclic",
+            ));
+        }
+
+        for (i, (ident, sequence)) in spec.args.sequences.elems.iter().enumerate() {
+            if sequence.steps.elems.is_empty() {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    "sequences must contain at least one step",
+                ));
+            }
+
+            // the token of a sequence holds the register
+            if spec.fields.iter().any(|field| field.is_resolvable()) {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    "sequences cannot be performed on registers with resolvable fields",
+                ));
+            }
+
+            if spec.args.sequences.elems[..i]
+                .iter()
+                .any(|(other, _)| other == ident)
+            {
+                errors.push(syn::Error::new(ident.span(), "sequence exists already"));
+            }
+
+            for step in sequence.steps.elems.iter().chain(&sequence.relock.elems) {
+                errors.maybe(|| spec.written_variant(step).map(|_| ()));
+            }
+        }
+
+        for (i, (ident, entries)) in spec.args.presets.elems.iter().enumerate() {
+            let mut written = Vec::new();
+
+            for entry in entries {
+                errors.maybe(|| {
+                    let field = match entry {
+                        PresetEntry::Variant(path) => {
+                            let (field, variant) = spec.written_variant(path)?;
+
+                            if !variant.entitlements.is_empty() {
+                                Err(syn::Error::new_spanned(
+                                    path,
+                                    "presets cannot write variants with entitlements",
+                                ))?
+                            }
+
+                            field
+                        }
+                        PresetEntry::Value { field, .. } => spec.written_value(field)?,
+                    };
+
+                    if written.contains(&&field.ident) {
+                        Err(syn::Error::new(
+                            entry.span(),
+                            "field is already written by this preset",
+                        ))?
+                    }
+
+                    written.push(&field.ident);
+
+                    Ok(())
+                });
+            }
+
+            for field in spec.fields.iter().filter(|field| field.is_resolvable()) {
+                if !written.contains(&&field.ident) {
+                    errors.push(syn::Error::new(
+                        ident.span(),
+                        format!("preset must specify the state of `{}`", field.ident),
+                    ));
+                }
+            }
+
+            // presets share the namespace of sequences, both
+            // being methods of the register
+            if spec.args.presets.elems[..i]
+                .iter()
+                .map(|(other, _)| other)
+                .chain(spec.args.sequences.elems.iter().map(|(other, _)| other))
+                .any(|other| other == ident)
+            {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    "preset or sequence exists already",
+                ));
+            }
+        }
+
+        // trigger methods are named after their variants, so they
+        // must not collide across fields or with other methods
+        let mut trigger_methods = Vec::<String>::new();
+
+        for field in spec
+            .fields
+            .iter()
+            .filter(|field| field.access.write_effect() == Some(WriteEffect::Trigger))
+        {
+            let Some(Numericity::Enumerated { variants }) =
+                field.write_schema().map(|schema| &schema.numericity)
+            else {
+                continue;
+            };
+
+            for variant in variants.iter().filter(|variant| variant.range.is_none()) {
+                let name = inflector::cases::snakecase::to_snake_case(&variant.ident.to_string());
+
+                if trigger_methods.contains(&name)
+                    || spec
+                        .args
+                        .presets
+                        .elems
+                        .iter()
+                        .map(|(other, _)| other)
+                        .chain(spec.args.sequences.elems.iter().map(|(other, _)| other))
+                        .any(|other| *other == name)
+                {
+                    errors.push(syn::Error::new(
+                        variant.ident.span(),
+                        format!("trigger method `{name}` exists already"),
+                    ));
+                }
+
+                trigger_methods.push(name);
+            }
+        }
+
+        // an exempt pair must not hide the overlap of others,
+        // so every pair is compared
+        for (i, lhs) in spec.fields.iter().enumerate() {
+            for rhs in &spec.fields[i + 1..] {
+                if lhs.args.allow.overlap || rhs.args.allow.overlap {
+                    continue;
+                }
+
+                // fields in exclusive modes may reinterpret the same bits,
+                // provided the mode is tracked by this register
+                if let Some(selector) = lhs.exclusive_selector(rhs) {
+                    if lhs.mask() & rhs.mask() != 0
+                        && !spec.fields.iter().any(|field| {
+                            &field.ident == selector
+                                && field.is_resolvable()
+                                && !lhs.selecting_states(selector).is_empty()
+                                && !rhs.selecting_states(selector).is_empty()
+                        })
+                    {
+                        errors.push(syn::Error::new(
+                            rhs.ident.span(),
+                            format!(
+                                "fields sharing bits with '{}' must be entitled by states of a resolvable field of this register, which selects the valid view",
+                                lhs.ident,
+                            ),
+                        ));
+                    }
+
+                    continue;
+                }
+
+                if lhs.offset + lhs.width() > rhs.offset {
+                    let msg = format!(
+                        "{} {{ domain: {}..{} }}, {} {{ domain: {}..{} }}",
+                        lhs.ident,
+                        lhs.offset,
+                        lhs.offset + lhs.width(),
+                        rhs.ident,
+                        rhs.offset,
+                        rhs.offset + rhs.width(),
+                    );
+
+                    let mut e = syn::Error::new(
+                        spec.args.span(),
+                        format!("field domains overlapping or unordered. {msg}"),
+                    );
+
+                    e.combine(syn::Error::new(
+                        lhs.ident.span(),
+                        format!(
+                            "field '{}' is overlapping or out of order with '{}'. {}",
+                            lhs.ident, rhs.ident, msg,
+                        ),
+                    ));
+
+                    e.combine(syn::Error::new(
+                        rhs.ident.span(),
+                        format!(
+                            "field '{}' is overlapping or out of order with '{}'. {}",
+                            rhs.ident, lhs.ident, msg,
+                        ),
+                    ));
+
+                    errors.push(e);
+                }
+            }
+        }
+
+        errors.coalesce()?;
+
+        let register = Self { spec };
+
+        register.validate_generated_idents()?;
+
+        Ok(register)
+    }
+}
+
+/// The field and variant of an entitlement to a state of a field
+/// within the same register (which take the form `field::Variant`).
+fn local_entitlement(path: &Path) -> Option<(&Ident, &Ident)> {
+    let [field, variant] = path.segments.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+
+    Some((&field.ident, &variant.ident))
+}
+
+/// A state of a field within a register, by field.
+type LocalState<'a> = (&'a Ident, &'a Variant);
+
+/// Fixed states and remaining fields, by identifiers, mapped
+/// to the reasons the remaining fields cannot be satisfied.
+type Contradictions<'a> = HashMap<(Vec<(&'a Ident, &'a Ident)>, Vec<&'a Ident>), Vec<String>>;
+
+/// A search for states of entitled fields which are
+/// consistent with each other and with a fixed pattern
+/// of states.
+///
+/// Conflicts between pairs of states are computed once, and
+/// patterns known to be contradictory are remembered, so searches
+/// sharing sub-patterns do not enumerate them again. Candidates
+/// conflicting with the pattern are pruned before descending, so
+/// contradictions are found as early as possible.
+#[derive(Default)]
+struct Search<'a> {
+    /// The conflict (if any) of each pair of states, by identifiers.
+    conflicts: HashMap<[&'a Ident; 4], Option<String>>,
+    /// Patterns and remaining fields (canonicalized, by identifiers)
+    /// for which the remaining fields have no consistent states, with
+    /// the reasons why.
+    contradictions: Contradictions<'a>,
+}
+
+impl<'a> Search<'a> {
+    /// Explain why two states of fields within a register
+    /// cannot be held at the same time (if they cannot).
+    fn conflict(&mut self, lhs: LocalState<'a>, rhs: LocalState<'a>) -> Option<String> {
+        let key = [lhs.0, &lhs.1.ident, rhs.0, &rhs.1.ident];
+
+        self.conflicts
+            .entry(key)
+            .or_insert_with(|| {
+                [(lhs, rhs), (rhs, lhs)]
+                    .into_iter()
+                    .find_map(|((field, state), (other_field, other))| {
+                        let required = sorted_paths(&state.entitlements)
+                            .into_iter()
+                            .filter_map(local_entitlement)
+                            .filter(|(ident, _)| *ident == other_field)
+                            .map(|(_, variant)| variant)
+                            .collect::<Vec<_>>();
+
+                        if required.is_empty() || required.contains(&&other.ident) {
+                            return None;
+                        }
+
+                        let required = match required.as_slice() {
+                            [required] => format!("`{other_field}::{required}`"),
+                            _ => format!(
+                                "one of {}",
+                                required
+                                    .iter()
+                                    .map(|variant| format!("`{other_field}::{variant}`"))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            ),
+                        };
+
+                        Some(format!(
+                            "`{field}::{}` requires {required}, but the pattern fixes `{other_field}::{}`",
+                            state.ident, other.ident,
+                        ))
+                    })
+            })
+            .clone()
+    }
+
+    /// The first conflict of a state with the states fixed by a pattern (if any).
+    fn conflict_with_pattern(
+        &mut self,
+        pattern: &[LocalState<'a>],
+        state: LocalState<'a>,
+    ) -> Option<String> {
+        pattern
+            .iter()
+            .find_map(|fixed| self.conflict(*fixed, state))
+    }
+
+    /// Search for states of the entitled fields (`groups`) which are
+    /// consistent with each other and with the states fixed by `pattern`.
+    ///
+    /// The contradictions encountered are recorded in `reasons`, which
+    /// explain why no states were found (if none were).
+    fn run(
+        &mut self,
+        pattern: &mut Vec<LocalState<'a>>,
+        groups: &[(&'a Ident, Vec<&'a Variant>)],
+        reasons: &mut Vec<String>,
+    ) -> bool {
+        // the outcome only depends on which states are fixed
+        // and which fields remain, not the order of either
+        let mut fixed = pattern
+            .iter()
+            .map(|(field, state)| (*field, &state.ident))
+            .collect::<Vec<_>>();
+        fixed.sort_unstable();
+
+        let mut remaining = groups.iter().map(|(field, _)| *field).collect::<Vec<_>>();
+        remaining.sort_unstable();
+
+        let key = (fixed, remaining);
+
+        if let Some(known) = self.contradictions.get(&key) {
+            for reason in known {
+                record(reasons, reason.clone());
+            }
+
+            return false;
+        }
+
+        let mut found_reasons = Vec::new();
+        let found = self.descend(pattern, groups, &mut found_reasons);
+
+        for reason in &found_reasons {
+            record(reasons, reason.clone());
+        }
+
+        if !found {
+            self.contradictions.insert(key, found_reasons);
+        }
+
+        found
+    }
+
+    /// Fix each consistent state of the first entitled field in turn,
+    /// after pruning candidates which conflict with the pattern.
+    fn descend(
+        &mut self,
+        pattern: &mut Vec<LocalState<'a>>,
+        groups: &[(&'a Ident, Vec<&'a Variant>)],
+        reasons: &mut Vec<String>,
+    ) -> bool {
+        let mut pruned = Vec::with_capacity(groups.len());
+
+        for (field, candidates) in groups {
+            let mut consistent = Vec::new();
+
+            for candidate in candidates {
+                match self.conflict_with_pattern(pattern, (field, candidate)) {
+                    Some(reason) => record(reasons, reason),
+                    None => consistent.push(*candidate),
+                }
+            }
+
+            // a field with no consistent states contradicts the pattern
+            if consistent.is_empty() {
+                return false;
+            }
+
+            pruned.push((*field, consistent));
+        }
+
+        let Some(((field, candidates), rest)) = pruned.split_first() else {
+            return true;
+        };
+
+        for candidate in candidates {
+            pattern.push((field, candidate));
+            let found = self.run(pattern, rest, reasons);
+            pattern.pop();
+
+            if found {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Record a reason unless it has already been recorded.
+fn record(reasons: &mut Vec<String>, reason: String) {
+    if !reasons.contains(&reason) {
+        reasons.push(reason);
+    }
+}
+
+pub enum AccessMarker {
+    Read,
+    Write,
+}
+
+enum Filter {
+    Resolvable,
+    Unresolvable,
+    Writable,
+    Readable,
+    Numeric(AccessMarker),
+    Enumerated(AccessMarker),
+}
+
+impl Filter {
+    fn retains(&self, field: &Field) -> bool {
+        match self {
+            Self::Resolvable => field.is_resolvable(),
+            Self::Unresolvable => !field.is_resolvable(),
+            Self::Writable => field.access.is_write(),
+            Self::Readable => field.access.is_read(),
+            // TODO: quite a lot of repeat code here...
+            Self::Numeric(marker) => match marker {
+                AccessMarker::Read => {
+                    let schema = match &field.access {
+                        Access::Read(read) | Access::ReadWrite { read, write: _ } => &read.schema,
+                        _ => return false,
+                    };
+
+                    schema.numericity.is_numeric()
+                }
+                AccessMarker::Write => {
+                    let schema = match &field.access {
+                        Access::Write(write) | Access::ReadWrite { read: _, write } => {
+                            &write.schema
+                        }
+                        _ => return false,
+                    };
+
+                    schema.numericity.is_numeric()
+                }
+            },
+            Self::Enumerated(marker) => match marker {
+                AccessMarker::Read => {
+                    let schema = match &field.access {
+                        Access::Read(read) | Access::ReadWrite { read, write: _ } => &read.schema,
+                        _ => return false,
+                    };
+
+                    schema.numericity.is_enumerated()
+                }
+                AccessMarker::Write => {
+                    let schema = match &field.access {
+                        Access::Write(write) | Access::ReadWrite { read: _, write } => {
+                            &write.schema
+                        }
+                        _ => return false,
+                    };
+
+                    schema.numericity.is_enumerated()
+                }
+            },
+        }
+    }
+}
+
+struct FieldIter<'a, I>
+where
+    I: Iterator<Item = &'a Field>,
+{
+    iter: I,
+    filters: Vec<Filter>,
+}
+
+impl<'a, I> FieldIter<'a, I>
+where
+    I: Iterator<Item = &'a Field>,
+{
+    const fn new(iter: I) -> Self {
+        Self {
+            iter,
+            filters: Vec::new(),
+        }
+    }
+
+    fn resolvable(mut self) -> Self {
+        self.filters.push(Filter::Resolvable);
+
+        self
+    }
+
+    fn unresolvable(mut self) -> Self {
+        self.filters.push(Filter::Unresolvable);
+
+        self
+    }
+
+    fn writable(mut self) -> Self {
+        self.filters.push(Filter::Writable);
+
+        self
+    }
+
+    fn readable(mut self) -> Self {
+        self.filters.push(Filter::Readable);
+
+        self
+    }
+
+    fn numeric(mut self, access: AccessMarker) -> Self {
+        self.filters.push(Filter::Numeric(access));
+
+        self
+    }
+
+    fn enumerated(mut self, access: AccessMarker) -> Self {
+        self.filters.push(Filter::Enumerated(access));
+
+        self
+    }
+
+    fn idents(self) -> impl Iterator<Item = &'a Ident> + use<'a, I> {
+        self.map(|field| &field.ident)
+    }
+
+    fn tys(self) -> impl Iterator<Item = Ident> + use<'a, I> {
+        self.map(|field| {
+            Ident::new(
+                &inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string()),
+                Span::call_site(),
+            )
+        })
+    }
+}
+
+impl<'a, I> Iterator for FieldIter<'a, I>
+where
+    I: Iterator<Item = &'a Field>,
+{
+    type Item = &'a Field;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(field) = self.iter.next() {
+                if self.filters.iter().all(|filter| filter.retains(field)) {
+                    break Some(field);
+                } else {
+                    continue;
+                }
+            } else {
+                break None;
+            }
+        }
+    }
+}
+
+impl Register {
+    fn fields(&self) -> FieldIter<'_, impl Iterator<Item = &'_ Field>> {
+        FieldIter::new(self.fields.iter())
+    }
+
+    /// Whether this register has writable resolvable fields,
+    /// which are returned to reset by the generated `reset`.
+    pub fn has_writable_state(&self) -> bool {
+        self.fields().writable().resolvable().next().is_some()
+    }
+
+    /// Whether this register has writable fields, which are
+    /// written to their reset values when the block is released.
+    pub fn is_writable(&self) -> bool {
+        self.fields().writable().next().is_some()
+    }
+
+    /// Write the fields which must be written before others in
+    /// separate, preceding accesses, in order of their stages.
+    ///
+    /// `current` is the value of the register before the write and
+    /// `value` is the (binding of the) final value to be written.
+    /// Stages in which no field changes are skipped.
+    fn generate_ordered_writes(
+        &self,
+        current: &TokenStream2,
+        value: &TokenStream2,
+    ) -> TokenStream2 {
+        let span = self.args.span();
+        let rewrite = self.rewrite_ident();
+
+        // constraints are validated to be acyclic
+        let stages = self.write_stages().unwrap_or_default();
+        let last = stages.iter().copied().max().unwrap_or(0);
+
+        // the last stage is written by the final write
+        let (stage_masks, cumulative_masks) = (0..last)
+            .map(|stage| {
+                let mask = |f: &dyn Fn(usize) -> bool| {
+                    self.fields
+                        .iter()
+                        .zip(&stages)
+                        .filter(|(_, field_stage)| f(**field_stage))
+                        .fold(0u32, |mask, (field, _)| mask | field.mask())
+                };
+
+                (
+                    mask(&|field_stage| field_stage == stage),
+                    mask(&|field_stage| field_stage <= stage),
+                )
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+
+        if stage_masks.is_empty() {
+            return TokenStream2::new();
+        }
+
+        quote_spanned! { span =>
+            {
+                let current = #current;
+
+                #(
+                    if (current ^ #value) & #stage_masks != 0 {
+                        #rewrite((current & !#cumulative_masks) | (#value & #cumulative_masks));
+
+                        ::core::sync::atomic::compiler_fence(::core::sync::atomic::Ordering::SeqCst);
+                    }
+                )*
+            }
+        }
+    }
+
+    fn generate_field_bodies(&self) -> TokenStream2 {
+        let span = self.args.span();
+        let field_bodies = self.fields().map(|field| quote_spanned! { span => #field });
+
+        quote_spanned! { span =>
+            #(
+                #field_bodies
+            )*
+        }
+    }
+
+    fn generate_view_impls(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        // states selecting a view implement its mode,
+        // so only the remaining states are implemented here
+        let impls = self.fields.iter().filter_map(|field| {
+            let selector = self.view_selector(field)?;
+            let selecting_states = field.selecting_states(&selector.ident);
+
+            let ident = &field.ident;
+            let selector_ident = &selector.ident;
+            let impls = selector
+                .states()?
+                .iter()
+                .filter(|variant| !selecting_states.contains(&variant.ident))
+                .map(|variant| {
+                    let variant_ident = &variant.ident;
+                    let (impl_generics, ty_generics) = variant.generics();
+
+                    quote_spanned! { span =>
+                        impl #impl_generics #ident::View for #selector_ident::#variant_ident #ty_generics {
+                            const ACTIVE: bool = false;
+                        }
+                    }
+                });
+
+            Some(quote_spanned! { span =>
+                #(
+                    #impls
+                )*
+            })
+        });
+
+        quote_spanned! { span =>
+            #(
+                #impls
+            )*
+        }
+    }
+
+    fn generate_offset_const(&self) -> TokenStream2 {
+        let span = self.args.span();
+        let offset = self.offset;
+        // the reset value is only known if the reset of every field is
+        let reset = self.reset_value().map(|reset_value| {
+            quote_spanned! { span =>
+                /// The reset value of this register.
+                pub const RESET: u32 = #reset_value;
+            }
+        });
+
+        quote_spanned! { span =>
+            /// The offset of this register within the block.
+            pub const OFFSET: u32 = #offset;
+
+            #reset
+        }
+    }
+
+    fn generate_assertions(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        let field_idents = self.fields().idents();
+        // views inactive at reset do not hold their reset, and
+        // field resets are only checked if the register reset is known
+        let resolvable_field_idents = self
+            .fields()
+            .resolvable()
+            .filter(|field| self.reset_value().is_some() && self.is_active_at_reset(field))
+            .map(|field| &field.ident);
+
+        quote_spanned! { span =>
+            // invariants of the description, checked
+            // against the generated interface
+            const _: () = {
+                assert!(OFFSET & (Raw::BITS / 8 - 1) == 0, "register is not aligned to its size");
+
+                #(
+                    assert!(
+                        #field_idents::OFFSET as u32 + #field_idents::WIDTH as u32 <= Raw::BITS,
+                        "field exceeds the register"
+                    );
+                )*
+
+                #(
+                    assert!(
+                        (RESET >> #resolvable_field_idents::OFFSET)
+                            & ::proto_hal::macro_utils::mask(0, #resolvable_field_idents::WIDTH)
+                            == #resolvable_field_idents::RESET,
+                        "field reset is inconsistent with the register reset"
+                    );
+                )*
+            };
+        }
+    }
+
+    fn generate_effect_masks(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        let effect_mask = |effects: &[WriteEffect]| {
+            self.fields()
+                .filter(|field| {
+                    field
+                        .access
+                        .write_effect()
+                        .is_some_and(|effect| effects.contains(&effect))
+                })
+                .fold(0u32, |mask, field| mask | field.mask())
+        };
+
+        let inert_zeros = effect_mask(&[WriteEffect::OneToClear, WriteEffect::Trigger]);
+        let inert_ones = effect_mask(&[WriteEffect::ZeroToClear]);
+
+        // registers which are never read-modify-written
+        // (i.e. read-only registers) leave these unused
+        quote_spanned! { span =>
+            /// Bits which are written as zero to be left
+            /// unaffected (write one to clear, triggers).
+            #[allow(dead_code)]
+            const INERT_ZEROS: u32 = #inert_zeros;
+
+            /// Bits which are written as one to be left
+            /// unaffected (write zero to clear).
+            #[allow(dead_code)]
+            const INERT_ONES: u32 = #inert_ones;
+        }
+    }
+
+    fn generate_raw_ty(&self) -> TokenStream2 {
+        let span = self.args.span();
+        let raw_ty = format_ident!("u{}", self.size());
+
+        quote_spanned! { span =>
+            /// The primitive type this register is accessed as.
+            pub type Raw = #raw_ty;
+        }
+    }
+
+    fn generate_raw_accessors(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        let mut body = TokenStream2::new();
+
+        let readable = self.fields().any(|field| field.access.is_read());
+        let writable = self.fields().any(|field| field.access.is_write());
+
+        if readable {
+            body.extend(quote_spanned! { span =>
+                /// Read the raw value of the register.
+                #[inline(always)]
+                pub(super) unsafe fn read_raw() -> u32 {
+                    let value = ::proto_hal::macro_utils::read::<Raw>(super::base_addr() + OFFSET);
+
+                    ::proto_hal::__trace!(Read, OFFSET, super::base_addr() + OFFSET, value);
+
+                    value
+                }
+            });
+        }
+
+        if writable {
+            let verification = self.generate_write_verification();
+            let reserved = self.maybe_generate_reserved_policy();
+
+            // values derived from a read of the register (i.e. by a
+            // read-modify-write) already hold the last-read value of
+            // reserved bits, so they are written with `rewrite_raw`
+            // rather than reading the register again
+            let (write_ident, keep) = if readable {
+                let mask = self.reserved_mask();
+
+                let keep = self
+                    .args
+                    .reserved
+                    .as_deref()
+                    .is_some_and(|reserved| *reserved == ReservedPolicy::Keep)
+                    .then(|| {
+                        quote_spanned! { span =>
+                            let value = (value & !#mask) | (read_raw() & #mask);
+                        }
+                    });
+
+                body.extend(quote_spanned! { span =>
+                    /// Write the raw value of the register.
+                    #[inline(always)]
+                    pub(super) unsafe fn write_raw(value: u32) {
+                        #keep
+
+                        rewrite_raw(value);
+                    }
+                });
+
+                (format_ident!("rewrite_raw"), true)
+            } else {
+                (format_ident!("write_raw"), false)
+            };
+
+            let doc = if keep {
+                "Write back a raw value derived from a read of the register, whose reserved bits therefore hold their last-read value."
+            } else {
+                "Write the raw value of the register."
+            };
+
+            body.extend(quote_spanned! { span =>
+                #[doc = #doc]
+                #[inline(always)]
+                pub(super) unsafe fn #write_ident(value: u32) {
+                    #reserved
+
+                    ::proto_hal::macro_utils::write::<Raw>(super::base_addr() + OFFSET, value);
+
+                    ::proto_hal::__trace!(Write, OFFSET, super::base_addr() + OFFSET, value);
+
+                    #verification
+                }
+            });
+        }
+
+        body
+    }
+
+    /// The raw writer of values derived from a read of the
+    /// register (see `generate_raw_accessors`).
+    fn rewrite_ident(&self) -> Ident {
+        if self.fields().any(|field| field.access.is_read())
+            && self.fields().any(|field| field.access.is_write())
+        {
+            format_ident!("rewrite_raw")
+        } else {
+            format_ident!("write_raw")
+        }
+    }
+
+    /// Apply the reserved bit policy (if any) to a value about to be
+    /// written, other than keeping them (see `generate_raw_accessors`).
+    fn maybe_generate_reserved_policy(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let mask = self.reserved_mask();
+
+        match **self.args.reserved.as_ref()? {
+            ReservedPolicy::Keep => None,
+            ReservedPolicy::Zero => Some(quote_spanned! { span =>
+                let value = value & !#mask;
+            }),
+            ReservedPolicy::One => Some(quote_spanned! { span =>
+                let value = value | #mask;
+            }),
+        }
+    }
+
+    /// Read the register back after a write (when enabled), comparing
+    /// the stable fields against the written value.
+    fn generate_write_verification(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let mask = self.verify_mask();
+
+        if mask == 0
+            || self
+                .fields()
+                .any(|field| field.access.read_effect().is_some())
+        {
+            return None;
+        }
+
+        let enabled = if self.args.verify {
+            quote_spanned! { span => true }
+        } else {
+            quote_spanned! { span => ::proto_hal::macro_utils::VERIFY_WRITES }
+        };
+
+        Some(quote_spanned! { span =>
+            if #enabled {
+                ::proto_hal::macro_utils::verify_write(
+                    super::base_addr() + OFFSET,
+                    value,
+                    read_raw(),
+                    #mask,
+                );
+            }
+        })
+    }
+
+    fn maybe_generate_refined_writers(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let writable_enumerated_fields = self
+            .fields()
+            .enumerated(AccessMarker::Write)
+            .collect::<Vec<_>>();
+
+        if writable_enumerated_fields.is_empty() {
+            return None;
+        }
+
+        let refined_writer_idents = writable_enumerated_fields
+            .iter()
+            .map(|field| {
+                format_ident!(
+                    "{}Writer",
+                    inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string())
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut body = TokenStream2::new();
+
+        for (field, refined_writer_ident) in
+            writable_enumerated_fields.iter().zip(refined_writer_idents)
+        {
+            let field_ident = &field.ident;
+
+            let schema = match &field.access {
+                Access::Write(write) | Access::ReadWrite { read: _, write } => &write.schema,
+                _ => unreachable!("fields are writable"),
+            };
+
+            let Numericity::Enumerated { variants } = &schema.numericity else {
+                unreachable!("field schemas are enumerated in write direction")
+            };
+
+            let accessors = variants.iter().map(|variant| {
+                let accessor = Ident::new(
+                    &inflector::cases::snakecase::to_snake_case(&variant.ident.to_string()),
+                    field.args.span(),
+                );
+                let variant_ident = &variant.ident;
+
+                match &variant.range {
+                    Some(range) => {
+                        let (start, end) = (range.start, range.end);
+
+                        quote_spanned! { span =>
+                            /// Write a value within the range of the variant.
+                            pub fn #accessor(self, value: u32) -> Result<&'a mut W, ::proto_hal::macro_utils::InvalidValue> {
+                                if !(#start..#end).contains(&value) {
+                                    Err(::proto_hal::macro_utils::InvalidValue(value))?
+                                }
+
+                                Ok(self.variant(#field_ident::WriteVariant::#variant_ident(value)))
+                            }
+                        }
+                    }
+                    None => quote_spanned! { span =>
+                        pub fn #accessor(self) -> &'a mut W {
+                            self.variant(#field_ident::WriteVariant::#variant_ident)
+                        }
+                    },
+                }
+            });
+
+            // values of range variants are constructed freely,
+            // so they must be checked before being written
+            let range_check = variants.iter().any(|variant| variant.range.is_some()).then(|| {
+                quote_spanned! { span =>
+                    assert!(
+                        #field_ident::WriteVariant::try_from_bits(variant.bits()) == Ok(variant),
+                        "value exceeds the range of the variant"
+                    );
+                }
+            });
+
+            body.extend(quote_spanned! { span =>
+                pub struct #refined_writer_ident<'a, W> {
+                    w: &'a mut W
+                }
+
+                impl<'a, W> #refined_writer_ident<'a, W>
+                where
+                    W: ::proto_hal::macro_utils::Writer,
+                {
+                    pub fn variant(self, variant: #field_ident::WriteVariant) -> &'a mut W {
+                        #range_check
+
+                        unsafe {
+                            ::proto_hal::macro_utils::Writer::write(self.w, |reg| {
+                                *reg &= !::proto_hal::macro_utils::mask(#field_ident::OFFSET, #field_ident::WIDTH);
+                                *reg |= variant.bits() << #field_ident::OFFSET;
+                            })
+                        }
+                    }
+
+                    /// Write the variant corresponding to `bits`, if any.
+                    pub fn try_bits(self, bits: u32) -> Result<&'a mut W, ::proto_hal::macro_utils::InvalidValue> {
+                        Ok(self.variant(#field_ident::WriteVariant::try_from_bits(bits)?))
+                    }
+
+                    #(
+                        #accessors
+                    )*
+                }
+            });
+        }
+
+        Some(body)
+    }
+
+    fn maybe_generate_reader(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let readable_unresolvable_fields =
+            self.fields().readable().unresolvable().collect::<Vec<_>>();
+
+        // don't generate a reader if there are no fields
+        // to be read
+        if readable_unresolvable_fields.is_empty() {
+            return None;
+        };
+
+        let readable_unresolvable_numeric_fields = self
+            .fields()
+            .readable()
+            .unresolvable()
+            .numeric(AccessMarker::Read)
+            .collect::<Vec<_>>();
+        let readable_unresolvable_numeric_field_idents = self
+            .fields()
+            .readable()
+            .unresolvable()
+            .numeric(AccessMarker::Read)
+            .idents();
+        let readable_unresolvable_enumerated_field_idents = self
+            .fields()
+            .readable()
+            .unresolvable()
+            .enumerated(AccessMarker::Read)
+            .idents();
+        let readable_unresolvable_numeric_deprecations = readable_unresolvable_numeric_fields
+            .iter()
+            .map(|field| field.deprecation());
+        let readable_unresolvable_enumerated_deprecations = self
+            .fields()
+            .readable()
+            .unresolvable()
+            .enumerated(AccessMarker::Read)
+            .map(|field| field.deprecation());
+
+        let value_tys = readable_unresolvable_numeric_fields
+            .iter()
+            .map(|field| field.value_ty())
+            .collect::<Vec<_>>();
+        let value_getters = readable_unresolvable_numeric_fields
+            .iter()
+            .map(|field| field.value_getter())
+            .collect::<Vec<_>>();
+
+        let snapshot_field_idents = readable_unresolvable_fields
+            .iter()
+            .map(|field| &field.ident)
+            .collect::<Vec<_>>();
+        let snapshot_field_tys = readable_unresolvable_fields.iter().map(|field| {
+            if field
+                .read_schema()
+                .is_some_and(|schema| matches!(schema.numericity, Numericity::Enumerated { .. }))
+            {
+                let ident = &field.ident;
+
+                quote_spanned! { span => #ident::ReadVariant }
+            } else {
+                field.value_ty().to_token_stream()
+            }
+        });
+
+        Some(quote_spanned! { span =>
+            /// The values of all readable fields, decoded
+            /// from a single read of the register.
+            #[derive(Clone, Copy, PartialEq, Eq)]
+            pub struct Snapshot {
+                #(
+                    pub #snapshot_field_idents: #snapshot_field_tys,
+                )*
+            }
+
+            impl From<Reader> for Snapshot {
+                fn from(reader: Reader) -> Self {
+                    Self {
+                        #(
+                            #snapshot_field_idents: reader.#snapshot_field_idents(),
+                        )*
+                    }
+                }
+            }
+
+            pub struct Reader {
+                value: ::proto_hal::macro_utils::RegisterValue,
+            }
+
+            impl From<UnsafeReader> for Reader {
+                fn from(unsafe_reader: UnsafeReader) -> Self {
+                    Self { value: unsafe_reader.value }
+                }
+            }
+
+            impl Reader {
+                #(
+                    #readable_unresolvable_enumerated_deprecations
+                    pub fn #readable_unresolvable_enumerated_field_idents(&self) -> #readable_unresolvable_enumerated_field_idents::ReadVariant {
+                        // SAFETY: assumes
+                        // 1. peripheral description is correct (offset/width)
+                        // 2. hardware is operating correctly
+                        unsafe {
+                            #readable_unresolvable_enumerated_field_idents::ReadVariant::from_bits(
+                                self.value.region(
+                                    #readable_unresolvable_enumerated_field_idents::OFFSET,
+                                    #readable_unresolvable_enumerated_field_idents::WIDTH
+                                )
+                            )
+                        }
+                    }
+                )*
+
+                #(
+                    #readable_unresolvable_numeric_deprecations
+                    pub fn #readable_unresolvable_numeric_field_idents(&self) -> #value_tys {
+                        self.value.#value_getters(#readable_unresolvable_numeric_field_idents::OFFSET)
+                    }
+                )*
+            }
+        })
+    }
+
+    fn maybe_generate_writer(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let writable_unresolvable_fields =
+            self.fields().writable().unresolvable().collect::<Vec<_>>();
+
+        // don't generate a reader if there are no fields
+        // to be written
+        if writable_unresolvable_fields.is_empty() {
+            return None;
+        };
+
+        let writable_unresolvable_numeric_fields = self
+            .fields()
+            .writable()
+            .unresolvable()
+            .numeric(AccessMarker::Write)
+            .collect::<Vec<_>>();
+        let writable_unresolvable_numeric_field_idents = self
+            .fields()
+            .writable()
+            .unresolvable()
+            .numeric(AccessMarker::Write)
+            .idents();
+        let writable_unresolvable_enumerated_fields = self
+            .fields()
+            .writable()
+            .unresolvable()
+            .enumerated(AccessMarker::Write)
+            .collect::<Vec<_>>();
+        let writable_unresolvable_enumerated_field_idents = self
+            .fields()
+            .writable()
+            .unresolvable()
+            .enumerated(AccessMarker::Write)
+            .idents();
+
+        let writable_unresolvable_numeric_deprecations = writable_unresolvable_numeric_fields
+            .iter()
+            .map(|field| field.deprecation());
+        let writable_unresolvable_numeric_entitlement_params = writable_unresolvable_numeric_fields
+            .iter()
+            .map(|field| field.write_entitlement_param());
+        let writable_unresolvable_enumerated_entitlement_params =
+            writable_unresolvable_enumerated_fields
+                .iter()
+                .map(|field| field.write_entitlement_param());
+        let writable_unresolvable_enumerated_deprecations = writable_unresolvable_enumerated_fields
+            .iter()
+            .map(|field| field.deprecation());
+
+        let value_tys = writable_unresolvable_numeric_fields
+            .iter()
+            .map(|field| field.value_ty())
+            .collect::<Vec<_>>();
+        let raw_values = writable_unresolvable_numeric_fields
+            .iter()
+            .map(|field| field.value_to_raw(&parse_quote! { value }))
+            .collect::<Vec<_>>();
+        let constraint_assertions = writable_unresolvable_numeric_fields
+            .iter()
+            .zip(&raw_values)
+            .map(|(field, raw_value)| {
+                let ident = &field.ident;
+
+                field.maybe_generate_constraint_assertion(
+                    &quote_spanned! { span => #ident },
+                    raw_value,
+                )
+            });
+
+        let constrained_fields = writable_unresolvable_numeric_fields
+            .iter()
+            .filter(|field| field.args.constraints.is_some())
+            .collect::<Vec<_>>();
+        let constrained_field_idents = constrained_fields
+            .iter()
+            .map(|field| &field.ident)
+            .collect::<Vec<_>>();
+        let try_constrained_field_idents = constrained_field_idents
+            .iter()
+            .map(|ident| format_ident!("try_{ident}"));
+        let constrained_value_tys = constrained_fields.iter().map(|field| field.value_ty());
+        let constrained_raw_values = constrained_fields
+            .iter()
+            .map(|field| field.value_to_raw(&parse_quote! { value }));
+        let constrained_entitlement_params = constrained_fields
+            .iter()
+            .map(|field| field.write_entitlement_param());
+        let constrained_entitlement_args = constrained_fields.iter().map(|field| {
+            field
+                .write_entitlements()
+                .map(|_| quote_spanned! { span => _entitlement, })
+        });
+
+        let unresolvable_refined_writer_idents = writable_unresolvable_enumerated_fields
+            .iter()
+            .map(|field| {
+                format_ident!(
+                    "{}Writer",
+                    inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string())
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Some(quote_spanned! { span =>
+            pub struct Writer {
+                value: u32,
+            }
+
+            impl ::proto_hal::macro_utils::Writer for Writer {
+                unsafe fn write(&mut self, f: impl FnOnce(&mut u32)) -> &mut Self {
+                    f(&mut self.value);
+                    self
+                }
+            }
+
+            impl Writer {
+                const fn new() -> Self {
+                    Self {
+                        value: INERT_ONES,
+                    }
+                }
+
+                #(
+                    #writable_unresolvable_enumerated_deprecations
+                    pub fn #writable_unresolvable_enumerated_field_idents(&mut self, #writable_unresolvable_enumerated_entitlement_params) -> #unresolvable_refined_writer_idents<Self> {
+                        #unresolvable_refined_writer_idents { w: self }
+                    }
+                )*
+
+                #(
+                    #writable_unresolvable_numeric_deprecations
+                    pub fn #writable_unresolvable_numeric_field_idents(&mut self, #writable_unresolvable_numeric_entitlement_params value: #value_tys) -> &mut Self {
+                        #constraint_assertions
+
+                        // SAFETY: the field is unresolvable and requires no
+                        // entitlements to be written, so writing a checked value
+                        // to its bits violates no states or entitlements
+                        unsafe {
+                            ::proto_hal::macro_utils::Writer::write(
+                                self,
+                                |reg| {
+                                    *reg &= !::proto_hal::macro_utils::mask(#writable_unresolvable_numeric_field_idents::OFFSET, #writable_unresolvable_numeric_field_idents::WIDTH);
+                                    *reg |= #raw_values << #writable_unresolvable_numeric_field_idents::OFFSET;
+                                }
+                            )
+                        }
+                    }
+                )*
+
+                #(
+                    /// Write a value to the field if it satisfies the field's constraints.
+                    pub fn #try_constrained_field_idents(&mut self, #constrained_entitlement_params value: #constrained_value_tys) -> Result<&mut Self, ::proto_hal::macro_utils::ConstraintViolation> {
+                        #constrained_field_idents::check(#constrained_raw_values)?;
+
+                        Ok(self.#constrained_field_idents(#constrained_entitlement_args value))
+                    }
+                )*
+            }
+        })
+    }
+
+    fn maybe_generate_array_writers(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let mut methods = TokenStream2::new();
+
+        for array in &self.arrays {
+            let field = &array.inherited;
+
+            if field.is_resolvable()
+                || !field.access.is_write()
+                || field.write_entitlements().is_some()
+            {
+                continue;
+            }
+
+            let Some(schema) = field.write_schema() else {
+                continue;
+            };
+
+            let method_ident = format_ident!("{}_each", array.name());
+            // arrays are validated ahead of time
+            let Ok(first_ident) = array.element_ident(array.range.start) else {
+                continue;
+            };
+
+            let (value_ty, raw_value) = match &schema.numericity {
+                // values of range variants are constructed freely,
+                // so they must be checked before being written
+                Numericity::Enumerated { variants }
+                    if variants.iter().any(|variant| variant.range.is_some()) =>
+                {
+                    (
+                        quote_spanned! { span => #first_ident::WriteVariant },
+                        quote_spanned! { span =>
+                            {
+                                assert!(
+                                    #first_ident::WriteVariant::try_from_bits(value.bits()) == Ok(value),
+                                    "value exceeds the range of the variant"
+                                );
+
+                                value.bits()
+                            }
+                        },
+                    )
+                }
+                Numericity::Enumerated { variants: _ } => (
+                    quote_spanned! { span => #first_ident::WriteVariant },
+                    quote_spanned! { span => value.bits() },
+                ),
+                Numericity::Numeric => {
+                    let value_ty = field.value_ty();
+                    let raw = field.value_to_raw(&parse_quote! { value });
+                    let assertion = field.maybe_generate_constraint_assertion(
+                        &quote_spanned! { span => #first_ident },
+                        &raw,
+                    );
+
+                    (
+                        quote_spanned! { span => #value_ty },
+                        quote_spanned! { span =>
+                            {
+                                #assertion
+
+                                #raw
+                            }
+                        },
+                    )
+                }
+            };
+
+            let start = array.range.start;
+            let end = array.range.end;
+            let offset = field.offset as u32;
+            let stride = array.stride() as u32;
+            let width = field.width();
+            let doc = format!(
+                "Write the same value to each field of `{}` at the provided indices, in a single register write.",
+                field.ident
+            );
+
+            methods.extend(quote_spanned! { span =>
+                #[doc = #doc]
+                ///
+                /// # Panics
+                ///
+                /// Panics if an index is out of range.
+                pub fn #method_ident(&mut self, indices: impl IntoIterator<Item = u32>, value: #value_ty) -> &mut Self {
+                    let raw = #raw_value;
+
+                    for i in indices {
+                        assert!((#start..#end).contains(&i), "field array index out of range");
+
+                        let offset = (#offset + (i - #start) * #stride) as u8;
+
+                        // SAFETY: the field is unresolvable and requires no
+                        // entitlements to be written, so writing a checked value
+                        // to its bits violates no states or entitlements
+                        unsafe {
+                            ::proto_hal::macro_utils::Writer::write(
+                                self,
+                                |reg| {
+                                    *reg &= !::proto_hal::macro_utils::mask(offset, #width);
+                                    *reg |= raw << offset;
+                                }
+                            );
+                        }
+                    }
+
+                    self
+                }
+            });
+        }
+
+        if methods.is_empty() {
+            return None;
+        }
+
+        Some(quote_spanned! { span =>
+            impl Writer {
+                #methods
+            }
+        })
+    }
+
+    /// Generate transitions of every field of resolvable arrays
+    /// to the same state, i.e. `mode_each_output` for `modeX`.
+    fn maybe_generate_array_transitions(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+
+        let mut methods = TokenStream2::new();
+
+        for array in &self.arrays {
+            let field = &array.inherited;
+
+            if !field.is_resolvable() || !field.access.is_write() {
+                continue;
+            }
+
+            let Some(Numericity::Enumerated { variants }) =
+                field.write_schema().map(|schema| &schema.numericity)
+            else {
+                continue;
+            };
+
+            // arrays are validated ahead of time
+            let Ok(elements) = array.to_fields() else {
+                continue;
+            };
+
+            // range variants are transitioned per field
+            for variant in variants.iter().filter(|variant| variant.range.is_none()) {
+                let variant_ident = &variant.ident;
+                let method_ident = format_ident!(
+                    "{}_each_{}",
+                    array.name(),
+                    inflector::cases::snakecase::to_snake_case(&variant_ident.to_string())
+                );
+                let doc = format!(
+                    "Set the state of each field of `{}` to `{variant_ident}`.",
+                    field.ident
+                );
+
+                let next_field_tys =
+                    self.fields()
+                        .resolvable()
+                        .zip(&resolvable_field_tys)
+                        .map(|(field, ty)| {
+                            let ident = &field.ident;
+
+                            if elements.iter().any(|element| element.ident == *ident) {
+                                quote_spanned! { span => #ident::#variant_ident }
+                            } else {
+                                quote_spanned! { span => #ty }
+                            }
+                        });
+
+                methods.extend(quote_spanned! { span =>
+                    #[doc = #doc]
+                    pub fn #method_ident(self) -> StateBuilder<#(#next_field_tys,)*> {
+                        // SAFETY: `self` is destroyed
+                        unsafe { StateBuilder::conjure() }
+                    }
+                });
+            }
+        }
+
+        if methods.is_empty() {
+            return None;
+        }
+
+        Some(quote_spanned! { span =>
+            impl<#(#resolvable_field_tys,)*> StateBuilder<#(#resolvable_field_tys,)*>
+            where
+                #(
+                    #resolvable_field_tys: #resolvable_field_idents::State,
+                )*
+            {
+                #methods
+            }
+        })
+    }
+
+    fn maybe_generate_unsafe_reader(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        // don't generate a reader if there are no fields
+        // to be read
+        if !self.fields().any(|field| field.access.is_read()) {
+            return None;
+        };
+
+        let readable_numeric_fields = self
+            .fields()
+            .readable()
+            .numeric(AccessMarker::Read)
+            .collect::<Vec<_>>();
+        let readable_numeric_field_idents = self
+            .fields()
+            .readable()
+            .numeric(AccessMarker::Read)
+            .idents();
+        let readable_enumerated_field_idents = self
+            .fields()
+            .readable()
+            .enumerated(AccessMarker::Read)
+            .idents();
+
+        let value_tys = readable_numeric_fields
+            .iter()
+            .map(|field| field.value_ty())
+            .collect::<Vec<_>>();
+        let value_getters = readable_numeric_fields
+            .iter()
+            .map(|field| field.value_getter())
+            .collect::<Vec<_>>();
+
+        Some(quote_spanned! { span =>
+            pub struct UnsafeReader {
+                value: ::proto_hal::macro_utils::RegisterValue,
+            }
+
+            impl UnsafeReader {
+                const fn new(value: u32) -> Self {
+                    Self {
+                        value: ::proto_hal::macro_utils::RegisterValue::new(value),
+                    }
+                }
+
+                #(
+                    pub fn #readable_enumerated_field_idents(&self) -> #readable_enumerated_field_idents::ReadVariant {
+                        // SAFETY: assumes
+                        // 1. peripheral description is correct (offset/width)
+                        // 2. hardware is operating correctly
+                        unsafe {
+                            #readable_enumerated_field_idents::ReadVariant::from_bits(
+                                self.value.region(
+                                    #readable_enumerated_field_idents::OFFSET,
+                                    #readable_enumerated_field_idents::WIDTH
+                                )
+                            )
+                        }
+                    }
+                )*
+
+                #(
+                    pub fn #readable_numeric_field_idents(&self) -> #value_tys {
+                        self.value.#value_getters(#readable_numeric_field_idents::OFFSET)
+                    }
+                )*
+            }
+        })
+    }
+
+    fn maybe_generate_unsafe_writer(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        // don't generate a reader if there are no fields
+        // to be written
+        if !self.fields().any(|field| field.access.is_write()) {
+            return None;
+        };
+
+        let writable_numeric_fields = self
+            .fields()
+            .writable()
+            .numeric(AccessMarker::Write)
+            .collect::<Vec<_>>();
+        let writable_numeric_field_idents = self
+            .fields()
+            .writable()
+            .numeric(AccessMarker::Write)
+            .idents();
+        let writable_enumerated_fields = self
+            .fields()
+            .writable()
+            .enumerated(AccessMarker::Write)
+            .collect::<Vec<_>>();
+        let writable_enumerated_field_idents = self
+            .fields()
+            .writable()
+            .enumerated(AccessMarker::Write)
+            .idents();
+
+        let value_tys = writable_numeric_fields
+            .iter()
+            .map(|field| field.value_ty())
+            .collect::<Vec<_>>();
+        let raw_values = writable_numeric_fields
+            .iter()
+            .map(|field| field.value_to_raw(&parse_quote! { value }))
+            .collect::<Vec<_>>();
+
+        let refined_writer_idents = writable_enumerated_fields
+            .iter()
+            .map(|field| {
+                format_ident!(
+                    "{}Writer",
+                    inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string())
+                )
+            })
+            .collect::<Vec<_>>();
+
+        Some(quote_spanned! { span =>
+            pub struct UnsafeWriter {
+                value: u32,
+            }
+
+            impl ::proto_hal::macro_utils::Writer for UnsafeWriter {
+                unsafe fn write(&mut self, f: impl FnOnce(&mut u32)) -> &mut Self {
+                    f(&mut self.value);
+                    self
+                }
+            }
+
+            impl UnsafeWriter {
+                const fn new() -> Self {
+                    Self {
+                        value: INERT_ONES,
+                    }
+                }
+
+                #(
+                    pub fn #writable_enumerated_field_idents(&mut self) -> #refined_writer_idents<Self> {
+                        #refined_writer_idents { w: self }
+                    }
+                )*
+
+                #(
+                    pub fn #writable_numeric_field_idents(&mut self, value: #value_tys) -> &mut Self {
+                        // SAFETY: the field is unresolvable and requires no
+                        // entitlements to be written, so writing a checked value
+                        // to its bits violates no states or entitlements
+                        unsafe {
+                            ::proto_hal::macro_utils::Writer::write(
+                                self,
+                                |reg| {
+                                    *reg &= !::proto_hal::macro_utils::mask(#writable_numeric_field_idents::OFFSET, #writable_numeric_field_idents::WIDTH);
+                                    *reg |= #raw_values << #writable_numeric_field_idents::OFFSET;
+                                }
+                            )
+                        }
+                    }
+                )*
+            }
+        })
+    }
+
+    fn generate_unsafe_interface(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        let mut body = TokenStream2::new();
+
+        if self.fields().any(|field| field.access.is_read()) {
+            body.extend(quote_spanned! { span =>
+                pub unsafe fn read() -> UnsafeReader {
+                    UnsafeReader::new(
+                        read_raw()
+                    )
+                }
+            });
+        }
+
+        if self.fields().any(|field| field.access.is_write()) {
+            body.extend(quote_spanned! { span =>
+                pub unsafe fn write(f: impl FnOnce(&mut UnsafeWriter) -> &mut UnsafeWriter) {
+                    let mut writer = UnsafeWriter::new();
+
+                    f(&mut writer);
+
+                    write_raw(writer.value);
+                }
+            });
+        }
+
+        if self.fields().any(|field| field.access.is_read())
+            && self.fields().any(|field| field.access.is_write())
+        {
+            body.extend(quote_spanned! { span =>
+                /// Read the register and write back the values produced
+                /// by `f` in a single write, preserving all fields which
+                /// are not written.
+                ///
+                /// *Note: This is a read-modify-write and as such is not atomic.*
+                pub unsafe fn modify(f: impl for<'w> FnOnce(&UnsafeReader, &'w mut UnsafeWriter) -> &'w mut UnsafeWriter) {
+                    let value = read_raw();
+
+                    // fields with write effects are written inert
+                    // unless explicitly written by `f`
+                    let mut writer = UnsafeWriter {
+                        value: (value & !INERT_ZEROS) | INERT_ONES,
+                    };
+
+                    f(&UnsafeReader::new(value), &mut writer);
+
+                    rewrite_raw(writer.value);
+                }
+            });
+        }
+
+        body
+    }
+
+    /// Generate accessors of single-bit fields used as flags
+    /// (i.e. the pending status of an interrupt and its clear field).
+    fn maybe_generate_flag_interface(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let flags = self
+            .fields()
+            .filter(|field| field.args.flag)
+            .collect::<Vec<_>>();
+
+        if flags.is_empty() {
+            return None;
+        }
+
+        let clear_fields = flags
+            .iter()
+            .filter_map(|field| Some((field, field.flag_clear_bits()?)))
+            .collect::<Vec<_>>();
+
+        // reading fields with read effects disturbs them,
+        // so only flags without read effects are read safely
+        let status_accessors = flags
+            .iter()
+            .filter(|field| field.access.is_read())
+            .map(|field| {
+                let ident = &field.ident;
+                let is_set_ident = format_ident!("is_{}_set", ident);
+
+                if field.access.read_effect().is_some() {
+                    quote_spanned! { span =>
+                        /// Whether the flag is set.
+                        ///
+                        /// # Safety
+                        ///
+                        /// Reading the flag invokes its read effect,
+                        /// which must not disturb other contexts.
+                        pub unsafe fn #is_set_ident() -> bool {
+                            (read_raw() >> #ident::OFFSET) & 1 != 0
+                        }
+                    }
+                } else {
+                    quote_spanned! { span =>
+                        /// Whether the flag is set.
+                        pub fn #is_set_ident() -> bool {
+                            // SAFETY: the flag has no read effect, so
+                            // reading it does not disturb other contexts
+                            (unsafe { read_raw() } >> #ident::OFFSET) & 1 != 0
+                        }
+                    }
+                }
+            });
+
+        let clear_idents = clear_fields
+            .iter()
+            .map(|(field, ..)| format_ident!("clear_{}", field.ident));
+        let clear_field_idents = clear_fields.iter().map(|(field, ..)| &field.ident);
+        let clear_bits = clear_fields.iter().map(|(.., bits)| bits);
+
+        // registers without readable fields have nothing to preserve
+        let rewrite = self.rewrite_ident();
+        let value = if self.fields().any(|field| field.access.is_read()) {
+            quote_spanned! { span =>
+                (read_raw() & !INERT_ZEROS) | INERT_ONES
+            }
+        } else {
+            quote_spanned! { span => INERT_ONES }
+        };
+
+        Some(quote_spanned! { span =>
+            #(
+                #status_accessors
+            )*
+
+            #(
+                /// Clear the flag, leaving the rest of the register unaffected.
+                ///
+                /// # Safety
+                ///
+                /// The register must not be accessed concurrently.
+                pub unsafe fn #clear_idents() {
+                    let value = #value;
+                    let value = value & !::proto_hal::macro_utils::mask(#clear_field_idents::OFFSET, #clear_field_idents::WIDTH);
+
+                    #rewrite(value | (#clear_bits << #clear_field_idents::OFFSET));
+                }
+            )*
+        })
+    }
+
+    /// Generate the depth of this register, which follows the
+    /// registers providing the entitlements of its fields.
+    fn generate_depth(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+
+        // entitlements within the register are ordered by
+        // the write stages of its fields instead
+        let providers = sorted_paths(
+            self.fields
+                .iter()
+                .flat_map(|field| field.access_entitlements())
+                .filter(|path| {
+                    path.segments
+                        .first()
+                        .is_some_and(|segment| segment.ident == "super")
+                }),
+        );
+
+        quote_spanned! { span =>
+            const DEPTH: usize = ::proto_hal::macro_utils::depth(super::DEPTH, &[#(<#providers as ::proto_hal::stasis::Freeze>::DEPTH,)*]);
+
+            /// Whether the fields of this register can be reached.
+            ///
+            /// *Note: This is unused by registers without resolvable fields.*
+            #[allow(dead_code)]
+            const REACHABLE: bool = super::REACHABLE;
+
+            impl<#(#resolvable_field_tys,)*> ::proto_hal::macro_utils::Ordered for Register<#(#resolvable_field_tys,)*> {
+                const DEPTH: usize = DEPTH;
+            }
+        }
+    }
+
+    fn generate_register_struct(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        let resolvable_field_idents = self.fields().resolvable().idents();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+
+        let unresolvable_field_idents = self
+            .fields()
+            .unresolvable()
+            .idents()
+            .map(|ident| format_ident!("_{ident}"));
+
+        let (context, context_doc) = match self.context() {
+            Context::Shared => (
+                quote_spanned! { span => Shared },
+                "The tokens of this register may be moved and shared between contexts.",
+            ),
+            Context::Movable => (
+                quote_spanned! { span => Movable },
+                "The tokens of this register may be moved between contexts, but not shared.",
+            ),
+            Context::Local => (
+                quote_spanned! { span => Local },
+                "The tokens of this register are bound to the context they were created in.",
+            ),
+        };
+
+        quote_spanned! { span =>
+            #[doc = #context_doc]
+            type Context = ::proto_hal::stasis::context::#context;
+
+            /// A register. This type gates access to
+            /// the fields it encapsulates.
+            ///
+            /// Field members can be directly moved out of this struct
+            /// for lossy modification, or modified in place with
+            /// accessor methods.
+            #[doc = ""]
+            #[doc = #context_doc]
+            pub struct Register<#(#resolvable_field_tys,)*> {
+                // resolvable fields.
+                #(
+                    pub #resolvable_field_idents: #resolvable_field_tys,
+                )*
+
+                // Q: what is this for?
+                // unresolvable fields.
+                #(
+                    #unresolvable_field_idents: (),
+                )*
+
+                _context: ::core::marker::PhantomData<Context>,
+            }
+        }
+    }
+
+    /// Generate a handle reading the read-only fields of this register,
+    /// which may be shared between contexts regardless of the register.
+    fn maybe_generate_status(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        // reading registers with read effects disturbs them
+        if self
+            .fields()
+            .any(|field| field.access.read_effect().is_some())
+        {
+            return None;
+        }
+
+        let status_fields = self
+            .fields()
+            .filter(|field| matches!(field.access, Access::Read(_)))
+            .collect::<Vec<_>>();
+
+        if status_fields.is_empty() {
+            return None;
+        }
+
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+
+        let status_field_idents = status_fields.iter().map(|field| &field.ident);
+        let status_field_deprecations = status_fields.iter().map(|field| field.deprecation());
+        let status_field_tys = status_fields.iter().map(|field| {
+            if field
+                .read_schema()
+                .is_some_and(|schema| schema.numericity.is_enumerated())
+            {
+                let ident = &field.ident;
+
+                quote_spanned! { span => #ident::ReadVariant }
+            } else {
+                field.value_ty().to_token_stream()
+            }
+        });
+
+        Some(quote_spanned! { span =>
+            /// A handle reading the read-only fields of this register.
+            ///
+            /// Reading these fields has no effect, so the handle may be
+            /// copied and shared between contexts (i.e. held in a `Mutex`)
+            /// regardless of the contexts of the register.
+            #[derive(Clone, Copy)]
+            pub struct Status {
+                _sealed: (),
+            }
+
+            impl Status {
+                #(
+                    #status_field_deprecations
+                    pub fn #status_field_idents(&self) -> #status_field_tys {
+                        // SAFETY: the fields of this register have no read effects
+                        Reader::from(unsafe { read() }).#status_field_idents()
+                    }
+                )*
+            }
+
+            impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+            where
+                #(
+                    #resolvable_field_tys: #resolvable_field_idents::State,
+                )*
+            {
+                /// Create a handle reading the read-only fields of this
+                /// register, which may be shared between contexts.
+                pub fn status(&self) -> Status {
+                    Status { _sealed: () }
+                }
+            }
+        })
+    }
+
+    fn maybe_generate_reset_alias(&self) -> Option<TokenStream2> {
+        if !self.is_resolvable() {
+            return None;
+        };
+
+        let span = self.args.span();
+
+        let resolvable_field_idents = self.fields().resolvable().idents();
+
+        Some(quote_spanned! { span =>
+            pub type Reset = Register<
+                #(
+                    #resolvable_field_idents::Reset,
+                )*
+            >;
+        })
+    }
+
+    fn maybe_generate_bank_alias(&self) -> Option<TokenStream2> {
+        let bank = self.args.bank.as_ref()?;
+
+        let span = self.args.span();
+
+        Some(quote_spanned! { span =>
+            /// The state of the bank selector which exposes this register.
+            ///
+            /// The register is held by the block as a
+            /// [`Banked`](::proto_hal::stasis::Banked) register, which
+            /// must be selected with an entitlement to this state.
+            pub type Bank = super::#bank;
+        })
+    }
+
+    fn maybe_generate_state_builder(&self) -> Option<TokenStream2> {
+        if !self.is_resolvable() {
+            return None;
+        };
+
+        let span = self.args.span();
+
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+        let state_ty = |field: &Field| self.state_ty(field);
+        let state_bits = self.generate_state_bits(self.fields().writable().resolvable(), state_ty);
+        let view_bounds = self.generate_view_bounds(state_ty);
+        let unresolvable_field_idents = self
+            .fields()
+            .unresolvable()
+            .idents()
+            .map(|ident| format_ident!("_{ident}"))
+            .collect::<Vec<_>>();
+        let ordered_writes = self.generate_ordered_writes(
+            &quote_spanned! { span => (read_raw() & !INERT_ZEROS) | INERT_ONES },
+            &quote_spanned! { span => reg_value },
+        );
+
+        // the other fields are written by the same access
+        let finish_with = self.fields().writable().unresolvable().next().map(|_| {
+            quote_spanned! { span =>
+                /// Complete the state transition, writing the values
+                /// produced by `f` to the unresolvable fields in the
+                /// same access.
+                pub fn finish_with(self, f: impl FnOnce(&mut Writer) -> &mut Writer) -> Register<#(#resolvable_field_tys,)*>
+                where
+                    Self: ::proto_hal::macro_utils::AsRegister,
+                    #(
+                        #view_bounds,
+                    )*
+                {
+                    ::proto_hal::__profile!(Transition, {
+                        let mut writer = Writer::new();
+
+                        #(
+                            writer.value |= #state_bits;
+                        )*
+
+                        f(&mut writer);
+
+                        let reg_value = writer.value;
+
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            #ordered_writes
+
+                            write_raw(reg_value);
+                        }
+                    });
+
+                    // SAFETY:
+                    // 1. `self` is destroyed
+                    // 2. state has been written
+                    Register {
+                        #(
+                            #resolvable_field_idents: unsafe { #resolvable_field_tys::conjure() },
+                        )*
+
+                        #(
+                            #unresolvable_field_idents: (), // placeholder
+                        )*
+
+                        _context: ::core::marker::PhantomData,
+                    }
+                }
+            }
+        });
+
+        Some(quote_spanned! { span =>
+            /// This type facilitates the static construction
+            /// of a valid register state.
+            pub struct StateBuilder<#(#resolvable_field_tys,)*> {
+                #(
+                    pub(crate) #resolvable_field_idents: core::marker::PhantomData<#resolvable_field_tys>,
+                )*
+            }
+
+            impl<#(#resolvable_field_tys,)*> StateBuilder<#(#resolvable_field_tys,)*>
+            where
+                #(
+                    #resolvable_field_tys: #resolvable_field_idents::State,
+                )*
+            {
+                /// For internal use.
+                unsafe fn conjure() -> Self {
+                    Self {
+                        #(
+                            #resolvable_field_idents: core::marker::PhantomData,
+                        )*
+                    }
+                }
+
+                /// Complete the state transition and incorporarate
+                /// it into the register.
+                pub fn finish(self) -> Register<#(#resolvable_field_tys,)*>
+                where
+                    Self: ::proto_hal::macro_utils::AsRegister,
+                    #(
+                        #view_bounds,
+                    )*
+                {
+                    ::proto_hal::__profile!(Transition, {
+                        #[allow(unused_parens)]
+                        let reg_value = #(
+                            #state_bits
+                        )|* | INERT_ONES;
+
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            #ordered_writes
+
+                            write_raw(reg_value);
+                        }
+                    });
+
+                    // SAFETY:
+                    // 1. `self` is destroyed
+                    // 2. state has been written
+                    Register {
+                        #(
+                            #resolvable_field_idents: unsafe { #resolvable_field_tys::conjure() },
+                        )*
+
+                        #(
+                            #unresolvable_field_idents: (), // placeholder
+                        )*
+
+                        _context: ::core::marker::PhantomData,
+                    }
+                }
+
+                #finish_with
+            }
+        })
+    }
+
+    fn generate_register_impls(&self) -> TokenStream2 {
+        let span = self.args.span();
+
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+
+        let mut body = TokenStream2::new();
+
+        if self.is_resolvable() {
+            // let new_resolvable_field_tys = self
+            //     .fields()
+            //     .resolvable()
+            //     .tys()
+            //     .map(|ty| format_ident!("New{ty}"))
+            //     .collect::<Vec<_>>();
+            // let new_entitlement_bounds = self
+            //     .fields()
+            //     .resolvable()
+            //     .map(|field| {
+            //         if field.schema.entitlement_fields.is_empty() {
+            //             return None;
+            //         }
+
+            //         let entitled_field_tys = field
+            //             .schema
+            //             .entitlement_fields
+            //             .iter()
+            //             .map(|ident| {
+            //                 format_ident!(
+            //                     "New{}",
+            //                     &inflector::cases::pascalcase::to_pascal_case(&ident.to_string(),),
+            //                 )
+            //             })
+            //             .collect::<Vec<_>>();
+
+            //         Some(quote_spanned! { span =>
+            //             + #(::proto_hal::stasis::Entitled<#entitled_field_tys>)+*
+            //         })
+            //     })
+            //     .collect::<Vec<_>>();
+
+            body.extend(quote_spanned! { span =>
+                impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+                where
+                    #(
+                        #resolvable_field_tys: #resolvable_field_idents::State,
+                    )*
+                {
+                    // TODO: this is broken for now
+                    // /// Perform a state transition inferred from context.
+                    // pub fn transition<#(#new_resolvable_field_tys,)*>(self) -> Register<#(#new_resolvable_field_tys,)*>
+                    // where
+                    //     #(
+                    //         #new_resolvable_field_tys: #resolvable_field_idents::State #new_entitlement_bounds,
+                    //     )*
+                    // {
+                    //     // SAFETY: `self` is destroyed
+                    //     unsafe { StateBuilder::conjure() }.finish()
+                    // }
+
+                    /// Create a state builder for this register to perform
+                    /// a state transition.
+                    pub fn build_state(self) -> StateBuilder<#(#resolvable_field_tys,)*> {
+                        // SAFETY: `self` is destroyed
+                        unsafe { StateBuilder::conjure() }
+                    }
+
+                    /// Perform the state transition built by `f`,
+                    /// i.e. `reg.transition(|b| b.field().into_variant())`.
+                    pub fn transition<R, B>(self, f: impl FnOnce(StateBuilder<#(#resolvable_field_tys,)*>) -> B) -> R
+                    where
+                        B: ::proto_hal::macro_utils::AsRegister<Register = R>,
+                    {
+                        f(self.build_state()).into()
+                    }
+                }
+            });
+        }
+
+        if self
+            .fields()
+            .unresolvable()
+            .any(|field| field.access.is_read())
+        {
+            body.extend(quote_spanned! { span =>
+                impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+                where
+                    #(
+                        #resolvable_field_tys: #resolvable_field_idents::State,
+                    )*
+                {
+                    pub fn read(&self) -> Reader {
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe { read() }.into()
+                    }
+
+                    /// Read the register once, decoding all readable fields.
+                    ///
+                    /// Fields read from separate reads may tear against
+                    /// hardware updates, so fields which are related
+                    /// should be read from the same snapshot.
+                    pub fn read_all(&self) -> Snapshot {
+                        self.read().into()
+                    }
+                }
+            });
+
+            if self.args.asynchronous {
+                body.extend(quote_spanned! { span =>
+                    impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+                    where
+                        #(
+                            #resolvable_field_tys: #resolvable_field_idents::State,
+                        )*
+                    {
+                        /// Wait for the register to satisfy a condition.
+                        ///
+                        /// The register is read and `f` is evaluated every time
+                        /// `waker` is woken (i.e. the associated interrupt fires)
+                        /// until `f` yields a value.
+                        pub fn wait_for<'a, T>(
+                            &'a self,
+                            waker: &'a ::proto_hal::interrupt::InterruptWaker,
+                            mut f: impl FnMut(Reader) -> Option<T> + 'a,
+                        ) -> impl ::core::future::Future<Output = T> + 'a {
+                            ::proto_hal::interrupt::WaitFor::new(waker, move || f(self.read()))
+                        }
+                    }
+                });
+            }
+        }
+
+        if self
+            .fields()
+            .unresolvable()
+            .any(|field| field.access.is_write())
+        {
+            let state_ty = |field: &Field| self.state_ty(field);
+            let state_bits = self.generate_state_bits(self.fields().resolvable(), state_ty);
+            let view_bounds = self.generate_view_bounds(state_ty);
+
+            body.extend(quote_spanned! { span =>
+                impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+                where
+                    #(
+                        #resolvable_field_tys: #resolvable_field_idents::State,
+                    )*
+                    #(
+                        #view_bounds,
+                    )*
+                {
+                    /// Write the values produced by `f`.
+                    ///
+                    /// Resolvable fields are written with the states held
+                    /// by this register, so they are left unchanged.
+                    pub fn write(&self, f: impl FnOnce(&mut Writer) -> &mut Writer) {
+                        let Ok(()) = self.try_write(|w| Ok::<_, ::core::convert::Infallible>(f(w)));
+                    }
+
+                    /// Write the values produced by `f`, only if
+                    /// `f` succeeds (i.e. all values are valid).
+                    pub fn try_write<E>(&self, f: impl FnOnce(&mut Writer) -> Result<&mut Writer, E>) -> Result<(), E> {
+                        ::proto_hal::__profile!(Write, {
+                            let mut writer = Writer::new();
+
+                            #(
+                                writer.value |= #state_bits;
+                            )*
+
+                            f(&mut writer)?;
+
+                            // SAFETY: assumes the proc macro implementation is sound
+                            // and that the peripheral description is accurate
+                            unsafe {
+                                write_raw(writer.value);
+                            }
+                        });
+
+                        Ok(())
+                    }
+                }
+            });
+        }
+
+        if self
+            .fields()
+            .unresolvable()
+            .any(|field| field.access.is_read())
+            && self
+                .fields()
+                .unresolvable()
+                .any(|field| field.access.is_write())
+        {
+            // fields requiring entitlements to be written
+            // are only written through the writer
+            let modifiable_fields = self
+                .fields()
+                .readable()
+                .writable()
+                .unresolvable()
+                .numeric(AccessMarker::Read)
+                .numeric(AccessMarker::Write)
+                .filter(|field| field.write_entitlements().is_none())
+                .collect::<Vec<_>>();
+            let modifiable_field_idents = modifiable_fields
+                .iter()
+                .map(|field| &field.ident)
+                .collect::<Vec<_>>();
+            let modify_field_idents = modifiable_field_idents
+                .iter()
+                .map(|ident| format_ident!("modify_{ident}"));
+            let value_tys = modifiable_fields
+                .iter()
+                .map(|field| field.value_ty())
+                .collect::<Vec<_>>();
+
+            let swappable_enumerated_field_idents = self
+                .fields()
+                .readable()
+                .writable()
+                .unresolvable()
+                .enumerated(AccessMarker::Read)
+                .enumerated(AccessMarker::Write)
+                .filter(|field| field.write_entitlements().is_none())
+                .map(|field| &field.ident)
+                .collect::<Vec<_>>();
+            let swap_field_idents = modifiable_field_idents
+                .iter()
+                .map(|ident| format_ident!("swap_{ident}"));
+            let (swap_doc, swap_guard) = match &self.args.atomic {
+                Some(AtomicStrategy::SingleContext) => (
+                    "*Note: This register is only accessed from a single context, so the read-modify-write is not protected.*",
+                    quote_spanned! { span => ::proto_hal::macro_utils::unguarded },
+                ),
+                _ => (
+                    "*Note: The read-modify-write is performed within a critical section.*",
+                    quote_spanned! { span => ::proto_hal::macro_utils::guarded },
+                ),
+            };
+
+            let swap_enumerated_field_idents = swappable_enumerated_field_idents
+                .iter()
+                .map(|ident| format_ident!("swap_{ident}"));
+
+            let read_effect_doc = if self
+                .fields()
+                .any(|field| field.access.read_effect() == Some(ReadEffect::Clear))
+            {
+                "\n*Note: This register contains fields which are cleared when read.*"
+            } else {
+                ""
+            };
+
+            let (atomicity_doc, write_back) = match &self.args.atomic {
+                // there are no other fields to preserve, so the value
+                // is written directly, unless a strategy is requested
+                None if self.is_single_field() => (
+                    "\n*Note: This register consists of a single field, so the value is written directly.*",
+                    quote_spanned! { span =>
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            write_raw(writer.value);
+                        }
+                    },
+                ),
+                None => (
+                    "\n*Note: This is a read-modify-write and as such is not atomic.*",
+                    quote_spanned! { span =>
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            rewrite_raw(writer.value);
+                        }
+                    },
+                ),
+                Some(AtomicStrategy::SetClear(SetClearArgs { set, clear })) => (
+                    "\n*Note: Only the modified bits are written via the set/clear registers, so concurrent modifications of other bits are not lost.*",
+                    quote_spanned! { span =>
+                        let set = writer.value & !value;
+                        let clear = value & !writer.value;
+
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            if set != 0 {
+                                ::proto_hal::macro_utils::write::<Raw>(super::base_addr() + #set, set);
+                                ::proto_hal::__trace!(Write, #set, super::base_addr() + #set, set);
+                            }
+
+                            if clear != 0 {
+                                ::proto_hal::macro_utils::write::<Raw>(super::base_addr() + #clear, clear);
+                                ::proto_hal::__trace!(Write, #clear, super::base_addr() + #clear, clear);
+                            }
+                        }
+                    },
+                ),
+                Some(AtomicStrategy::Exclusive) => (
+                    "\n*Note: The modified bits are written with exclusive accesses, retrying if another access intervenes, so concurrent modifications of other bits are not lost.*",
+                    quote_spanned! { span =>
+                        let changed = writer.value ^ value;
+
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            ::proto_hal::macro_utils::modify_exclusive::<Raw>(super::base_addr() + OFFSET, |current| {
+                                // fields with write effects are written inert
+                                let current = (current & !INERT_ZEROS) | INERT_ONES;
+
+                                (current & !changed) | (writer.value & changed)
+                            });
+                            ::proto_hal::__trace!(Write, OFFSET, super::base_addr() + OFFSET, writer.value);
+                        }
+                    },
+                ),
+                Some(AtomicStrategy::SingleContext) => (
+                    "\n*Note: This register is only accessed from a single context, so the read-modify-write is not protected.*",
+                    quote_spanned! { span =>
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            rewrite_raw(writer.value);
+                        }
+                    },
+                ),
+                Some(AtomicStrategy::BitBand) => (
+                    "\n*Note: Only the modified fields are written (each a single bit) via the bit-band alias region, so concurrent modifications of other fields are not lost.*",
+                    quote_spanned! { span =>
+                        let changed = writer.value ^ value;
+
+                        for bit in 0..Raw::BITS as u8 {
+                            if changed & (1 << bit) != 0 {
+                                // SAFETY: assumes the proc macro implementation is sound
+                                // and that the peripheral description is accurate
+                                unsafe {
+                                    let addr = ::proto_hal::macro_utils::bit_band_alias(super::base_addr() + OFFSET, bit);
+
+                                    ::proto_hal::macro_utils::write::<u32>(addr, (writer.value >> bit) & 1);
+                                    ::proto_hal::__trace!(Write, OFFSET, addr, (writer.value >> bit) & 1);
+                                }
+                            }
+                        }
+                    },
+                ),
+            };
+
+            body.extend(quote_spanned! { span =>
+                impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+                where
+                    #(
+                        #resolvable_field_tys: #resolvable_field_idents::State,
+                    )*
+                {
+                    /// Read the register and write back the values
+                    /// produced by `f`, preserving all fields which
+                    /// are not written.
+                    #[doc = #atomicity_doc]
+                    #[doc = #read_effect_doc]
+                    pub fn modify(&self, f: impl for<'w> FnOnce(&Reader, &'w mut Writer) -> &'w mut Writer) {
+                        let Ok(()) = self.try_modify(|r, w| Ok::<_, ::core::convert::Infallible>(f(r, w)));
+                    }
+
+                    /// Read the register and write back the values
+                    /// produced by `f`, only if `f` succeeds (i.e.
+                    /// all values are valid).
+                    #[doc = #atomicity_doc]
+                    #[doc = #read_effect_doc]
+                    pub fn try_modify<E>(&self, f: impl for<'w> FnOnce(&Reader, &'w mut Writer) -> Result<&'w mut Writer, E>) -> Result<(), E> {
+                        ::proto_hal::__profile!(Modify, {
+                            // SAFETY: assumes the proc macro implementation is sound
+                            // and that the peripheral description is accurate
+                            let value = unsafe { read_raw() };
+
+                            let reader = Reader { value: ::proto_hal::macro_utils::RegisterValue::new(value) };
+
+                            // fields with write effects are written inert
+                            // unless explicitly written by `f`
+                            let value = (value & !INERT_ZEROS) | INERT_ONES;
+                            let mut writer = Writer { value };
+
+                            f(&reader, &mut writer)?;
+
+                            #write_back
+                        });
+
+                        Ok(())
+                    }
+
+                    #(
+                        /// Read the current value of the field and write back
+                        /// the value produced by `f`, preserving the rest of the register.
+                        pub fn #modify_field_idents(&self, f: impl FnOnce(#value_tys) -> #value_tys) {
+                            self.modify(|r, w| w.#modifiable_field_idents(f(r.#modifiable_field_idents())))
+                        }
+                    )*
+
+                    #(
+                        /// Write a new value to the field, returning the previous value.
+                        ///
+                        #[doc = #swap_doc]
+                        pub fn #swap_field_idents(&self, value: #value_tys) -> #value_tys {
+                            #swap_guard(|| {
+                                let mut previous = None;
+
+                                self.modify(|r, w| {
+                                    previous.replace(r.#modifiable_field_idents());
+                                    w.#modifiable_field_idents(value)
+                                });
+
+                                // `modify` always invokes the closure
+                                previous.unwrap()
+                            })
+                        }
+                    )*
+
+                    #(
+                        /// Write a new variant to the field, returning the previous variant.
+                        ///
+                        #[doc = #swap_doc]
+                        pub fn #swap_enumerated_field_idents(&self, variant: #swappable_enumerated_field_idents::WriteVariant) -> #swappable_enumerated_field_idents::ReadVariant {
+                            #swap_guard(|| {
+                                let mut previous = None;
+
+                                self.modify(|r, w| {
+                                    previous.replace(r.#swappable_enumerated_field_idents());
+                                    w.#swappable_enumerated_field_idents().variant(variant)
+                                });
+
+                                // `modify` always invokes the closure
+                                previous.unwrap()
+                            })
+                        }
+                    )*
+                }
+            });
+        }
+
+        body
+    }
+
+    fn maybe_generate_clear_methods(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let clearable_fields = self
+            .fields()
+            .filter(|field| {
+                matches!(
+                    field.access.write_effect(),
+                    Some(WriteEffect::OneToClear | WriteEffect::ZeroToClear)
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if clearable_fields.is_empty() {
+            return None;
+        }
+
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+
+        // registers without readable fields have nothing to preserve
+        let rewrite = self.rewrite_ident();
+        let value = if self.fields().any(|field| field.access.is_read()) {
+            quote_spanned! { span =>
+                (read_raw() & !INERT_ZEROS) | INERT_ONES
+            }
+        } else {
+            quote_spanned! { span => INERT_ONES }
+        };
+
+        let clear_idents = clearable_fields
+            .iter()
+            .map(|field| format_ident!("clear_{}", field.ident));
+        let clear_exprs = clearable_fields.iter().map(|field| {
+            let mask = field.mask();
+
+            match field.access.write_effect() {
+                Some(WriteEffect::ZeroToClear) => quote_spanned! { span => value & !#mask },
+                _ => quote_spanned! { span => value | #mask },
+            }
+        });
+
+        Some(quote_spanned! { span =>
+            impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+            where
+                #(
+                    #resolvable_field_tys: #resolvable_field_idents::State,
+                )*
+            {
+                #(
+                    /// Clear the field, leaving the rest of the register unaffected.
+                    pub fn #clear_idents(&self) {
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            let value = #value;
+
+                            #rewrite(#clear_exprs);
+                        }
+                    }
+                )*
+            }
+        })
     }
 
-    fn maybe_generate_refined_writers(&self) -> Option<TokenStream2> {
+    fn maybe_generate_dyn_state(&self) -> Option<TokenStream2> {
+        if !self.is_resolvable() {
+            return None;
+        }
+
         let span = self.args.span();
 
-        let writable_enumerated_fields = self
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+        let unresolvable_field_idents = self
             .fields()
-            .enumerated(AccessMarker::Write)
+            .unresolvable()
+            .idents()
+            .map(|ident| format_ident!("_{ident}"))
             .collect::<Vec<_>>();
 
-        if writable_enumerated_fields.is_empty() {
+        let ordered_writes = self.generate_ordered_writes(
+            &quote_spanned! { span => (read_raw() & !INERT_ZEROS) | INERT_ONES },
+            &quote_spanned! { span => value },
+        );
+
+        // whether a view sharing bits is selected by the
+        // state of its selector, as decoded by `selector_state`
+        let is_selected = |field: &Field, selector_state: &dyn Fn(&Ident) -> TokenStream2| {
+            let selector_ident = &self.view_selector(field)?.ident;
+            let selector_state = selector_state(selector_ident);
+            let mut states = field
+                .selecting_states(selector_ident)
+                .into_iter()
+                .collect::<Vec<_>>();
+            states.sort();
+
+            Some(quote_spanned! { span =>
+                matches!(#selector_state, #(#selector_ident::ReadVariant::#states { .. })|*)
+            })
+        };
+
+        // the bits of inactive views hold no state, so
+        // they are decoded as the reset of the view
+        let decoded_states = self.fields().resolvable().map(|field| {
+            let ident = &field.ident;
+            let decode = |ident: &Ident| {
+                quote_spanned! { span =>
+                    #ident::ReadVariant::from_bits(
+                        (value >> #ident::OFFSET)
+                            & (u32::MAX >> (32 - #ident::WIDTH as u32))
+                    )
+                }
+            };
+            let state = decode(ident);
+
+            match is_selected(field, &decode) {
+                Some(is_selected) => quote_spanned! { span =>
+                    if #is_selected { #state } else { <#ident::Reset as #ident::State>::RAW }
+                },
+                None => state,
+            }
+        });
+
+        // views sharing bits are only written when
+        // selected by the state of their selector
+        let state_bits = self.fields().resolvable().map(|field| {
+            let ident = &field.ident;
+            let bits = quote_spanned! { span =>
+                values.#ident.bits() << #ident::OFFSET
+            };
+
+            match is_selected(field, &|ident| quote_spanned! { span => values.#ident }) {
+                Some(is_selected) => quote_spanned! { span =>
+                    (if #is_selected { #bits } else { 0 })
+                },
+                None => bits,
+            }
+        });
+
+        Some(quote_spanned! { span =>
+            /// The values of the states of the resolvable fields of
+            /// this register, to be inspected or applied with a
+            /// [`DynState`].
+            #[derive(Clone, Copy, PartialEq, Eq)]
+            pub struct StateValues {
+                #(
+                    pub #resolvable_field_idents: #resolvable_field_idents::ReadVariant,
+                )*
+            }
+
+            /// The states of the resolvable fields of this register,
+            /// tracked at runtime rather than in the type system.
+            ///
+            /// Like the state tokens of the register, this claims
+            /// ownership of the register, so it is only obtained by
+            /// converting the register or by (unsafely) reading it.
+            #[derive(PartialEq, Eq)]
+            pub struct DynState {
+                values: StateValues,
+            }
+
+            impl DynState {
+                /// Read the current states of the resolvable fields.
+                ///
+                /// # Safety
+                ///
+                /// No state tokens of this register may exist, as the
+                /// produced states claim ownership of the register.
+                pub unsafe fn read() -> Self {
+                    Self::from_raw(read_raw())
+                }
+
+                /// Decode the states of the resolvable fields
+                /// from a raw value of the register.
+                ///
+                /// # Safety
+                ///
+                /// See [`DynState::read`]. Additionally, `value`
+                /// must be the current value of the register.
+                pub unsafe fn from_raw(value: u32) -> Self {
+                    Self {
+                        values: StateValues {
+                            #(
+                                #resolvable_field_idents: #decoded_states,
+                            )*
+                        },
+                    }
+                }
+
+                /// The current states of the resolvable fields.
+                pub fn values(&self) -> StateValues {
+                    self.values
+                }
+
+                /// Write the states to the register, preserving
+                /// the rest of the register.
+                ///
+                /// # Safety
+                ///
+                /// The entitlements of the states must be satisfied,
+                /// as the typed transitions of the register would
+                /// require.
+                pub unsafe fn apply(self, values: StateValues) -> Self {
+                    let mut value = (read_raw() & !INERT_ZEROS) | INERT_ONES;
+
+                    #(
+                        value &= !((u32::MAX >> (32 - #resolvable_field_idents::WIDTH as u32)) << #resolvable_field_idents::OFFSET);
+                    )*
+
+                    #(
+                        value |= #state_bits;
+                    )*
+
+                    #ordered_writes
+
+                    rewrite_raw(value);
+
+                    Self { values }
+                }
+
+                /// Convert the states into state tokens, only if the
+                /// fields are in the states of the requested tokens.
+                pub fn into_typed<#(#resolvable_field_tys,)*>(self) -> Result<Register<#(#resolvable_field_tys,)*>, Self>
+                where
+                    #(
+                        #resolvable_field_tys: #resolvable_field_idents::State,
+                    )*
+                {
+                    if #(self.values.#resolvable_field_idents == #resolvable_field_tys::RAW)&&* {
+                        // SAFETY: the states are consumed and match the tokens
+                        Ok(Register {
+                            #(
+                                #resolvable_field_idents: unsafe { #resolvable_field_tys::conjure() },
+                            )*
+
+                            #(
+                                #unresolvable_field_idents: (), // placeholder
+                            )*
+
+                            _context: ::core::marker::PhantomData,
+                        })
+                    } else {
+                        Err(self)
+                    }
+                }
+
+                /// Read the register, converting the states into state
+                /// tokens only if the fields are in the states of the
+                /// requested tokens.
+                ///
+                /// Otherwise, the states read are yielded.
+                pub fn resolve<#(#resolvable_field_tys,)*>(self) -> Result<Register<#(#resolvable_field_tys,)*>, Self>
+                where
+                    #(
+                        #resolvable_field_tys: #resolvable_field_idents::State,
+                    )*
+                {
+                    // SAFETY: the states are consumed, so the read
+                    // states hold ownership of the register
+                    unsafe { Self::read() }.into_typed()
+                }
+            }
+
+            impl From<DynState> for ::proto_hal::error::Error {
+                fn from(_: DynState) -> Self {
+                    Self::Unresolved
+                }
+            }
+
+            impl<#(#resolvable_field_tys,)*> From<Register<#(#resolvable_field_tys,)*>> for DynState
+            where
+                #(
+                    #resolvable_field_tys: #resolvable_field_idents::State,
+                )*
+            {
+                fn from(_: Register<#(#resolvable_field_tys,)*>) -> Self {
+                    Self {
+                        values: StateValues {
+                            #(
+                                #resolvable_field_idents: #resolvable_field_tys::RAW,
+                            )*
+                        },
+                    }
+                }
+            }
+        })
+    }
+
+    /// Generate the write of the reset values of all writable fields,
+    /// as performed when the block is released.
+    ///
+    /// Fields with write effects are written inert, and fields whose
+    /// reset is unknown (or which are inactive at reset) are preserved.
+    fn maybe_generate_release_write(&self) -> Option<TokenStream2> {
+        if !self.is_writable() {
             return None;
         }
 
-        let refined_writer_idents = writable_enumerated_fields
-            .iter()
-            .map(|field| {
-                format_ident!(
-                    "{}Writer",
-                    inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string())
-                )
+        let span = self.args.span();
+
+        let reset_value = self.reset_value();
+        let (mask, bits) = self
+            .fields()
+            .writable()
+            .filter(|field| field.access.write_effect().is_none() && self.is_active_at_reset(field))
+            .filter_map(|field| {
+                let mask = field.mask();
+                let bits = match reset_value {
+                    Some(reset_value) => reset_value & mask,
+                    None => (field.reset_bits()? << field.offset) & mask,
+                };
+
+                Some((mask, bits))
             })
+            .fold((0u32, 0u32), |(mask, bits), (field_mask, field_bits)| {
+                (mask | field_mask, bits | field_bits)
+            });
+
+        // registers without readable fields have nothing to preserve
+        let rewrite = self.rewrite_ident();
+        let value = if self.fields().any(|field| field.access.is_read()) {
+            quote_spanned! { span =>
+                (read_raw() & !INERT_ZEROS) | INERT_ONES
+            }
+        } else {
+            quote_spanned! { span => INERT_ONES }
+        };
+
+        let ordered_writes =
+            self.generate_ordered_writes(&value, &quote_spanned! { span => value });
+
+        Some(quote_spanned! { span =>
+            /// Write the reset values of all writable fields,
+            /// preserving those whose reset is unknown.
+            pub(super) unsafe fn write_release() {
+                let value = #value;
+                let value = (value & !#mask) | #bits;
+
+                #ordered_writes
+
+                #rewrite(value);
+            }
+        })
+    }
+
+    fn maybe_generate_reset_write(&self) -> Option<TokenStream2> {
+        if !self.has_writable_state() {
+            return None;
+        }
+
+        let span = self.args.span();
+
+        let fields = self.fields().writable().resolvable().collect::<Vec<_>>();
+
+        let masks = fields.iter().map(|field| field.mask());
+        let state_bits = self.generate_state_bits(fields.iter().copied(), |field| {
+            let ident = &field.ident;
+
+            quote_spanned! { span => #ident::Reset }
+        });
+
+        // registers without readable fields have nothing to preserve
+        let rewrite = self.rewrite_ident();
+        let value = if self.fields().any(|field| field.access.is_read()) {
+            quote_spanned! { span =>
+                (read_raw() & !INERT_ZEROS) | INERT_ONES
+            }
+        } else {
+            quote_spanned! { span => INERT_ONES }
+        };
+
+        let ordered_writes =
+            self.generate_ordered_writes(&value, &quote_spanned! { span => value });
+
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+        let unresolvable_field_idents = self
+            .fields()
+            .unresolvable()
+            .idents()
+            .map(|ident| format_ident!("_{ident}"))
             .collect::<Vec<_>>();
 
+        Some(quote_spanned! { span =>
+            /// Write the reset state of all writable resolvable
+            /// fields, preserving the rest of the register.
+            pub(super) unsafe fn write_reset() {
+                let mut value = #value;
+
+                #(
+                    value &= !#masks;
+                )*
+
+                #(
+                    value |= #state_bits;
+                )*
+
+                #ordered_writes
+
+                #rewrite(value);
+            }
+
+            impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+            where
+                #(
+                    #resolvable_field_tys: #resolvable_field_idents::State,
+                )*
+            {
+                /// Return all resolvable fields to their reset states,
+                /// consuming the field tokens.
+                ///
+                /// Unresolvable fields are preserved.
+                pub fn reset(self) -> Reset {
+                    ::proto_hal::__profile!(Transition, {
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            write_reset();
+                        }
+                    });
+
+                    // SAFETY:
+                    // 1. `self` is destroyed
+                    // 2. the reset state has been written
+                    Register {
+                        #(
+                            #resolvable_field_idents: unsafe { <#resolvable_field_idents::Reset as #resolvable_field_idents::State>::conjure() },
+                        )*
+                        #(
+                            #unresolvable_field_idents: (),
+                        )*
+
+                        _context: ::core::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl<#(#resolvable_field_tys,)*> ::proto_hal::IntoReset for Register<#(#resolvable_field_tys,)*>
+            where
+                #(
+                    #resolvable_field_tys: #resolvable_field_idents::State,
+                )*
+            {
+                type Reset = Reset;
+
+                fn into_reset(self) -> Self::Reset {
+                    self.reset()
+                }
+            }
+        })
+    }
+
+    fn maybe_generate_sequences(&self) -> Option<TokenStream2> {
+        if self.args.sequences.elems.is_empty() {
+            return None;
+        }
+
+        let span = self.args.span();
+
+        // registers without readable fields have nothing to preserve
+        let rewrite = self.rewrite_ident();
+        let value = if self.fields().any(|field| field.access.is_read()) {
+            quote_spanned! { span =>
+                (read_raw() & !INERT_ZEROS) | INERT_ONES
+            }
+        } else {
+            quote_spanned! { span => INERT_ONES }
+        };
+
+        // steps are validated ahead of time
+        let write_steps = |steps: &PathArray| {
+            let (field_idents, variant_idents) = steps
+                .elems
+                .iter()
+                .filter_map(|step| self.written_variant(step).ok())
+                .map(|(field, variant)| (&field.ident, &variant.ident))
+                .unzip::<_, _, Vec<_>, Vec<_>>();
+
+            let doc = variant_idents
+                .iter()
+                .map(|variant| format!("`{variant}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let writes = quote_spanned! { span =>
+                #(
+                    // SAFETY: assumes the proc macro implementation is sound
+                    // and that the peripheral description is accurate
+                    unsafe {
+                        let value = #value;
+                        let value = value & !::proto_hal::macro_utils::mask(#field_idents::OFFSET, #field_idents::WIDTH);
+                        let value = value | (#field_idents::WriteVariant::#variant_idents.bits() << #field_idents::OFFSET);
+
+                        #rewrite(value);
+                    }
+
+                    ::core::sync::atomic::fence(::core::sync::atomic::Ordering::SeqCst);
+                )*
+            };
+
+            (doc, writes)
+        };
+
         let mut body = TokenStream2::new();
 
-        for (field, refined_writer_ident) in
-            writable_enumerated_fields.iter().zip(refined_writer_idents)
-        {
-            let field_ident = &field.ident;
+        for (ident, sequence) in &self.args.sequences.elems {
+            let token_ident = Ident::new(
+                &inflector::cases::pascalcase::to_pascal_case(&ident.to_string()),
+                ident.span(),
+            );
+
+            let (steps_doc, steps) = write_steps(&sequence.steps);
+            let (relock_doc, relock) = write_steps(&sequence.relock);
+
+            let token_doc = format!(
+                "Proof that the `{ident}` sequence has been performed, holding the register until relocked."
+            );
+            let method_doc =
+                format!("Perform the `{ident}` sequence, writing {steps_doc} in order.");
+            let relock_doc = if sequence.relock.elems.is_empty() {
+                "Relinquish this token, yielding the register.".to_string()
+            } else {
+                format!("Relock the `{ident}` sequence, writing {relock_doc} in order, yielding the register.")
+            };
+
+            body.extend(quote_spanned! { span =>
+                #[doc = #token_doc]
+                ///
+                /// Fields may require this token as a write entitlement.
+                pub struct #token_ident {
+                    register: Register,
+                }
+
+                impl ::proto_hal::stasis::Freeze for #token_ident {}
+
+                impl Register {
+                    #[doc = #method_doc]
+                    pub fn #ident(self) -> #token_ident {
+                        #steps
+
+                        #token_ident { register: self }
+                    }
+                }
+
+                impl #token_ident {
+                    #[doc = #relock_doc]
+                    pub fn relock(self) -> Register {
+                        #relock
+
+                        self.register
+                    }
+                }
+            });
+        }
+
+        Some(body)
+    }
+
+    /// Warn of writable registers with reserved bits
+    /// which do not specify how they are written
+    /// (only if enabled with `warn_reserved` on the block).
+    pub fn maybe_generate_reserved_warning(&self) -> Option<TokenStream2> {
+        if self.args.reserved.is_some()
+            || self.reserved_mask() == 0
+            || !self.fields().any(|field| field.access.is_write())
+        {
+            return None;
+        }
+
+        Some(warning(
+            self.ident.span(),
+            &format!(
+                "`{}` has reserved bits but no reserved bit policy. specify one with `reserved(keep)`, `reserved(zero)`, or `reserved(one)`",
+                self.ident,
+            ),
+        ))
+    }
+
+    /// The states which can never be entered because their
+    /// entitlements within this register contradict, with the
+    /// reasons for each contradiction (or otherwise the
+    /// reachability of entitled fields which are not resolvable).
+    fn unreachable_states(&self) -> Vec<(&Field, &Variant, Vec<String>)> {
+        let mut unreachable = Vec::new();
+
+        // conflicts between states are shared by all searches
+        let mut search = Search::default();
 
-            let schema = match &field.access {
-                Access::Write(write) | Access::ReadWrite { read: _, write } => &write.schema,
-                _ => unreachable!("fields are writable"),
+        for field in &self.fields {
+            let Some(states) = field.states() else {
+                continue;
             };
 
-            let Numericity::Enumerated { variants } = &schema.numericity else {
-                unreachable!("field schemas are enumerated in write direction")
-            };
+            for state in states {
+                let mut entitled = Vec::<(&Ident, Vec<&Ident>)>::new();
 
-            let accessors = variants.iter().map(|variant| {
-                Ident::new(
-                    &inflector::cases::snakecase::to_snake_case(&variant.ident.to_string()),
-                    field.args.span(),
-                )
-            });
+                for (entitled_field, entitled_state) in sorted_paths(&state.entitlements)
+                    .into_iter()
+                    .filter_map(local_entitlement)
+                {
+                    match entitled
+                        .iter_mut()
+                        .find(|(ident, _)| *ident == entitled_field)
+                    {
+                        Some((_, states)) => states.push(entitled_state),
+                        None => entitled.push((entitled_field, vec![entitled_state])),
+                    }
+                }
+
+                // the candidate states of each entitled field
+                let mut groups = Vec::new();
+                let mut unresolvable = Vec::new();
+
+                for (entitled_field_ident, entitled_states) in entitled {
+                    let Some(entitled_field) = self
+                        .fields
+                        .iter()
+                        .find(|candidate| &candidate.ident == entitled_field_ident)
+                    else {
+                        continue;
+                    };
 
-            let variant_idents = variants.iter().map(|variant| &variant.ident);
+                    let Some(entitled_field_states) = entitled_field.states() else {
+                        unresolvable.push(format!(
+                            "it is entitled to states of `{entitled_field_ident}`, which is not resolvable, so its state is never known",
+                        ));
 
-            body.extend(quote_spanned! { span =>
-                pub struct #refined_writer_ident<'a, W> {
-                    w: &'a mut W
+                        continue;
+                    };
+
+                    groups.push((
+                        entitled_field_ident,
+                        entitled_field_states
+                            .iter()
+                            .filter(|candidate| entitled_states.contains(&&candidate.ident))
+                            .collect::<Vec<_>>(),
+                    ));
                 }
 
-                impl<'a, W> #refined_writer_ident<'a, W>
-                where
-                    W: ::proto_hal::macro_utils::Writer,
-                {
-                    pub fn variant(self, variant: #field_ident::WriteVariant) -> &'a mut W {
-                        unsafe { ::proto_hal::macro_utils::Writer::write(self.w, |reg| *reg |= (variant as u32) << #field_ident::OFFSET) }
+                if !unresolvable.is_empty() {
+                    unreachable.push((field, state, unresolvable));
+
+                    continue;
+                }
+
+                // the state is reachable if some states of the fields it
+                // is entitled to permit the state and each other in return
+                let mut reasons = Vec::new();
+
+                if !search.run(&mut vec![(&field.ident, state)], &groups, &mut reasons) {
+                    if reasons.is_empty() {
+                        reasons.push("none of the states it requires exist".to_string());
                     }
 
-                    #(
-                        pub fn #accessors(self) -> &'a mut W {
-                            self.variant(#field_ident::WriteVariant::#variant_idents)
-                        }
-                    )*
+                    unreachable.push((field, state, reasons));
                 }
-            });
+            }
         }
 
-        Some(body)
+        unreachable
     }
 
-    fn maybe_generate_reader(&self) -> Option<TokenStream2> {
-        let span = self.args.span();
+    /// Warn of variants which can never be entered because
+    /// their entitlements within this register contradict,
+    /// explaining each contradiction (only if enabled with
+    /// `warn_unreachable` on the block).
+    pub fn generate_reachability_warnings(&self) -> TokenStream2 {
+        self.unreachable_states()
+            .into_iter()
+            .flat_map(|(field, state, reasons)| {
+                warning(
+                    state.args.span(),
+                    &format!(
+                        "`{}::{}` is unreachable: {}",
+                        field.ident,
+                        state.ident,
+                        reasons.join("; "),
+                    ),
+                )
+            })
+            .collect()
+    }
 
-        let readable_unresolvable_fields =
-            self.fields().readable().unresolvable().collect::<Vec<_>>();
+    /// Generate whether each state can be entered with respect to the
+    /// other fields of this register, which the states inherit in
+    /// addition to the reachability of their field.
+    ///
+    /// *Note: Entitlements to states of other registers are not
+    /// considered, as registers entitled to each other would
+    /// depend on each other to be evaluated.*
+    fn maybe_generate_local_reachability(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
 
-        // don't generate a reader if there are no fields
-        // to be read
-        if readable_unresolvable_fields.is_empty() {
-            return None;
-        };
+        let unreachable = self.unreachable_states();
 
-        let readable_unresolvable_numeric_fields = self
-            .fields()
-            .readable()
-            .unresolvable()
-            .numeric(AccessMarker::Read);
-        let readable_unresolvable_numeric_field_idents = self
-            .fields()
-            .readable()
-            .unresolvable()
-            .numeric(AccessMarker::Read)
-            .idents();
-        let readable_unresolvable_enumerated_field_idents = self
+        let impls = self
             .fields()
-            .readable()
-            .unresolvable()
-            .enumerated(AccessMarker::Read)
-            .idents();
-
-        let value_tys = readable_unresolvable_numeric_fields
-            .map(|field| {
-                let ident = format_ident!(
-                    "u{}",
-                    Index {
-                        index: field.width() as _,
-                        span: Span::call_site(),
-                    }
-                );
+            .resolvable()
+            .filter_map(|field| Some((field, field.states()?)))
+            .flat_map(|(field, states)| states.iter().map(move |state| (field, state)))
+            .map(|(field, state)| {
+                let field_ident = &field.ident;
+                let ident = &state.ident;
+                let (impl_generics, ty_generics) = state.generics();
+                let reachable =
+                    !unreachable
+                        .iter()
+                        .any(|(unreachable_field, unreachable_state, _)| {
+                            unreachable_field.ident == field.ident
+                                && unreachable_state.ident == state.ident
+                        });
 
-                match field.width() {
-                    1 => parse_quote! { bool },
-                    8 | 16 | 32 => {
-                        parse_quote! { #ident }
-                    }
-                    _ => {
-                        parse_quote! { ::proto_hal::macro_utils::arbitrary_int::#ident }
+                quote_spanned! { span =>
+                    impl #impl_generics Reachable for #field_ident::#ident #ty_generics {
+                        const REACHABLE: bool = #reachable;
                     }
                 }
             })
-            .collect::<Vec<Path>>();
+            .collect::<Vec<_>>();
 
-        Some(quote_spanned! { span =>
-            pub struct Reader {
-                value: ::proto_hal::macro_utils::RegisterValue,
-            }
+        if impls.is_empty() {
+            return None;
+        }
 
-            impl From<UnsafeReader> for Reader {
-                fn from(unsafe_reader: UnsafeReader) -> Self {
-                    Self { value: unsafe_reader.value }
-                }
+        Some(quote_spanned! { span =>
+            /// Whether states can be entered with respect
+            /// to the other fields of this register.
+            trait Reachable {
+                const REACHABLE: bool;
             }
 
-            impl Reader {
-                #(
-                    pub fn #readable_unresolvable_enumerated_field_idents(&self) -> #readable_unresolvable_enumerated_field_idents::ReadVariant {
-                        // SAFETY: assumes
-                        // 1. peripheral description is correct (offset/width)
-                        // 2. hardware is operating correctly
-                        unsafe {
-                            #readable_unresolvable_enumerated_field_idents::ReadVariant::from_bits(
-                                self.value.region(
-                                    #readable_unresolvable_enumerated_field_idents::OFFSET,
-                                    #readable_unresolvable_enumerated_field_idents::WIDTH
-                                )
-                            )
-                        }
-                    }
-                )*
-
-                #(
-                    pub fn #readable_unresolvable_numeric_field_idents(&self) -> #value_tys {
-                        self.value.#value_tys(#readable_unresolvable_numeric_field_idents::OFFSET)
-                    }
-                )*
-            }
+            #(
+                #impls
+            )*
         })
     }
 
-    fn maybe_generate_writer(&self) -> Option<TokenStream2> {
+    fn maybe_generate_unit_accessors(&self) -> Option<TokenStream2> {
         let span = self.args.span();
 
-        let writable_unresolvable_fields =
-            self.fields().writable().unresolvable().collect::<Vec<_>>();
-
-        // don't generate a reader if there are no fields
-        // to be written
-        if writable_unresolvable_fields.is_empty() {
-            return None;
-        };
+        let has_unit = |field: &&Field| field.args.unit.is_some();
 
-        let writable_unresolvable_numeric_fields = self
-            .fields()
-            .writable()
-            .unresolvable()
-            .numeric(AccessMarker::Write);
-        let writable_unresolvable_numeric_field_idents = self
-            .fields()
-            .writable()
-            .unresolvable()
-            .numeric(AccessMarker::Write)
-            .idents();
-        let writable_unresolvable_enumerated_fields = self
+        let readable_field_idents = self
             .fields()
-            .writable()
+            .readable()
             .unresolvable()
-            .enumerated(AccessMarker::Write)
+            .filter(has_unit)
+            .map(|field| &field.ident)
             .collect::<Vec<_>>();
-        let writable_unresolvable_enumerated_field_idents = self
+        let writable_fields = self
             .fields()
             .writable()
             .unresolvable()
-            .enumerated(AccessMarker::Write)
-            .idents();
+            .filter(has_unit)
+            .collect::<Vec<_>>();
+        let writable_field_idents = writable_fields
+            .iter()
+            .map(|field| &field.ident)
+            .collect::<Vec<_>>();
+        let writable_entitlement_params = writable_fields
+            .iter()
+            .map(|field| field.write_entitlement_param());
+        let writable_constraint_assertions = writable_fields.iter().map(|field| {
+            let ident = &field.ident;
 
-        let value_tys = writable_unresolvable_numeric_fields
-            .map(|field| {
-                let ident = format_ident!(
-                    "u{}",
-                    Index {
-                        index: field.width() as _,
-                        span: Span::call_site(),
-                    }
-                );
+            field.maybe_generate_constraint_assertion(
+                &quote_spanned! { span => #ident },
+                &quote_spanned! { span => quantity.raw() },
+            )
+        });
 
-                match field.width() {
-                    1 => parse_quote! { bool },
-                    8 | 16 | 32 => parse_quote! { #ident },
-                    _ => parse_quote! { ::proto_hal::macro_utils::arbitrary_int::#ident },
-                }
-            })
-            .collect::<Vec<Path>>();
+        if readable_field_idents.is_empty() && writable_field_idents.is_empty() {
+            return None;
+        }
 
-        let unresolvable_refined_writer_idents = writable_unresolvable_enumerated_fields
+        let readable_quantity_idents = readable_field_idents
             .iter()
-            .map(|field| {
-                format_ident!(
-                    "{}Writer",
-                    inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string())
-                )
-            })
-            .collect::<Vec<_>>();
-
-        Some(quote_spanned! { span =>
-            pub struct Writer {
-                value: u32,
-            }
+            .map(|ident| format_ident!("{ident}_quantity"));
+        let writable_quantity_idents = writable_field_idents
+            .iter()
+            .map(|ident| format_ident!("{ident}_quantity"));
 
-            impl ::proto_hal::macro_utils::Writer for Writer {
-                unsafe fn write(&mut self, f: impl FnOnce(&mut u32)) -> &mut Self {
-                    f(&mut self.value);
-                    self
-                }
-            }
+        let mut body = TokenStream2::new();
 
-            impl Writer {
-                const fn new() -> Self {
-                    Self {
-                        value: 0,
-                    }
+        if !readable_field_idents.is_empty() {
+            body.extend(quote_spanned! { span =>
+                impl Reader {
+                    #(
+                        pub fn #readable_quantity_idents(&self) -> #readable_field_idents::Quantity {
+                            #readable_field_idents::Quantity::from_raw(
+                                self.value.region(#readable_field_idents::OFFSET, #readable_field_idents::WIDTH)
+                            )
+                        }
+                    )*
                 }
+            });
+        }
 
-                #(
-                    pub fn #writable_unresolvable_enumerated_field_idents(&mut self) -> #unresolvable_refined_writer_idents<Self> {
-                        #unresolvable_refined_writer_idents { w: self }
-                    }
-                )*
+        if !writable_field_idents.is_empty() {
+            body.extend(quote_spanned! { span =>
+                impl Writer {
+                    #(
+                        pub fn #writable_quantity_idents(&mut self, #writable_entitlement_params quantity: #writable_field_idents::Quantity) -> &mut Self {
+                            #writable_constraint_assertions
 
-                #(
-                    pub fn #writable_unresolvable_numeric_field_idents(&mut self, value: #value_tys) -> &mut Self {
-                        unsafe {
-                            ::proto_hal::macro_utils::Writer::write(
-                                self,
-                                |reg| *reg |= (value as u32) << #writable_unresolvable_numeric_field_idents::OFFSET
-                            )
+                            unsafe {
+                                ::proto_hal::macro_utils::Writer::write(
+                                    self,
+                                    |reg| {
+                                        *reg &= !::proto_hal::macro_utils::mask(#writable_field_idents::OFFSET, #writable_field_idents::WIDTH);
+                                        *reg |= quantity.raw() << #writable_field_idents::OFFSET;
+                                    }
+                                )
+                            }
                         }
-                    }
-                )*
-            }
-        })
+                    )*
+                }
+            });
+        }
+
+        Some(body)
     }
 
-    fn maybe_generate_unsafe_reader(&self) -> Option<TokenStream2> {
+    fn maybe_generate_stream(&self) -> Option<TokenStream2> {
         let span = self.args.span();
 
-        // don't generate a reader if there are no fields
-        // to be read
-        if !self.fields().any(|field| field.access.is_read()) {
-            return None;
-        };
+        let stream = self.args.stream.as_ref()?;
 
-        let readable_numeric_fields = self.fields().readable().numeric(AccessMarker::Read);
-        let readable_numeric_field_idents = self
-            .fields()
-            .readable()
-            .numeric(AccessMarker::Read)
-            .idents();
-        let readable_enumerated_field_idents = self
-            .fields()
-            .readable()
-            .enumerated(AccessMarker::Read)
-            .idents();
+        // validated
+        let field = self.fields.first().unwrap();
+        let ident = &field.ident;
+        let value_ty = field.value_ty();
 
-        let value_tys = readable_numeric_fields
-            .map(|field| {
-                let ident = format_ident!(
-                    "u{}",
-                    Index {
-                        index: field.width() as _,
-                        span: Span::call_site(),
-                    }
+        let wait = |ready: &Option<Path>| {
+            ready.as_ref().map(|ready| {
+                let segments = ready.segments.iter().collect::<Vec<_>>();
+                let (register, field, variant) = (
+                    &segments[0].ident,
+                    &segments[1].ident,
+                    &segments[2].ident,
                 );
 
-                match field.width() {
-                    1 => parse_quote! { bool },
-                    8 | 16 | 32 => {
-                        parse_quote! { #ident }
-                    }
-                    _ => {
-                        parse_quote! { ::proto_hal::macro_utils::arbitrary_int::#ident }
-                    }
+                quote_spanned! { span =>
+                    // SAFETY: the field is only read
+                    while unsafe { super::#register::read() }.#field() != super::#register::#field::ReadVariant::#variant {}
                 }
             })
-            .collect::<Vec<Path>>();
+        };
 
-        Some(quote_spanned! { span =>
-            pub struct UnsafeReader {
-                value: ::proto_hal::macro_utils::RegisterValue,
-            }
+        let mut methods = TokenStream2::new();
 
-            impl UnsafeReader {
-                const fn new(value: u32) -> Self {
-                    Self {
-                        value: ::proto_hal::macro_utils::RegisterValue::new(value),
+        if field.access.is_read() {
+            let wait = wait(&stream.read_ready);
+
+            methods.extend(quote_spanned! { span =>
+                /// Fill `buf` with successive reads of the register.
+                pub fn read_into(&self, buf: &mut [#value_ty]) {
+                    for slot in buf {
+                        #wait
+
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        *slot = unsafe { read() }.#ident();
                     }
                 }
+            });
+        }
 
-                #(
-                    pub fn #readable_enumerated_field_idents(&self) -> #readable_enumerated_field_idents::ReadVariant {
-                        // SAFETY: assumes
-                        // 1. peripheral description is correct (offset/width)
-                        // 2. hardware is operating correctly
+        if field.access.is_write() {
+            let wait = wait(&stream.write_ready);
+            let raw = field.value_to_raw(&parse_quote! { value });
+            let assertion =
+                field.maybe_generate_constraint_assertion(&quote_spanned! { span => #ident }, &raw);
+
+            methods.extend(quote_spanned! { span =>
+                /// Write each value of `iter` to the register in turn.
+                pub fn write_iter(&self, iter: impl IntoIterator<Item = #value_ty>) {
+                    for value in iter {
+                        #assertion
+
+                        #wait
+
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
                         unsafe {
-                            #readable_enumerated_field_idents::ReadVariant::from_bits(
-                                self.value.region(
-                                    #readable_enumerated_field_idents::OFFSET,
-                                    #readable_enumerated_field_idents::WIDTH
-                                )
-                            )
+                            write_raw(#raw << #ident::OFFSET);
                         }
                     }
-                )*
+                }
+            });
+        }
 
-                #(
-                    pub fn #readable_numeric_field_idents(&self) -> #value_tys {
-                        self.value.#value_tys(#readable_numeric_field_idents::OFFSET)
-                    }
-                )*
+        Some(quote_spanned! { span =>
+            impl Register {
+                #methods
             }
         })
     }
 
-    fn maybe_generate_unsafe_writer(&self) -> Option<TokenStream2> {
+    fn maybe_generate_sized_writes(&self) -> Option<TokenStream2> {
         let span = self.args.span();
 
-        // don't generate a reader if there are no fields
-        // to be written
-        if !self.fields().any(|field| field.access.is_write()) {
+        let sized_fields = self
+            .fields
+            .iter()
+            .filter(|field| field.args.access_size.is_some())
+            .collect::<Vec<_>>();
+
+        if sized_fields.is_empty() {
             return None;
-        };
+        }
 
-        let writable_numeric_fields = self.fields().writable().numeric(AccessMarker::Write);
-        let writable_numeric_field_idents = self
-            .fields()
-            .writable()
-            .numeric(AccessMarker::Write)
-            .idents();
-        let writable_enumerated_fields = self
-            .fields()
-            .writable()
-            .enumerated(AccessMarker::Write)
-            .collect::<Vec<_>>();
-        let writable_enumerated_field_idents = self
-            .fields()
-            .writable()
-            .enumerated(AccessMarker::Write)
-            .idents();
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+        let state_ty = |field: &Field| self.state_ty(field);
+        let view_bounds = self.generate_view_bounds(state_ty);
 
-        let value_tys = writable_numeric_fields
-            .map(|field| {
-                let ident = format_ident!(
-                    "u{}",
-                    Index {
-                        index: field.width() as _,
-                        span: Span::call_site(),
-                    }
-                );
+        let methods = sized_fields.iter().map(|field| {
+            let ident = &field.ident;
+            let method_ident = format_ident!("write_{ident}_sized");
+            let value_ty = field.value_ty();
+            let raw = field.value_to_raw(&parse_quote! { value });
+            let assertion =
+                field.maybe_generate_constraint_assertion(&quote_spanned! { span => #ident }, &raw);
+            let entitlement_param = field.write_entitlement_param();
+
+            // validated
+            let access_size = **field.args.access_size.as_ref().unwrap();
+            let access_ty = format_ident!("u{access_size}");
+            let byte_offset = (field.offset / access_size * access_size / 8) as u32;
+            let shift = byte_offset * 8;
+
+            // the states of the other writable fields are written
+            // as they are when the register state is finished
+            let state_bits = self.generate_state_bits(
+                self.fields()
+                    .writable()
+                    .resolvable()
+                    .filter(|other| other.ident != *ident),
+                state_ty,
+            );
 
-                match field.width() {
-                    1 => parse_quote! { bool },
-                    8 | 16 | 32 => parse_quote! { #ident },
-                    _ => parse_quote! { ::proto_hal::macro_utils::arbitrary_int::#ident },
-                }
-            })
-            .collect::<Vec<Path>>();
+            let doc = format!(
+                "Write the `{ident}` field alone with a `{access_ty}` access to the bytes containing it.\n\n\
+                Other unresolvable fields within the access are written as zero, or inert."
+            );
 
-        let refined_writer_idents = writable_enumerated_fields
-            .iter()
-            .map(|field| {
-                format_ident!(
-                    "{}Writer",
-                    inflector::cases::pascalcase::to_pascal_case(&field.ident.to_string())
-                )
-            })
-            .collect::<Vec<_>>();
+            quote_spanned! { span =>
+                #[doc = #doc]
+                pub fn #method_ident(&self, #entitlement_param value: #value_ty)
+                where
+                    #(
+                        #view_bounds,
+                    )*
+                {
+                    #assertion
 
-        Some(quote_spanned! { span =>
-            pub struct UnsafeWriter {
-                value: u32,
-            }
+                    #[allow(unused_parens)]
+                    let value = (#raw << #ident::OFFSET) #(| #state_bits)* | INERT_ONES;
 
-            impl ::proto_hal::macro_utils::Writer for UnsafeWriter {
-                unsafe fn write(&mut self, f: impl FnOnce(&mut u32)) -> &mut Self {
-                    f(&mut self.value);
-                    self
-                }
-            }
+                    let addr = super::base_addr() + OFFSET + #byte_offset;
+                    let value = value >> #shift;
 
-            impl UnsafeWriter {
-                const fn new() -> Self {
-                    Self {
-                        value: 0,
+                    // SAFETY: assumes the proc macro implementation is sound
+                    // and that the peripheral description is accurate
+                    unsafe {
+                        ::proto_hal::macro_utils::write::<#access_ty>(addr, value);
                     }
+
+                    ::proto_hal::__trace!(Write, OFFSET, addr, value);
                 }
+            }
+        });
 
+        Some(quote_spanned! { span =>
+            impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+            where
                 #(
-                    pub fn #writable_enumerated_field_idents(&mut self) -> #refined_writer_idents<Self> {
-                        #refined_writer_idents { w: self }
-                    }
+                    #resolvable_field_tys: #resolvable_field_idents::State,
                 )*
-
+            {
                 #(
-                    pub fn #writable_numeric_field_idents(&mut self, value: #value_tys) -> &mut Self {
-                        unsafe {
-                            ::proto_hal::macro_utils::Writer::write(
-                                self,
-                                |reg| *reg |= (value as u32) << #writable_numeric_field_idents::OFFSET
-                            )
-                        }
-                    }
+                    #methods
                 )*
             }
         })
     }
 
-    fn generate_unsafe_interface(&self) -> TokenStream2 {
+    fn maybe_generate_presets(&self) -> Option<TokenStream2> {
+        if self.args.presets.elems.is_empty() {
+            return None;
+        }
+
         let span = self.args.span();
 
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+        let unresolvable_field_idents = self
+            .fields()
+            .unresolvable()
+            .idents()
+            .map(|ident| format_ident!("_{ident}"))
+            .collect::<Vec<_>>();
+
         let mut body = TokenStream2::new();
+        let mut methods = TokenStream2::new();
 
-        if self.fields().any(|field| field.access.is_read()) {
-            body.extend(quote_spanned! { span =>
-                pub unsafe fn read() -> UnsafeReader {
-                    UnsafeReader::new(
-                        ::core::ptr::read_volatile((super::BASE_ADDR + OFFSET) as *const u32)
+        for (ident, entries) in &self.args.presets.elems {
+            let const_ident = Ident::new(
+                &inflector::cases::screamingsnakecase::to_screaming_snake_case(&ident.to_string()),
+                ident.span(),
+            );
+            let alias_ident = Ident::new(
+                &inflector::cases::pascalcase::to_pascal_case(&ident.to_string()),
+                ident.span(),
+            );
+
+            // entries are validated ahead of time
+            let written = entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    PresetEntry::Variant(path) => self.written_variant(path).ok(),
+                    PresetEntry::Value { .. } => None,
+                })
+                .collect::<Vec<_>>();
+
+            let values = entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    PresetEntry::Value { field, value } => {
+                        self.written_value(field).ok().map(|field| (field, value))
+                    }
+                    PresetEntry::Variant(_) => None,
+                })
+                .collect::<Vec<_>>();
+
+            let (value, mask) = written
+                .iter()
+                .map(|(field, variant)| (*field, variant.bits))
+                .fold((0u32, 0u32), |(value, mask), (field, bits)| {
+                    (
+                        value | (bits << field.offset) & field.mask(),
+                        mask | field.mask(),
                     )
+                });
+            let mask = values
+                .iter()
+                .fold(mask, |mask, (field, _)| mask | field.mask());
+
+            // numeric values are only known once evaluated,
+            // so they are composed (and checked) in const context
+            let numeric_values = values.iter().map(|(field, value)| {
+                let offset = field.offset;
+
+                quote_spanned! { value.span() => ((#value) as u32) << #offset }
+            });
+            let numeric_checks = values.iter().map(|(field, value)| {
+                let max = u32::MAX >> (32 - field.width());
+                let msg = format!("value of `{}` does not fit within field width", field.ident);
+
+                quote_spanned! { value.span() =>
+                    const _: () = assert!(((#value) as u32) <= #max, #msg);
                 }
             });
-        }
 
-        if self.fields().any(|field| field.access.is_write()) {
+            // the states of resolvable fields in declaration order
+            let preset_tys = self
+                .fields()
+                .resolvable()
+                .filter_map(|field| {
+                    written
+                        .iter()
+                        .find(|(written, _)| written.ident == field.ident)
+                        .map(|(_, variant)| {
+                            let field_ident = &field.ident;
+                            let variant_ident = &variant.ident;
+
+                            quote_spanned! { span => #field_ident::#variant_ident }
+                        })
+                })
+                .collect::<Vec<_>>();
+
+            let const_doc = format!("The value written by the `{ident}` preset.");
+            let alias_doc = format!("The register as configured by the `{ident}` preset.");
+            let method_doc = format!(
+                "Apply the `{ident}` preset, writing {} in a single write.",
+                entries
+                    .iter()
+                    .map(|entry| match entry {
+                        PresetEntry::Variant(path) => {
+                            format!("`{}`", path.segments.last().unwrap().ident)
+                        }
+                        PresetEntry::Value { field, value } => {
+                            format!("`{field} = {}`", value.to_token_stream())
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
             body.extend(quote_spanned! { span =>
-                pub unsafe fn write(f: impl FnOnce(&mut UnsafeWriter) -> &mut UnsafeWriter) {
-                    let mut writer = UnsafeWriter::new();
+                #[doc = #const_doc]
+                ///
+                /// *Note: Fields not specified by the preset are written
+                /// as zero, or inert if they have write effects.*
+                pub const #const_ident: u32 = #value #(| #numeric_values)* | (INERT_ONES & !#mask);
 
-                    f(&mut writer);
+                #(#numeric_checks)*
 
-                    ::core::ptr::write_volatile((super::BASE_ADDR + OFFSET) as *mut u32, writer.value);
-                }
+                #[doc = #alias_doc]
+                pub type #alias_ident = Register<#(#preset_tys,)*>;
             });
-        }
-
-        body
-    }
 
-    fn generate_register_struct(&self) -> TokenStream2 {
-        let span = self.args.span();
+            methods.extend(quote_spanned! { span =>
+                #[doc = #method_doc]
+                pub fn #ident(self) -> #alias_ident {
+                    // SAFETY: assumes the proc macro implementation is sound
+                    // and that the peripheral description is accurate
+                    unsafe {
+                        write_raw(#const_ident);
+                    }
 
-        let resolvable_field_idents = self.fields().resolvable().idents();
-        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+                    // SAFETY:
+                    // 1. `self` is destroyed
+                    // 2. state has been written
+                    Register {
+                        #(
+                            #resolvable_field_idents: unsafe { #resolvable_field_idents::State::conjure() },
+                        )*
 
-        let unresolvable_field_idents = self
-            .fields()
-            .unresolvable()
-            .idents()
-            .map(|ident| format_ident!("_{ident}"));
+                        #(
+                            #unresolvable_field_idents: (), // placeholder
+                        )*
 
-        quote_spanned! { span =>
-            /// A register. This type gates access to
-            /// the fields it encapsulates.
-            ///
-            /// Field members can be directly moved out of this struct
-            /// for lossy modification, or modified in place with
-            /// accessor methods.
-            pub struct Register<#(#resolvable_field_tys,)*> {
-                // resolvable fields.
-                #(
-                    pub #resolvable_field_idents: #resolvable_field_tys,
-                )*
+                        _context: ::core::marker::PhantomData,
+                    }
+                }
+            });
+        }
 
-                // Q: what is this for?
-                // unresolvable fields.
+        body.extend(quote_spanned! { span =>
+            impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+            where
                 #(
-                    #unresolvable_field_idents: (),
+                    #resolvable_field_tys: #resolvable_field_idents::State,
                 )*
+            {
+                #methods
             }
-        }
+        });
+
+        Some(body)
     }
 
-    fn maybe_generate_reset_alias(&self) -> Option<TokenStream2> {
-        if !self.is_resolvable() {
+    fn maybe_generate_trigger_methods(&self) -> Option<TokenStream2> {
+        let span = self.args.span();
+
+        let trigger_fields = self
+            .fields()
+            .filter(|field| field.access.write_effect() == Some(WriteEffect::Trigger))
+            .collect::<Vec<_>>();
+
+        if trigger_fields.is_empty() {
             return None;
+        }
+
+        let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
+        let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
+
+        // registers without readable fields have nothing to preserve
+        let rewrite = self.rewrite_ident();
+        let value = if self.fields().any(|field| field.access.is_read()) {
+            quote_spanned! { span =>
+                (read_raw() & !INERT_ZEROS) | INERT_ONES
+            }
+        } else {
+            quote_spanned! { span => INERT_ONES }
         };
 
-        let span = self.args.span();
+        let mut methods = TokenStream2::new();
 
-        let resolvable_field_idents = self.fields().resolvable().idents();
+        for field in trigger_fields {
+            let field_ident = &field.ident;
+            let entitlement_param = field.write_entitlement_param();
+
+            let Some(Numericity::Enumerated { variants }) =
+                field.write_schema().map(|schema| &schema.numericity)
+            else {
+                continue;
+            };
+
+            // range variants are written with field writers
+            for variant in variants.iter().filter(|variant| variant.range.is_none()) {
+                let variant_ident = &variant.ident;
+                let method_ident = Ident::new(
+                    &inflector::cases::snakecase::to_snake_case(&variant_ident.to_string()),
+                    span,
+                );
+                let doc = format!("Trigger `{variant_ident}` by writing it to `{field_ident}`.");
+
+                methods.extend(quote_spanned! { span =>
+                    #[doc = #doc]
+                    pub fn #method_ident(&self, #entitlement_param) {
+                        // SAFETY: assumes the proc macro implementation is sound
+                        // and that the peripheral description is accurate
+                        unsafe {
+                            let value = #value;
+                            let value = value & !::proto_hal::macro_utils::mask(#field_ident::OFFSET, #field_ident::WIDTH);
+                            let value = value | (#field_ident::WriteVariant::#variant_ident.bits() << #field_ident::OFFSET);
+
+                            #rewrite(value);
+                        }
+                    }
+                });
+            }
+        }
 
         Some(quote_spanned! { span =>
-            pub type Reset = Register<
+            impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+            where
                 #(
-                    #resolvable_field_idents::Reset,
+                    #resolvable_field_tys: #resolvable_field_idents::State,
                 )*
-            >;
+            {
+                #methods
+            }
         })
     }
 
-    fn maybe_generate_state_builder(&self) -> Option<TokenStream2> {
+    /// The bounds requiring the state of each resolvable field
+    /// to be entitled to the states of the fields it depends on.
+    fn generate_entitlement_bounds(&self) -> Vec<Option<TokenStream2>> {
+        let span = self.args.span();
+
+        self.fields()
+            .resolvable()
+            .map(|field| {
+                let schema = match &field.access {
+                    Access::Read(read) | Access::ReadWrite { read, write: _ } => &read.schema,
+                    _ => panic!("a resolvable field should not be write-only"),
+                };
+
+                if schema.entitlement_fields.is_empty() {
+                    return None;
+                }
+
+                let entitled_field_tys = schema
+                    .entitlement_fields
+                    .iter()
+                    .map(|ident| {
+                        Ident::new(
+                            &inflector::cases::pascalcase::to_pascal_case(&ident.to_string()),
+                            Span::call_site(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+
+                Some(quote_spanned! { span =>
+                    + #(::proto_hal::stasis::Entitled<#entitled_field_tys>)+*
+                })
+            })
+            .collect()
+    }
+
+    fn maybe_generate_parts(&self) -> Option<TokenStream2> {
         if !self.is_resolvable() {
             return None;
         };
@@ -994,73 +5047,131 @@ impl Register {
 
         let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
         let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
-        let writable_resolvable_field_idents = self.fields().writable().resolvable().idents();
-        let writable_resolvable_field_tys = self.fields().writable().resolvable().tys();
         let unresolvable_field_idents = self
             .fields()
             .unresolvable()
             .idents()
             .map(|ident| format_ident!("_{ident}"));
 
+        let entitlement_bounds = self.generate_entitlement_bounds();
+
+        // the tokens of resolvable arrays are moved out together,
+        // in order of their indices
+        let array_methods = self
+            .arrays
+            .iter()
+            .filter(|array| array.inherited.is_resolvable())
+            .filter_map(|array| {
+                // arrays are validated ahead of time
+                let elements = array
+                    .range
+                    .clone()
+                    .map(|i| array.element_ident(i))
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()?;
+                let element_tys = elements.iter().map(|ident| {
+                    Ident::new(
+                        &inflector::cases::pascalcase::to_pascal_case(&ident.to_string()),
+                        Span::call_site(),
+                    )
+                });
+                let method_ident = format_ident!("into_{}", array.name());
+                let doc = format!(
+                    "Move the tokens of the fields of `{}` out of the parts, in order of their indices. The tokens of other fields are dropped.",
+                    array.inherited.ident
+                );
+
+                Some(quote_spanned! { span =>
+                    #[doc = #doc]
+                    pub fn #method_ident(self) -> (#(#element_tys,)*) {
+                        (#(self.#elements,)*)
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let array_tokens = (!array_methods.is_empty()).then(|| {
+            quote_spanned! { span =>
+                impl<#(#resolvable_field_tys,)*> Parts<#(#resolvable_field_tys,)*>
+                where
+                    #(
+                        #resolvable_field_tys: #resolvable_field_idents::State,
+                    )*
+                {
+                    #(#array_methods)*
+                }
+            }
+        });
+
         Some(quote_spanned! { span =>
-            /// This type facilitates the static construction
-            /// of a valid register state.
-            pub struct StateBuilder<#(#resolvable_field_tys,)*> {
+            /// The field tokens of a register, moved out with
+            /// [`Register::split`] and rejoined with [`Register::from_parts`].
+            pub struct Parts<#(#resolvable_field_tys,)*> {
                 #(
-                    pub(crate) #resolvable_field_idents: core::marker::PhantomData<#resolvable_field_tys>,
+                    pub #resolvable_field_idents: #resolvable_field_tys,
                 )*
             }
 
-            impl<#(#resolvable_field_tys,)*> StateBuilder<#(#resolvable_field_tys,)*>
+            impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
             where
                 #(
                     #resolvable_field_tys: #resolvable_field_idents::State,
                 )*
             {
-                /// For internal use.
-                unsafe fn conjure() -> Self {
-                    Self {
+                /// Move all field tokens out of the register.
+                pub fn split(self) -> Parts<#(#resolvable_field_tys,)*> {
+                    Parts {
                         #(
-                            #resolvable_field_idents: core::marker::PhantomData,
+                            #resolvable_field_idents: self.#resolvable_field_idents,
                         )*
                     }
                 }
 
-                /// Complete the state transition and incorporarate
-                /// it into the register.
-                pub fn finish(self) -> Register<#(#resolvable_field_tys,)*>
+                /// Reconstruct the register from field tokens, so long
+                /// as the states of the fields are entitled to each other.
+                pub fn from_parts(parts: Parts<#(#resolvable_field_tys,)*>) -> Self
                 where
-                    Self: ::proto_hal::macro_utils::AsRegister,
+                    #(
+                        #resolvable_field_tys: #resolvable_field_idents::State #entitlement_bounds,
+                    )*
                 {
-                    #[allow(unused_parens)]
-                    let reg_value = #(
-                        ((#writable_resolvable_field_tys::RAW as u32) << #writable_resolvable_field_idents::OFFSET)
-                    )|*;
-
-                    // SAFETY: assumes the proc macro implementation is sound
-                    // and that the peripheral description is accurate
-                    unsafe {
-                        core::ptr::write_volatile((super::BASE_ADDR + OFFSET) as *mut u32, reg_value);
-                    }
-
-                    // SAFETY:
-                    // 1. `self` is destroyed
-                    // 2. state has been written
-                    Register {
+                    Self {
                         #(
-                            #resolvable_field_idents: unsafe { #resolvable_field_tys::conjure() },
+                            #resolvable_field_idents: parts.#resolvable_field_idents,
                         )*
-
                         #(
-                            #unresolvable_field_idents: (), // placeholder
+                            #unresolvable_field_idents: (),
                         )*
+
+                        _context: ::core::marker::PhantomData,
                     }
                 }
             }
+
+            impl<#(#resolvable_field_tys,)*> Parts<#(#resolvable_field_tys,)*>
+            where
+                #(
+                    #resolvable_field_tys: #resolvable_field_idents::State #entitlement_bounds,
+                )*
+            {
+                /// Rejoin the field tokens into the register.
+                pub fn join(self) -> Register<#(#resolvable_field_tys,)*> {
+                    Register::from_parts(self)
+                }
+            }
+
+            #array_tokens
         })
     }
 
-    fn generate_register_impls(&self) -> TokenStream2 {
+    /// Expose the states held by the register (and its parts)
+    /// as evidence of those states, so entitlements to the
+    /// register can be narrowed into entitlements to its states.
+    fn maybe_generate_evidence(&self) -> Option<TokenStream2> {
+        if !self.is_resolvable() {
+            return None;
+        };
+
         let span = self.args.span();
 
         let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
@@ -1068,117 +5179,29 @@ impl Register {
 
         let mut body = TokenStream2::new();
 
-        if self.is_resolvable() {
-            // let new_resolvable_field_tys = self
-            //     .fields()
-            //     .resolvable()
-            //     .tys()
-            //     .map(|ty| format_ident!("New{ty}"))
-            //     .collect::<Vec<_>>();
-            // let new_entitlement_bounds = self
-            //     .fields()
-            //     .resolvable()
-            //     .map(|field| {
-            //         if field.schema.entitlement_fields.is_empty() {
-            //             return None;
-            //         }
-
-            //         let entitled_field_tys = field
-            //             .schema
-            //             .entitlement_fields
-            //             .iter()
-            //             .map(|ident| {
-            //                 format_ident!(
-            //                     "New{}",
-            //                     &inflector::cases::pascalcase::to_pascal_case(&ident.to_string(),),
-            //                 )
-            //             })
-            //             .collect::<Vec<_>>();
-
-            //         Some(quote_spanned! { span =>
-            //             + #(::proto_hal::stasis::Entitled<#entitled_field_tys>)+*
-            //         })
-            //     })
-            //     .collect::<Vec<_>>();
-
-            body.extend(quote_spanned! { span =>
-                impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
-                where
-                    #(
-                        #resolvable_field_tys: #resolvable_field_idents::State,
-                    )*
-                {
-                    // TODO: this is broken for now
-                    // /// Perform a state transition inferred from context.
-                    // pub fn transition<#(#new_resolvable_field_tys,)*>(self) -> Register<#(#new_resolvable_field_tys,)*>
-                    // where
-                    //     #(
-                    //         #new_resolvable_field_tys: #resolvable_field_idents::State #new_entitlement_bounds,
-                    //     )*
-                    // {
-                    //     // SAFETY: `self` is destroyed
-                    //     unsafe { StateBuilder::conjure() }.finish()
-                    // }
-
-                    /// Create a state builder for this register to perform
-                    /// a state transition.
-                    pub fn build_state(self) -> StateBuilder<#(#resolvable_field_tys,)*> {
-                        // SAFETY: `self` is destroyed
-                        unsafe { StateBuilder::conjure() }
-                    }
-                }
-            });
-        }
-
-        if self
-            .fields()
-            .unresolvable()
-            .any(|field| field.access.is_read())
-        {
+        for holder in [format_ident!("Register"), format_ident!("Parts")] {
             body.extend(quote_spanned! { span =>
-                impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+                impl<#(#resolvable_field_tys,)*> ::proto_hal::stasis::Freeze for #holder<#(#resolvable_field_tys,)*>
                 where
                     #(
                         #resolvable_field_tys: #resolvable_field_idents::State,
                     )*
-                {
-                    pub fn read(&self) -> Reader {
-                        // SAFETY: assumes the proc macro implementation is sound
-                        // and that the peripheral description is accurate
-                        unsafe { read() }.into()
-                    }
-                }
+                {}
             });
-        }
 
-        if self
-            .fields()
-            .unresolvable()
-            .any(|field| field.access.is_write())
-        {
-            body.extend(quote_spanned! { span =>
-                    impl<#(#resolvable_field_tys,)*> Register<#(#resolvable_field_tys,)*>
+            for (held_ident, held_ty) in resolvable_field_idents.iter().zip(&resolvable_field_tys) {
+                body.extend(quote_spanned! { span =>
+                    unsafe impl<#(#resolvable_field_tys,)*> ::proto_hal::stasis::Holds<#held_ty, #held_ident::Held> for #holder<#(#resolvable_field_tys,)*>
                     where
                         #(
                             #resolvable_field_tys: #resolvable_field_idents::State,
                         )*
-                    {
-                        pub fn write(&self, f: impl FnOnce(&mut Writer) -> &mut Writer) {
-                            let mut writer = Writer::new();
-
-                            f(&mut writer);
-
-                            // SAFETY: assumes the proc macro implementation is sound
-                            // and that the peripheral description is accurate
-                            unsafe {
-                                core::ptr::write_volatile((super::BASE_ADDR + OFFSET) as *mut u32, writer.value);
-                            }
-                        }
-                    }
+                    {}
                 });
+            }
         }
 
-        body
+        Some(body)
     }
 
     fn maybe_generate_conversion_trait_impls(&self) -> Option<TokenStream2> {
@@ -1191,35 +5214,8 @@ impl Register {
         let resolvable_field_idents = self.fields().resolvable().idents().collect::<Vec<_>>();
         let resolvable_field_tys = self.fields().resolvable().tys().collect::<Vec<_>>();
 
-        let entitlement_bounds = self
-            .fields()
-            .resolvable()
-            .map(|field| {
-                let schema = match &field.access {
-                    Access::Read(read) | Access::ReadWrite { read, write: _ } => &read.schema,
-                    _ => panic!("a resolvable field should not be write-only"),
-                };
-
-                if schema.entitlement_fields.is_empty() {
-                    return None;
-                }
-
-                let entitled_field_tys = schema
-                    .entitlement_fields
-                    .iter()
-                    .map(|ident| {
-                        Ident::new(
-                            &inflector::cases::pascalcase::to_pascal_case(&ident.to_string()),
-                            Span::call_site(),
-                        )
-                    })
-                    .collect::<Vec<_>>();
-
-                Some(quote_spanned! { span =>
-                    + #(::proto_hal::stasis::Entitled<#entitled_field_tys>)+*
-                })
-            })
-            .collect::<Vec<_>>();
+        let entitlement_bounds = self.generate_entitlement_bounds();
+        let view_bounds = self.generate_view_bounds(|field: &Field| self.state_ty(field));
 
         Some(quote_spanned! { span =>
             impl<#(#resolvable_field_tys,)*> ::proto_hal::macro_utils::AsBuilder for Register<#(#resolvable_field_tys,)*>
@@ -1236,6 +5232,9 @@ impl Register {
                 #(
                     #resolvable_field_tys: #resolvable_field_idents::State #entitlement_bounds,
                 )*
+                #(
+                    #view_bounds,
+                )*
             {
                 type Register = Register<#(#resolvable_field_tys,)*>;
             }
@@ -1258,6 +5257,9 @@ impl Register {
                 #(
                     #resolvable_field_tys: #resolvable_field_idents::State,
                 )*
+                #(
+                    #view_bounds,
+                )*
                 Self: ::proto_hal::macro_utils::AsRegister,
             {
                 fn into(self) -> Register<#(#resolvable_field_tys,)*> {
@@ -1312,9 +5314,13 @@ impl Register {
                 for variant in variants {
                     if variant.entitlement_fields.is_empty() {
                         let state_ty = &variant.ident;
+                        let ty_generics = variant.generics().1;
+                        let const_param = ty_generics
+                            .is_some()
+                            .then(|| quote_spanned! { span => const V: u32, });
 
                         body.extend(quote_spanned! { span =>
-                            unsafe impl<T> ::proto_hal::stasis::Entitled<T> for #ident::#state_ty {}
+                            unsafe impl<#const_param T> ::proto_hal::stasis::Entitled<T> for #ident::#state_ty #ty_generics {}
                         });
                     }
                 }
@@ -1359,7 +5365,9 @@ impl Register {
                             }
                         });
 
-                    for (ty, accessor) in variant_tys.iter().zip(variant_accessor_idents) {
+                    for (variant, accessor) in variants.iter().zip(variant_accessor_idents) {
+                        let ty = &variant.ident;
+                        let (impl_generics, ty_generics) = variant.generics();
                         let doc = format!("Set the state of the field to [`{ty}`]({ident}::{ty}).");
 
                         body.extend(quote_spanned! { span =>
@@ -1370,9 +5378,9 @@ impl Register {
                                     )*
                                 {
                                     #[doc = #doc]
-                                    pub fn #accessor(self) -> StateBuilder<#(#prev_field_tys,)* #ident::#ty, #(#next_field_tys,)*>
+                                    pub fn #accessor #impl_generics(self) -> StateBuilder<#(#prev_field_tys,)* #ident::#ty #ty_generics, #(#next_field_tys,)*>
                                     where
-                                        #ident::#ty: #ident::State,
+                                        #ident::#ty #ty_generics: #ident::State,
                                     {
                                         self.generic()
                                     }
@@ -1385,30 +5393,189 @@ impl Register {
 
         Some(body)
     }
-}
 
-impl ToTokens for Register {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let span = self.args.span();
-        let ident = &self.ident;
+    /// Generate the items of the register module which do not
+    /// depend on the sequences, presets or trigger methods of the
+    /// description, and so must not collide with them.
+    fn generate_fixed_body(&self) -> TokenStream2 {
         let mut body = TokenStream2::new();
 
         body.extend(self.generate_field_bodies());
+        body.extend(self.generate_view_impls());
         body.extend(self.generate_offset_const());
+        body.extend(self.generate_raw_ty());
+        body.extend(self.generate_assertions());
+        body.extend(self.generate_raw_accessors());
+        body.extend(self.generate_effect_masks());
         body.extend(self.maybe_generate_refined_writers());
         body.extend(self.maybe_generate_reader());
         body.extend(self.maybe_generate_writer());
+        body.extend(self.maybe_generate_unit_accessors());
+        body.extend(self.maybe_generate_array_writers());
+        body.extend(self.maybe_generate_array_transitions());
         body.extend(self.maybe_generate_unsafe_reader());
         body.extend(self.maybe_generate_unsafe_writer());
         body.extend(self.generate_unsafe_interface());
+        body.extend(self.maybe_generate_flag_interface());
         body.extend(self.generate_register_struct());
+        body.extend(self.maybe_generate_local_reachability());
+        body.extend(self.generate_depth());
+        body.extend(self.maybe_generate_status());
         body.extend(self.maybe_generate_reset_alias());
+        body.extend(self.maybe_generate_bank_alias());
         body.extend(self.maybe_generate_state_builder());
         body.extend(self.generate_register_impls());
+        body.extend(self.maybe_generate_clear_methods());
+        body.extend(self.maybe_generate_reset_write());
+        body.extend(self.maybe_generate_release_write());
+        body.extend(self.maybe_generate_dyn_state());
+        body.extend(self.maybe_generate_sized_writes());
+        body.extend(self.maybe_generate_stream());
         body.extend(self.maybe_generate_conversion_trait_impls());
+        body.extend(self.maybe_generate_parts());
+        body.extend(self.maybe_generate_evidence());
         body.extend(self.maybe_generate_builder_methods());
 
+        body
+    }
+
+    /// The names of the items of the register module and of the
+    /// methods of the register which are generated regardless of
+    /// its sequences, presets and trigger methods.
+    fn generated_idents(&self) -> (Vec<Ident>, Vec<Ident>) {
+        // validated
+        let file = syn::parse2::<syn::File>(self.generate_fixed_body()).unwrap();
+
+        let mut items = Vec::new();
+        let mut methods = Vec::new();
+
+        for item in file.items {
+            match item {
+                syn::Item::Const(item) => items.push(item.ident),
+                syn::Item::Enum(item) => items.push(item.ident),
+                syn::Item::Fn(item) => items.push(item.sig.ident),
+                syn::Item::Mod(item) => items.push(item.ident),
+                syn::Item::Static(item) => items.push(item.ident),
+                syn::Item::Struct(item) => items.push(item.ident),
+                syn::Item::Trait(item) => items.push(item.ident),
+                syn::Item::Type(item) => items.push(item.ident),
+                syn::Item::Union(item) => items.push(item.ident),
+                syn::Item::Impl(item) => {
+                    let syn::Type::Path(ty) = &*item.self_ty else {
+                        continue;
+                    };
+
+                    if ty
+                        .path
+                        .segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "Register")
+                    {
+                        methods.extend(item.items.into_iter().filter_map(|item| match item {
+                            syn::ImplItem::Fn(item) => Some(item.sig.ident),
+                            _ => None,
+                        }));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        (items, methods)
+    }
+
+    /// Validate the methods and items generated from the description
+    /// (i.e. sequences, presets and trigger methods) do not collide
+    /// with those generated regardless of it.
+    fn validate_generated_idents(&self) -> syn::Result<()> {
+        let (items, methods) = self.generated_idents();
+        let mut errors = SynErrorCombinator::new();
+
+        let mut validate = |ident: &Ident, name: String, reserved: &[Ident], kind| {
+            if reserved.iter().any(|other| *other == name) {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    format!("`{name}` collides with {kind} of the register"),
+                ));
+            }
+        };
+
+        for ident in self
+            .args
+            .sequences
+            .elems
+            .iter()
+            .map(|(ident, _)| ident)
+            .chain(self.args.presets.elems.iter().map(|(ident, _)| ident))
+        {
+            validate(ident, ident.to_string(), &methods, "a method");
+            validate(
+                ident,
+                inflector::cases::pascalcase::to_pascal_case(&ident.to_string()),
+                &items,
+                "an item",
+            );
+        }
+
+        for (ident, _) in &self.args.presets.elems {
+            validate(
+                ident,
+                inflector::cases::screamingsnakecase::to_screaming_snake_case(&ident.to_string()),
+                &items,
+                "an item",
+            );
+        }
+
+        for variant in self
+            .fields()
+            .filter(|field| field.access.write_effect() == Some(WriteEffect::Trigger))
+            .filter_map(
+                |field| match field.write_schema().map(|schema| &schema.numericity) {
+                    Some(Numericity::Enumerated { variants }) => Some(variants),
+                    _ => None,
+                },
+            )
+            .flatten()
+            .filter(|variant| variant.range.is_none())
+        {
+            validate(
+                &variant.ident,
+                inflector::cases::snakecase::to_snake_case(&variant.ident.to_string()),
+                &methods,
+                "a method",
+            );
+        }
+
+        errors.coalesce()
+    }
+}
+
+impl ToTokens for Register {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = self.args.span();
+        let ident = &self.ident;
+        let mut body = self.generate_fixed_body();
+
+        body.extend(self.maybe_generate_trigger_methods());
+        body.extend(self.maybe_generate_sequences());
+        body.extend(self.maybe_generate_presets());
+
+        // fields of the register are free to use deprecated items
+        let allow_deprecated = self
+            .fields
+            .iter()
+            .any(|field| {
+                field.args.deprecated.is_some()
+                    || field.variant_enums().iter().any(|(_, variants)| {
+                        variants
+                            .iter()
+                            .any(|variant| variant.args.deprecated.is_some())
+                    })
+            })
+            .then(|| quote_spanned! { span => #[allow(deprecated)] });
+
         tokens.extend(quote_spanned! { span =>
+            #allow_deprecated
             pub mod #ident {
                 #body
             }
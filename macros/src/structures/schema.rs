@@ -8,8 +8,7 @@ use super::{
     variant_array::{VariantArray, VariantArrayArgs},
     Args,
 };
-use crate::utils::{require_struct, Spanned, SynErrorCombinator, Width};
-use tiva::Validator;
+use crate::utils::{docs_of, require_struct, Spanned, SynErrorCombinator, Validator, Width};
 
 #[derive(Debug, Clone, Default, FromMeta)]
 pub struct SchemaArgs {
@@ -89,10 +88,14 @@ impl SchemaSpec {
             errors.try_maybe_then(get_args(), |arg_collection| {
                 let entitlements = match arg_collection {
                     (Some(state_args), None) => {
-                        let state =
-                            Variant::parse(s.ident.clone(), state_bits, state_args.clone())?;
+                        let state = Variant::parse(
+                            s.ident.clone(),
+                            docs_of(&s.attrs),
+                            state_bits,
+                            state_args.clone(),
+                        )?;
 
-                        state_bits = state.bits + 1;
+                        state_bits = state.last_bits() + 1;
                         variants.push(state);
 
                         Ok(state_args.entitlements.elems.clone())
@@ -100,6 +103,7 @@ impl SchemaSpec {
                     (None, Some(state_array_args)) => {
                         let state_array = VariantArray::parse(
                             s.ident.clone(),
+                            docs_of(&s.attrs),
                             state_bits,
                             state_array_args.clone(),
                         )?;
@@ -172,13 +176,45 @@ impl Validator<SchemaSpec> for Schema {
                     errors.push(syn::Error::new(variant.args.span(), "state bit value `bits` must be specified. to infer the bit value, use `auto_increment`"));
                 }
 
-                if variant.bits >> spec.width != 0 {
+                if u64::from(variant.last_bits()) >> spec.width != 0 {
                     errors.push(syn::Error::new(
                         variant.args.span(),
                         "state bit value does not fit within field width",
                     ));
                 }
             }
+
+            // variants (including those declared by variant arrays)
+            // must not collide, and range variants must not overlap
+            // other variants
+            for (i, variant) in variants.iter().enumerate() {
+                for other in &variants[i + 1..] {
+                    if other.ident == variant.ident {
+                        errors.push(syn::Error::new(
+                            other.args.span(),
+                            format!("variant `{}` is declared more than once", other.ident),
+                        ));
+                    } else if variant.range.is_none()
+                        && other.range.is_none()
+                        && variant.bits == other.bits
+                    {
+                        errors.push(syn::Error::new(
+                            other.args.span(),
+                            format!(
+                                "variant `{}` has the same bit value as `{}`",
+                                other.ident, variant.ident
+                            ),
+                        ));
+                    } else if (variant.range.is_some() || other.range.is_some())
+                        && (variant.spans(other.bits) || other.spans(variant.bits))
+                    {
+                        errors.push(syn::Error::new(
+                            other.args.span(),
+                            format!("variant overlaps `{}`", variant.ident),
+                        ));
+                    }
+                }
+            }
         }
 
         errors.coalesce()?;
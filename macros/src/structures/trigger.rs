@@ -0,0 +1,129 @@
+use darling::FromMeta;
+use quote::{quote_spanned, ToTokens};
+use syn::{Ident, ItemMod};
+
+use crate::utils::{extract_items_from, PathArray, Spanned, SynErrorCombinator};
+
+use super::{register::Register, Args};
+
+/// An internal signal emitted by a block (i.e. a timer TRGO),
+/// which other blocks can select as an input with variants
+/// that connect to it.
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default, from_word = || Ok(Self::default()))]
+pub struct TriggerArgs {
+    /// The states (`register::field::Variant`) in which the
+    /// trigger is emitted. If empty, the trigger is always emitted.
+    pub sources: PathArray,
+}
+
+impl Args for TriggerArgs {
+    const NAME: &str = "trigger";
+}
+
+#[derive(Debug)]
+pub struct Trigger {
+    pub args: Spanned<TriggerArgs>,
+    pub ident: Ident,
+}
+
+impl Trigger {
+    pub fn parse(module: &ItemMod, args: Spanned<TriggerArgs>) -> syn::Result<Self> {
+        if !extract_items_from(module)?.is_empty() {
+            Err(syn::Error::new_spanned(
+                module,
+                "triggers cannot contain items",
+            ))?
+        }
+
+        Ok(Self {
+            args,
+            ident: module.ident.clone(),
+        })
+    }
+
+    /// Validate the sources of this trigger refer to
+    /// states of fields within the provided registers.
+    pub fn validate(&self, registers: &[Register]) -> syn::Result<()> {
+        let mut errors = SynErrorCombinator::new();
+
+        for source in &self.args.sources.elems {
+            let [register, field, variant] = source.segments.iter().collect::<Vec<_>>()[..] else {
+                errors.push(syn::Error::new_spanned(
+                    source,
+                    "expected a path of the form `register::field::Variant`",
+                ));
+
+                continue;
+            };
+
+            let Some(register) = registers
+                .iter()
+                .find(|candidate| candidate.ident == register.ident)
+            else {
+                errors.push(syn::Error::new_spanned(register, "register does not exist"));
+
+                continue;
+            };
+
+            let Some(field) = register
+                .fields
+                .iter()
+                .find(|candidate| candidate.ident == field.ident)
+            else {
+                errors.push(syn::Error::new_spanned(field, "field does not exist"));
+
+                continue;
+            };
+
+            let Some(states) = field.states() else {
+                errors.push(syn::Error::new_spanned(
+                    source,
+                    "trigger sources must be states of resolvable fields",
+                ));
+
+                continue;
+            };
+
+            if !states
+                .iter()
+                .any(|candidate| candidate.ident == variant.ident)
+            {
+                errors.push(syn::Error::new_spanned(variant, "variant does not exist"));
+            }
+        }
+
+        errors.coalesce()
+    }
+}
+
+impl ToTokens for Trigger {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let span = self.args.span();
+        let ident = &self.ident;
+
+        let emits = if self.args.sources.elems.is_empty() {
+            quote_spanned! { span =>
+                // the trigger is always emitted
+                unsafe impl ::proto_hal::stasis::Emits<Trigger> for Trigger {}
+            }
+        } else {
+            let sources = self.args.sources.elems.iter();
+
+            quote_spanned! { span =>
+                #(
+                    unsafe impl ::proto_hal::stasis::Emits<Trigger> for super::#sources {}
+                )*
+            }
+        };
+
+        tokens.extend(quote_spanned! { span =>
+            pub mod #ident {
+                /// The type representing this trigger.
+                pub struct Trigger;
+
+                #emits
+            }
+        });
+    }
+}
@@ -1,11 +1,11 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, ops::Range};
 
 use darling::FromMeta;
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote_spanned, ToTokens};
-use syn::{Ident, Path};
+use syn::{ExprRange, Ident, Path};
 
-use crate::utils::{PathArray, Spanned, SynErrorCombinator};
+use crate::utils::{parse_expr_range, sorted_paths, PathArray, Spanned, SynErrorCombinator};
 
 use super::Args;
 
@@ -14,7 +14,21 @@ use super::Args;
 pub struct VariantArgs {
     #[darling(default)]
     pub bits: Option<u32>,
+    /// The values this variant spans (i.e. `1..=7`). Each value
+    /// is a distinct state, carried by the state type as a const
+    /// generic (i.e. `Divide<3>`).
+    pub range: Option<ExprRange>,
     pub entitlements: PathArray,
+    pub clock: Option<ClockArgs>,
+    /// The trigger (relative to the register) this variant
+    /// selects as an input (i.e. `super::super::tim1::trgo`).
+    pub connects: Option<Path>,
+    /// The hardware enters this state on its own (i.e. a
+    /// ready flag being set or a start bit clearing), so
+    /// the field is unresolvable and can be polled.
+    pub hardware: bool,
+    /// The variant is deprecated, with a note on migration.
+    pub deprecated: Option<String>,
 
     #[darling(skip)]
     pub span: Option<Span>,
@@ -24,20 +38,72 @@ impl Args for VariantArgs {
     const NAME: &str = "variant";
 }
 
+/// The frequency relationship of a clock tree state.
+///
+/// A state either produces a fixed frequency (`hz`) or
+/// scales its input frequency (`mul` and/or `div`).
+#[derive(Debug, Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct ClockArgs {
+    pub hz: Option<u32>,
+    pub mul: Option<u32>,
+    pub div: Option<u32>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Variant {
     pub args: Spanned<VariantArgs>,
     pub ident: Ident,
+    /// The doc comments of this variant as described.
+    pub docs: Vec<String>,
     pub bits: u32,
+    /// The values this variant spans (if it is a range variant).
+    pub range: Option<Range<u32>>,
     pub entitlements: HashSet<Path>,
     pub entitlement_fields: HashSet<Ident>,
 }
 
 impl Variant {
-    pub fn parse(ident: Ident, bits: u32, args: Spanned<VariantArgs>) -> syn::Result<Self> {
+    pub fn parse(
+        ident: Ident,
+        docs: Vec<String>,
+        bits: u32,
+        args: Spanned<VariantArgs>,
+    ) -> syn::Result<Self> {
         let mut errors = SynErrorCombinator::new();
 
-        let bits = args.bits.unwrap_or(bits);
+        let range = match &args.range {
+            Some(range) => {
+                let range = parse_expr_range(range)?;
+
+                if range.is_empty() {
+                    errors.push(syn::Error::new(args.span(), "variant range is empty"));
+                }
+
+                if args.bits.is_some() {
+                    errors.push(syn::Error::new(
+                        args.span(),
+                        "range variants cannot specify `bits`",
+                    ));
+                }
+
+                if args.clock.is_some() {
+                    errors.push(syn::Error::new(
+                        args.span(),
+                        "range variants cannot be clock states",
+                    ));
+                }
+
+                Some(range)
+            }
+            None => None,
+        };
+
+        let bits = range
+            .as_ref()
+            .map(|range| range.start)
+            .or(args.bits)
+            .unwrap_or(bits);
         let mut entitlements = HashSet::new();
         let mut entitlement_fields = HashSet::new();
 
@@ -60,22 +126,85 @@ impl Variant {
             }
         }
 
+        if let Some(clock) = &args.clock {
+            match (clock.hz, clock.mul, clock.div) {
+                (Some(_), None, None) => {}
+                (Some(_), _, _) => errors.push(syn::Error::new(
+                    args.span(),
+                    "clock states produce a fixed frequency or scale their input, not both",
+                )),
+                (None, None, None) => errors.push(syn::Error::new(
+                    args.span(),
+                    "clock states must specify `hz`, `mul`, or `div`",
+                )),
+                (None, mul, div) => {
+                    if mul == Some(0) || div == Some(0) {
+                        errors.push(syn::Error::new(
+                            args.span(),
+                            "clock scale factors must be non-zero",
+                        ));
+                    }
+                }
+            }
+        }
+
         errors.coalesce()?;
 
         Ok(Self {
             args,
             ident,
+            docs,
             bits,
+            range,
             entitlements,
             entitlement_fields,
         })
     }
+
+    /// The last value this variant spans.
+    pub fn last_bits(&self) -> u32 {
+        self.range.as_ref().map_or(self.bits, |range| range.end - 1)
+    }
+
+    /// Whether this variant spans the value `bits`.
+    pub fn spans(&self, bits: u32) -> bool {
+        (self.bits..=self.last_bits()).contains(&bits)
+    }
+
+    /// The generic parameters of the state type of this variant
+    /// (if it is a range variant), to be declared and to be used.
+    pub fn generics(&self) -> (Option<TokenStream2>, Option<TokenStream2>) {
+        if self.range.is_none() {
+            return (None, None);
+        }
+
+        let span = self.args.span();
+
+        (
+            Some(quote_spanned! { span => <const V: u32> }),
+            Some(quote_spanned! { span => <V> }),
+        )
+    }
+
+    /// Require this variant to be a single value, as
+    /// the provided `path` cannot name a range variant.
+    pub fn require_discrete(&self, path: &Path) -> syn::Result<()> {
+        if self.range.is_some() {
+            Err(syn::Error::new_spanned(
+                path,
+                "range variants are not supported here",
+            ))?
+        }
+
+        Ok(())
+    }
 }
 
 impl PartialEq for Variant {
     fn eq(&self, other: &Self) -> bool {
         self.ident == other.ident
             && self.bits == other.bits
+            && self.range == other.range
             && self.entitlements == other.entitlements
     }
 }
@@ -85,35 +214,92 @@ impl PartialEq for Variant {
 impl ToTokens for Variant {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let ident = &self.ident;
+        let docs = &self.docs;
 
         let span = self.args.span();
 
+        let deprecation = self.args.deprecated.as_ref().map(|note| {
+            quote_spanned! { span =>
+                #[deprecated(note = #note)]
+            }
+        });
+
+        let (impl_generics, ty_generics) = self.generics();
+
+        let raw = match &self.range {
+            Some(range) => {
+                let (start, end) = (range.start, range.end);
+
+                quote_spanned! { span =>
+                    {
+                        assert!(V >= #start && V < #end, "value exceeds the range of the variant");
+
+                        ReadVariant::#ident(V)
+                    }
+                }
+            }
+            None => quote_spanned! { span => ReadVariant::#ident },
+        };
+
         tokens.extend(quote_spanned! { span =>
-            pub struct #ident {
-                _sealed: (),
+            #(
+                #[doc = #docs]
+            )*
+            #deprecation
+            pub struct #ident #impl_generics {
+                _sealed: ::core::marker::PhantomData<super::Context>,
             }
 
-            impl ::proto_hal::stasis::Freeze for #ident {}
+            impl #impl_generics ::proto_hal::stasis::Freeze for #ident #ty_generics {
+                const DEPTH: usize = super::DEPTH;
+                const REACHABLE: bool = REACHABLE && <Self as super::Reachable>::REACHABLE;
+            }
 
-            impl State for #ident {
-                const RAW: ReadVariant = ReadVariant::#ident;
+            impl #impl_generics State for #ident #ty_generics {
+                const RAW: ReadVariant = #raw;
 
                 unsafe fn conjure() -> Self {
                     Self {
-                        _sealed: (),
+                        _sealed: ::core::marker::PhantomData,
                     }
                 }
             }
         });
 
         if !self.entitlements.is_empty() {
-            let entitlement_paths = self.entitlements.iter();
+            let entitlement_paths = sorted_paths(&self.entitlements);
 
             tokens.extend(quote_spanned! { span =>
                 #(
-                    unsafe impl ::proto_hal::stasis::Entitled<super::#entitlement_paths> for #ident {}
+                    unsafe impl #impl_generics ::proto_hal::stasis::Entitled<super::#entitlement_paths> for #ident #ty_generics {}
                 )*
             });
         }
+
+        if let Some(trigger) = &self.args.connects {
+            tokens.extend(quote_spanned! { span =>
+                unsafe impl #impl_generics ::proto_hal::stasis::Connects<super::#trigger::Trigger> for #ident #ty_generics {}
+            });
+        }
+
+        if let Some(clock) = &self.args.clock {
+            if let Some(hz) = clock.hz {
+                tokens.extend(quote_spanned! { span =>
+                    impl ::proto_hal::clock::Source for #ident {
+                        const HZ: u32 = #hz;
+                    }
+                });
+            } else {
+                let mul = clock.mul.unwrap_or(1);
+                let div = clock.div.unwrap_or(1);
+
+                tokens.extend(quote_spanned! { span =>
+                    impl ::proto_hal::clock::Scale for #ident {
+                        const MUL: u32 = #mul;
+                        const DIV: u32 = #div;
+                    }
+                });
+            }
+        }
     }
 }
@@ -35,6 +35,10 @@ pub struct VariantArrayArgs {
     pub range: ExprRange,
     #[darling(default)]
     pub step: Step,
+    /// Only the powers of two within the range are declared
+    /// (i.e. `DivX` with `range = 1..=512` for `Div1`..`Div512`).
+    #[darling(default)]
+    pub powers_of_two: bool,
 
     #[darling(flatten)]
     pub state: VariantArgs,
@@ -48,6 +52,8 @@ impl Args for VariantArrayArgs {
 pub struct VariantArray {
     pub args: Spanned<VariantArrayArgs>,
     pub ident: Ident,
+    /// The doc comments shared by all variants of this array.
+    pub docs: Vec<String>,
     pub range: Range<u32>,
     pub step: Step,
     pub bits: u32,
@@ -56,7 +62,12 @@ pub struct VariantArray {
 }
 
 impl VariantArray {
-    pub fn parse(ident: Ident, bits: u32, args: Spanned<VariantArrayArgs>) -> syn::Result<Self> {
+    pub fn parse(
+        ident: Ident,
+        docs: Vec<String>,
+        bits: u32,
+        args: Spanned<VariantArrayArgs>,
+    ) -> syn::Result<Self> {
         let mut errors = SynErrorCombinator::new();
 
         let bits = args.state.bits.unwrap_or(bits);
@@ -82,6 +93,20 @@ impl VariantArray {
             }
         }
 
+        if args.powers_of_two && *args.step != 1 {
+            errors.push(syn::Error::new(
+                args.span(),
+                "the step of variant arrays of powers of two is implied",
+            ));
+        }
+
+        if args.state.clock.is_some() {
+            errors.push(syn::Error::new(
+                args.span(),
+                "clock relationships cannot be specified for variant arrays",
+            ));
+        }
+
         errors.coalesce()?;
 
         // TODO: outside of error combinator but whatever
@@ -91,6 +116,7 @@ impl VariantArray {
         Ok(Self {
             args,
             ident,
+            docs,
             range,
             step,
             bits,
@@ -101,13 +127,39 @@ impl VariantArray {
 }
 
 impl VariantArray {
+    /// The values named by the variants of this array, in order.
+    pub fn values(&self) -> Vec<u32> {
+        let values = self.range.clone().step_by(*self.step as _);
+
+        if self.args.powers_of_two {
+            values.filter(|value| value.is_power_of_two()).collect()
+        } else {
+            values.collect()
+        }
+    }
+
     pub fn count(&self) -> u32 {
-        self.range.clone().count() as _
+        self.values().len() as _
     }
 
     pub fn to_states(&self) -> syn::Result<Vec<Variant>> {
+        if self.args.state.range.is_some() {
+            Err(syn::Error::new(
+                self.args.span(),
+                "variant arrays cannot be range variants",
+            ))?
+        }
+
+        let values = self.values();
+
+        if values.is_empty() {
+            Err(syn::Error::new(
+                self.args.span(),
+                "variant array declares no variants",
+            ))?
+        }
+
         let mut states = Vec::new();
-        let mut bits = self.bits;
 
         let replace_pos = self.ident.to_string().rfind("X").ok_or(syn::Error::new(
             self.ident.span(),
@@ -115,7 +167,7 @@ impl VariantArray {
         ))?;
 
         // generate states
-        for i in self.range.clone().step_by(*self.step as _) {
+        for (bits, i) in (self.bits..).zip(values) {
             let mut s = self.ident.to_string();
             s.replace_range(replace_pos..replace_pos + 1, &i.to_string());
             let ident = Ident::new(&s, self.ident.span());
@@ -125,12 +177,13 @@ impl VariantArray {
             let state = Variant {
                 args,
                 ident,
+                docs: self.docs.clone(),
                 bits,
+                range: None,
                 entitlements: self.entitlements.clone(),
                 entitlement_fields: self.entitlement_fields.clone(),
             };
 
-            bits += 1;
             states.push(state);
         }
 
@@ -4,10 +4,11 @@ use std::{
 };
 
 use darling::FromMeta;
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote_spanned, ToTokens};
 use syn::{
-    spanned::Spanned as _, Expr, ExprArray, ExprLit, ExprRange, Ident, Item, ItemMod, ItemStruct,
-    Lit, LitInt, Meta, Path, RangeLimits,
+    spanned::Spanned as _, Attribute, Expr, ExprArray, ExprLit, ExprRange, Ident, Item, ItemMod,
+    ItemStruct, Lit, LitInt, Meta, Path, RangeLimits,
 };
 
 use crate::structures::schema::Schema;
@@ -39,12 +40,53 @@ pub fn extract_items_from(module: &ItemMod) -> syn::Result<&Vec<Item>> {
         .1)
 }
 
+/// Emit a compiler warning at `span`.
+///
+/// *Note: Proc macros cannot emit warnings on stable, so the
+/// warning is surfaced as the use of a deprecated item.*
+pub fn warning(span: Span, msg: &str) -> TokenStream2 {
+    quote_spanned! { span =>
+        const _: () = {
+            #[deprecated(note = #msg)]
+            #[allow(non_upper_case_globals)]
+            const warning: () = ();
+
+            warning
+        };
+    }
+}
+
+/// Collect the doc comments of an item, one entry per line.
+pub fn docs_of(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
 pub fn get_schema_from_set(ident: &Ident, set: &HashMap<Ident, Schema>) -> syn::Result<Schema> {
     set.get(ident)
         .cloned()
         .ok_or(syn::Error::new_spanned(ident, "schema does not exist"))
 }
 
+/// Sort paths by their textual representation so
+/// generated code does not depend on hash order.
+pub fn sorted_paths<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Vec<&'a Path> {
+    let mut paths = paths.into_iter().collect::<Vec<_>>();
+    paths.sort_by_cached_key(|path| path.to_token_stream().to_string());
+
+    paths
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct PathArray {
     pub elems: Vec<Path>,
@@ -71,6 +113,15 @@ impl FromMeta for PathArray {
     }
 }
 
+/// Validation checks which may be suppressed
+/// for an item, i.e. `allow(overlap)`.
+#[derive(Debug, Clone, Copy, Default, FromMeta)]
+#[darling(default)]
+pub struct AllowArgs {
+    /// The item may overlap its neighbors (i.e. aliased registers).
+    pub overlap: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct Spanned<T> {
     item: T,
@@ -178,6 +229,23 @@ impl SynErrorCombinator {
     }
 }
 
+/// Types which can be produced by validating a specification
+/// (i.e. a block validated from its parsed description).
+pub trait Validator<S>: Sized {
+    type Error;
+
+    fn validate(spec: S) -> Result<Self, Self::Error>;
+}
+
+/// Validate a specification into any type it can be validated into.
+pub trait Validate: Sized {
+    fn validate<V: Validator<Self>>(self) -> Result<V, V::Error> {
+        V::validate(self)
+    }
+}
+
+impl<T> Validate for T {}
+
 pub fn parse_expr_range(range: &ExprRange) -> syn::Result<Range<u32>> {
     // get range from range expr (so stupid)
     let expr = *(range.start.clone().unwrap_or(Box::new(Expr::Lit(ExprLit {
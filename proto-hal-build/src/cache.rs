@@ -0,0 +1,14 @@
+use std::{fs, io, path::Path};
+
+/// Write the provided contents to a file, leaving the file untouched
+/// if it already holds the contents.
+///
+/// Preserving the modification time of unchanged outputs prevents
+/// cargo and the linker from redoing work on every build.
+pub(crate) fn write_if_changed(path: &Path, contents: &str) -> io::Result<()> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == contents) {
+        return Ok(());
+    }
+
+    fs::write(path, contents)
+}
@@ -0,0 +1,121 @@
+//! Diagnostics surfaced by build scripts.
+
+use std::fmt::{self, Display};
+
+/// The rank of a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rank {
+    Error,
+    Warning,
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A diagnostic produced while processing a device description.
+///
+/// Diagnostics render as plain text (with no terminal styling)
+/// so they read the same in logs as they do in a terminal.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub rank: Rank,
+    pub message: String,
+    /// The path of the offending item (i.e. `interrupts::USART1`).
+    pub context: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            rank: Rank::Error,
+            message: message.into(),
+            context: Vec::new(),
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            rank: Rank::Warning,
+            message: message.into(),
+            context: Vec::new(),
+        }
+    }
+
+    /// Append a segment to the context path of the diagnostic.
+    pub fn with_context(mut self, segment: impl Into<String>) -> Self {
+        self.context.push(segment.into());
+        self
+    }
+
+    /// Render the diagnostic as a single line, prefixed by
+    /// the context path if present.
+    fn line(&self) -> String {
+        // cargo instructions are terminated by newlines
+        let message = self.message.replace('\n', " ");
+
+        if self.context.is_empty() {
+            message
+        } else {
+            format!("{}: {message}", self.context.join("::"))
+        }
+    }
+
+    /// The `cargo::warning` instruction surfacing this diagnostic.
+    pub fn to_compile_warning(&self) -> String {
+        format!("cargo::warning={}", self.line())
+    }
+
+    /// The `cargo::error` instruction surfacing this diagnostic.
+    ///
+    /// *Note: Cargo fails the build once the build script exits
+    /// if any errors were emitted.*
+    pub fn to_compile_error(&self) -> String {
+        format!("cargo::error={}", self.line())
+    }
+
+    /// Print the instruction corresponding to the rank of
+    /// this diagnostic for cargo to surface.
+    pub fn emit(&self) {
+        match self.rank {
+            Rank::Error => println!("{}", self.to_compile_error()),
+            Rank::Warning => println!("{}", self.to_compile_warning()),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rank, self.message)?;
+
+        if !self.context.is_empty() {
+            write!(f, "\n  --> {}", self.context.join("::"))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Diagnostic;
+
+    #[test]
+    fn instructions() {
+        let diagnostic = Diagnostic::warning("first\nsecond").with_context("interrupts");
+
+        assert_eq!(
+            diagnostic.to_compile_warning(),
+            "cargo::warning=interrupts: first second"
+        );
+        assert_eq!(
+            Diagnostic::error("message").to_compile_error(),
+            "cargo::error=message"
+        );
+    }
+}
@@ -1,21 +1,115 @@
-use std::{
-    env,
-    fs::File,
-    io::{BufWriter, Write},
-    path::PathBuf,
-};
-
-/// Generate the `device.x` linker script
-/// as required by `cortex-m-rt` for
-/// interrupt vector default handlers.
+use std::{env, fmt::Write, path::PathBuf};
+
+use crate::{cache, diagnostic::Diagnostic, target::TargetProfile};
+
+/// Generate the linker script providing interrupt vector
+/// default handlers for the target being built for.
+///
+/// Targets without a [`TargetProfile`] are assumed
+/// to be Cortex-M.
 pub fn build(interrupt_idents: &[&str]) {
+    let profile = TargetProfile::from_env().unwrap_or_else(|| {
+        Diagnostic::warning("unsupported target architecture, assuming Cortex-M").emit();
+
+        TargetProfile::CORTEX_M
+    });
+
+    build_for(&profile, interrupt_idents);
+}
+
+/// Generate the linker script providing interrupt vector
+/// default handlers as required by the runtime crate
+/// of the target (i.e. `device.x` for `cortex-m-rt`).
+///
+/// *Note: The linker script is only rewritten when
+/// the interrupts change.*
+///
+/// Invalid or duplicate interrupt identifiers are
+/// reported as cargo errors and no script is written.
+pub fn build_for(profile: &TargetProfile, interrupt_idents: &[&str]) {
+    let diagnostics = validate(interrupt_idents);
+
+    for diagnostic in &diagnostics {
+        diagnostic.emit();
+    }
+
+    if !diagnostics.is_empty() {
+        return;
+    }
+
     let out = &PathBuf::from(env::var_os("OUT_DIR").unwrap());
 
-    let mut writer = BufWriter::new(File::create(out.join("device.x")).unwrap());
+    let mut script = String::new();
 
     for vector in interrupt_idents {
-        writeln!(writer, "PROVIDE({} = DefaultHandler);", vector).unwrap();
+        writeln!(script, "PROVIDE({} = {});", vector, profile.default_handler).unwrap();
     }
 
-    println!("cargo:rustc-link-search={}", out.display());
+    cache::write_if_changed(&out.join(profile.script), &script).unwrap();
+
+    println!("cargo::rustc-link-search={}", out.display());
+}
+
+/// Validate the interrupt identifiers are valid symbols
+/// and unique.
+fn validate(interrupt_idents: &[&str]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (i, ident) in interrupt_idents.iter().enumerate() {
+        let valid = ident
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && ident.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        if !valid {
+            diagnostics.push(
+                Diagnostic::error(format!("`{ident}` is not a valid interrupt identifier"))
+                    .with_context("interrupts")
+                    .with_context(ident.to_string()),
+            );
+        }
+
+        if interrupt_idents[..i].contains(ident) {
+            diagnostics.push(
+                Diagnostic::error(format!("interrupt `{ident}` is declared more than once"))
+                    .with_context("interrupts")
+                    .with_context(ident.to_string()),
+            );
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+
+    #[test]
+    fn valid() {
+        assert!(validate(&["USART1", "_reserved", "TIM1_UP_TIM16"]).is_empty());
+    }
+
+    #[test]
+    fn invalid_ident() {
+        let diagnostics = validate(&["1USART", "USART-2", ""]);
+
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            diagnostics[0].to_compile_error(),
+            "cargo::error=interrupts::1USART: `1USART` is not a valid interrupt identifier"
+        );
+    }
+
+    #[test]
+    fn duplicate_ident() {
+        let diagnostics = validate(&["USART1", "USART2", "USART1"]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "interrupt `USART1` is declared more than once"
+        );
+    }
 }
@@ -1 +1,11 @@
+//! Build-time utilities for HALs built with proto-hal.
+//!
+//! *Note: Build script instructions are emitted with the `cargo::`
+//! syntax, and diagnostics with `cargo::error`, which require
+//! Cargo 1.84 or later.*
+
+mod cache;
+pub mod diagnostic;
 pub mod interrupts;
+pub mod memory;
+pub mod target;
@@ -0,0 +1,295 @@
+//! Placement of code into fast memories (i.e. CCM, ITCM).
+//!
+//! Each [`FastMemory`] is given a linker section of the same name
+//! which functions marked with `#[proto_hal::ram_func]` are placed in.
+//! The code is loaded from flash and must be copied into the memory
+//! at startup with `proto_hal::init_fast_memory!`.
+//!
+//! ```ignore
+//! // build.rs
+//! proto_hal_build::memory::build(&[
+//!     FastMemory::new("ccm", 0x1000_0000, 0x8000),
+//! ]);
+//! ```
+//!
+//! *Note: The generated script is a fragment which inserts itself
+//! after `.bss`, so applications must pass it to the linker alongside
+//! the runtime script (i.e. `-Tlink.x -Tfast_memory.x`).*
+
+use std::{env, fmt::Write, path::PathBuf};
+
+use crate::{cache, diagnostic::Diagnostic, target::TargetProfile};
+
+/// The linker script fragment placing code into fast memories.
+pub const SCRIPT: &str = "fast_memory.x";
+
+/// The section functions marked without a memory are placed in.
+///
+/// Such functions are placed in the first memory described.
+pub const DEFAULT_SECTION: &str = ".ram_func";
+
+/// A memory code can be placed in to be executed faster
+/// than from flash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastMemory {
+    /// The name of the memory, which names its linker
+    /// section and symbols (i.e. `ccm` -> `.ccm`, `__sccm`).
+    pub name: &'static str,
+    pub origin: u64,
+    /// The size (in bytes) of the memory.
+    pub length: u64,
+}
+
+impl FastMemory {
+    pub const fn new(name: &'static str, origin: u64, length: u64) -> Self {
+        Self {
+            name,
+            origin,
+            length,
+        }
+    }
+
+    /// The name of the memory region declared for this memory.
+    fn region(&self) -> String {
+        self.name.to_uppercase()
+    }
+}
+
+/// Generate the linker script fragment placing code into
+/// the fast memories for the target being built for.
+///
+/// Targets without a [`TargetProfile`] are assumed
+/// to be Cortex-M.
+pub fn build(memories: &[FastMemory]) {
+    let profile = TargetProfile::from_env().unwrap_or_else(|| {
+        Diagnostic::warning("unsupported target architecture, assuming Cortex-M").emit();
+
+        TargetProfile::CORTEX_M
+    });
+
+    build_for(&profile, memories);
+}
+
+/// Generate the linker script fragment placing code into
+/// the fast memories, loaded from the region the runtime
+/// crate of the target places code in.
+///
+/// *Note: The linker script is only rewritten when
+/// the memories change.*
+///
+/// Invalid or overlapping memories are reported as
+/// cargo errors and no script is written.
+pub fn build_for(profile: &TargetProfile, memories: &[FastMemory]) {
+    let diagnostics = validate(profile, memories);
+
+    for diagnostic in &diagnostics {
+        diagnostic.emit();
+    }
+
+    if !diagnostics.is_empty() {
+        return;
+    }
+
+    let out = &PathBuf::from(env::var_os("OUT_DIR").unwrap());
+
+    let mut script = String::new();
+
+    writeln!(script, "MEMORY\n{{").unwrap();
+    for memory in memories {
+        writeln!(
+            script,
+            "  {} : ORIGIN = {:#x}, LENGTH = {:#x}",
+            memory.region(),
+            memory.origin,
+            memory.length
+        )
+        .unwrap();
+    }
+    writeln!(script, "}}\n\nSECTIONS\n{{").unwrap();
+
+    for (i, memory) in memories.iter().enumerate() {
+        let name = memory.name;
+
+        // functions marked without a memory go to the first memory
+        let default = if i == 0 {
+            format!(" {DEFAULT_SECTION} {DEFAULT_SECTION}.*")
+        } else {
+            String::new()
+        };
+
+        writeln!(script, "  .{name} : ALIGN(4)\n  {{").unwrap();
+        writeln!(script, "    __s{name} = .;").unwrap();
+        writeln!(script, "    *(.{name} .{name}.*{default});").unwrap();
+        writeln!(script, "    . = ALIGN(4);").unwrap();
+        writeln!(script, "    __e{name} = .;").unwrap();
+        writeln!(
+            script,
+            "  }} > {} AT > {}",
+            memory.region(),
+            profile.load_region
+        )
+        .unwrap();
+        writeln!(script, "  __si{name} = LOADADDR(.{name});\n").unwrap();
+    }
+
+    writeln!(script, "}} INSERT AFTER .bss;").unwrap();
+
+    cache::write_if_changed(&out.join(SCRIPT), &script).unwrap();
+
+    println!("cargo::rustc-link-search={}", out.display());
+}
+
+/// Validate the memories have valid names, are unique,
+/// and are addressable and disjoint.
+fn validate(profile: &TargetProfile, memories: &[FastMemory]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (i, memory) in memories.iter().enumerate() {
+        let name = memory.name;
+        let end = memory.origin + memory.length;
+
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        let error = |message: String| {
+            Diagnostic::error(message)
+                .with_context("memories")
+                .with_context(name)
+        };
+
+        if !valid {
+            diagnostics.push(error(format!("`{name}` is not a valid memory name")));
+        }
+
+        if memory.length == 0 {
+            diagnostics.push(error(format!("memory `{name}` is empty")));
+        } else if !profile.addresses(end - 1) {
+            diagnostics.push(error(format!(
+                "memory `{name}` exceeds the {}-bit address space",
+                profile.address_width
+            )));
+        }
+
+        for other in &memories[..i] {
+            if other.name == name {
+                diagnostics.push(error(format!("memory `{name}` is declared more than once")));
+            } else if memory.origin < other.origin + other.length && other.origin < end {
+                diagnostics.push(error(format!(
+                    "memory `{name}` overlaps memory `{}`",
+                    other.name
+                )));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate, FastMemory};
+    use crate::target::TargetProfile;
+
+    #[test]
+    fn valid() {
+        let memories = [
+            FastMemory::new("ccm", 0x1000_0000, 0x8000),
+            FastMemory::new("itcm", 0x0000_0000, 0x1_0000),
+        ];
+
+        assert!(validate(&TargetProfile::CORTEX_M, &memories).is_empty());
+    }
+
+    #[test]
+    fn invalid_name() {
+        let diagnostics = validate(
+            &TargetProfile::CORTEX_M,
+            &[FastMemory::new("fast-ram", 0x1000_0000, 0x8000)],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].to_compile_error(),
+            "cargo::error=memories::fast-ram: `fast-ram` is not a valid memory name"
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let diagnostics = validate(
+            &TargetProfile::CORTEX_M,
+            &[FastMemory::new("ccm", 0x1000_0000, 0)],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "memory `ccm` is empty");
+    }
+
+    #[test]
+    fn exceeds_address_space() {
+        // the memory ends exactly at the end of the address space
+        assert!(validate(
+            &TargetProfile::MSP430,
+            &[FastMemory::new("ram", 0xc000, 0x4000)],
+        )
+        .is_empty());
+
+        let diagnostics = validate(
+            &TargetProfile::MSP430,
+            &[FastMemory::new("ram", 0xc000, 0x4001)],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "memory `ram` exceeds the 16-bit address space"
+        );
+    }
+
+    #[test]
+    fn duplicate() {
+        let diagnostics = validate(
+            &TargetProfile::CORTEX_M,
+            &[
+                FastMemory::new("ccm", 0x1000_0000, 0x8000),
+                FastMemory::new("ccm", 0x2000_0000, 0x8000),
+            ],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "memory `ccm` is declared more than once"
+        );
+    }
+
+    #[test]
+    fn overlapping() {
+        // adjacent memories do not overlap
+        assert!(validate(
+            &TargetProfile::CORTEX_M,
+            &[
+                FastMemory::new("ccm", 0x1000_0000, 0x8000),
+                FastMemory::new("itcm", 0x1000_8000, 0x8000),
+            ],
+        )
+        .is_empty());
+
+        let diagnostics = validate(
+            &TargetProfile::CORTEX_M,
+            &[
+                FastMemory::new("ccm", 0x1000_0000, 0x8000),
+                FastMemory::new("itcm", 0x1000_7ffc, 0x8000),
+            ],
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "memory `itcm` overlaps memory `ccm`"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Target-specific aspects of the generated scaffolding.
+
+use std::env;
+
+/// The conventions of the runtime crate of a target architecture
+/// which the generated scaffolding must abide by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetProfile {
+    /// The name of the target architecture (as in `CARGO_CFG_TARGET_ARCH`).
+    pub arch: &'static str,
+    /// The linker script the runtime crate includes to
+    /// provide default interrupt handlers.
+    pub script: &'static str,
+    /// The handler interrupts are bound to by default.
+    pub default_handler: &'static str,
+    /// The memory region code is loaded from.
+    pub load_region: &'static str,
+    /// The width of addresses in bits.
+    pub address_width: u8,
+}
+
+impl TargetProfile {
+    /// Cortex-M, as supported by `cortex-m-rt`.
+    pub const CORTEX_M: Self = Self {
+        arch: "arm",
+        script: "device.x",
+        default_handler: "DefaultHandler",
+        load_region: "FLASH",
+        address_width: 32,
+    };
+
+    /// RISC-V (i.e. ESP32-C3, GD32VF), as supported by `riscv-rt`.
+    pub const RISCV: Self = Self {
+        arch: "riscv32",
+        script: "device.x",
+        default_handler: "DefaultHandler",
+        load_region: "REGION_TEXT",
+        address_width: 32,
+    };
+
+    /// MSP430, as supported by `msp430-rt`.
+    pub const MSP430: Self = Self {
+        arch: "msp430",
+        script: "device.x",
+        default_handler: "DefaultHandler",
+        load_region: "ROM",
+        address_width: 16,
+    };
+
+    /// The profile of the target being built for (if supported).
+    pub fn from_env() -> Option<Self> {
+        let arch = env::var("CARGO_CFG_TARGET_ARCH").ok()?;
+
+        [Self::CORTEX_M, Self::RISCV, Self::MSP430]
+            .into_iter()
+            .find(|profile| profile.arch == arch)
+    }
+
+    /// Whether the address is addressable on the target.
+    pub fn addresses(&self, addr: u64) -> bool {
+        addr >> self.address_width == 0
+    }
+}
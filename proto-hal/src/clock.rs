@@ -0,0 +1,116 @@
+//! Compile-time propagation of clock frequencies.
+//!
+//! States of clock tree fields may declare the frequency they
+//! produce ([`Source`]) or how they transform their input frequency
+//! ([`Scale`]). These compose into types ([`Scaled`]) whose frequency
+//! is known at compile time, so downstream peripherals can compute
+//! their configuration (i.e. baud rate divisors) statically.
+//!
+//! ```ignore
+//! // sysclk = hsi / ahb prescaler
+//! type Sysclk = Scaled<rcc::cfgr::sw::Hsi, rcc::cfgr::hpre::Div2>;
+//!
+//! const BRR: u32 = clock::hz::<Sysclk>() / 115_200;
+//! ```
+
+use core::marker::PhantomData;
+
+/// Types which produce a known frequency.
+pub trait Source {
+    /// The produced frequency in hertz.
+    const HZ: u32;
+}
+
+/// Types which scale their input frequency by `MUL / DIV`.
+pub trait Scale {
+    /// The multiplier applied to the input frequency.
+    const MUL: u32;
+    /// The divisor applied to the input frequency.
+    const DIV: u32;
+}
+
+/// The frequency produced by scaling the source `S` by `T`.
+pub struct Scaled<S, T> {
+    _p: PhantomData<(S, T)>,
+}
+
+impl<S, T> Source for Scaled<S, T>
+where
+    S: Source,
+    T: Scale,
+{
+    const HZ: u32 = match checked_scale::<T>(S::HZ) {
+        Some(hz) => hz,
+        None => panic!("scaled frequency exceeds u32::MAX"),
+    };
+}
+
+/// The frequency produced by `S` in hertz.
+pub const fn hz<S: Source>() -> u32 {
+    S::HZ
+}
+
+/// Scale the provided frequency by `T`, rounding
+/// to the nearest hertz.
+///
+/// *Note: Panics if the scaled frequency exceeds `u32::MAX`,
+/// see [`checked_scale`].*
+pub const fn scale<T: Scale>(hz: u32) -> u32 {
+    match checked_scale::<T>(hz) {
+        Some(hz) => hz,
+        None => panic!("scaled frequency exceeds u32::MAX"),
+    }
+}
+
+/// Scale the provided frequency by `T`, rounding to
+/// the nearest hertz, or `None` if the scaled frequency
+/// exceeds `u32::MAX`.
+pub const fn checked_scale<T: Scale>(hz: u32) -> Option<u32> {
+    let div = T::DIV as u64;
+    let hz = (hz as u64 * T::MUL as u64 + div / 2) / div;
+
+    if hz > u32::MAX as u64 {
+        None
+    } else {
+        Some(hz as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Div3;
+
+    impl Scale for Div3 {
+        const MUL: u32 = 1;
+        const DIV: u32 = 3;
+    }
+
+    struct Mul2;
+
+    impl Scale for Mul2 {
+        const MUL: u32 = 2;
+        const DIV: u32 = 1;
+    }
+
+    struct Hsi;
+
+    impl Source for Hsi {
+        const HZ: u32 = 16_000_000;
+    }
+
+    #[test]
+    fn scale_rounds_to_nearest() {
+        assert_eq!(scale::<Div3>(16_000_000), 5_333_333);
+        assert_eq!(scale::<Div3>(5), 2);
+        assert_eq!(scale::<Div3>(4), 1);
+        assert_eq!(hz::<Scaled<Hsi, Div3>>(), 5_333_333);
+    }
+
+    #[test]
+    fn checked_scale_overflow() {
+        assert_eq!(checked_scale::<Mul2>(u32::MAX / 2), Some(u32::MAX - 1));
+        assert_eq!(checked_scale::<Mul2>(u32::MAX), None);
+    }
+}
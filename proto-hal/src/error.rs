@@ -0,0 +1,73 @@
+//! A single error type encompassing the failures
+//! of fallible operations on generated interfaces.
+
+use core::fmt;
+
+use crate::macro_utils::{ConstraintViolation, InvalidValue, WriteMismatch};
+
+/// A fallible operation on a register or field failed.
+///
+/// Errors of individual operations convert into this type,
+/// so they may be propagated by drivers with `?`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// A value did not correspond to any variant of a field.
+    InvalidValue(InvalidValue),
+    /// A value written to a field violated one of its constraints.
+    ConstraintViolation(ConstraintViolation),
+    /// The stable fields of a register did not read back as written.
+    WriteMismatch(WriteMismatch),
+    /// A field did not enter the awaited state in time.
+    Timeout,
+    /// The hardware was not in the requested states.
+    Unresolved,
+}
+
+/// A result whose error is [`Error`] by default.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidValue(InvalidValue(value)) => {
+                write!(f, "{value:#x} does not correspond to any variant")
+            }
+            Self::ConstraintViolation(violation) => match violation {
+                ConstraintViolation::OutOfRange => write!(f, "value is out of range"),
+                ConstraintViolation::NotMultiple => {
+                    write!(f, "value is not a multiple of the required value")
+                }
+                ConstraintViolation::Zero => write!(f, "value is zero"),
+            },
+            Self::WriteMismatch(mismatch) => write!(
+                f,
+                "register at {:#010x} read back {:#x} after writing {:#x}",
+                mismatch.addr, mismatch.actual, mismatch.expected
+            ),
+            Self::Timeout => write!(f, "timed out waiting for the hardware"),
+            Self::Unresolved => write!(f, "the hardware is not in the requested states"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl From<InvalidValue> for Error {
+    fn from(value: InvalidValue) -> Self {
+        Self::InvalidValue(value)
+    }
+}
+
+impl From<ConstraintViolation> for Error {
+    fn from(violation: ConstraintViolation) -> Self {
+        Self::ConstraintViolation(violation)
+    }
+}
+
+impl From<WriteMismatch> for Error {
+    fn from(mismatch: WriteMismatch) -> Self {
+        Self::WriteMismatch(mismatch)
+    }
+}
@@ -17,3 +17,64 @@ impl<Mode: AlternateMode> super::PinMode for Alternate<Mode> {}
 
 /// Analog pin types implement this trait to represent an analog pin.
 pub trait AlternatePin<Mode: AlternateMode>: super::Pin<Alternate<Mode>> {}
+
+/// Types implement this trait to represent a peripheral signal
+/// which can be routed to pins (i.e. the TX signal of a USART).
+pub trait Signal {}
+
+/// Pin types implement this trait to indicate the signal `S` can be
+/// routed to the pin in the alternate function mode `Self::Mode`.
+///
+/// *Note: Any entitlements required to configure the pin (i.e. the
+/// GPIO port clock) are required by the [`IntoMode`](super::IntoMode)
+/// implementation.*
+pub trait SignalPin<S: Signal>: super::IntoMode<Alternate<Self::Mode>> + Sized {
+    /// The alternate function mode routing the signal to the pin.
+    type Mode: AlternateMode;
+
+    /// Put the pin into the alternate function mode routing `S`,
+    /// producing a token which can be consumed by the peripheral.
+    fn into_signal(self) -> Routed<S, Self::Into> {
+        Routed {
+            pin: self.into_mode(),
+            _signal: PhantomData,
+        }
+    }
+}
+
+/// A pin with the signal `S` routed to it.
+pub struct Routed<S: Signal, P> {
+    pin: P,
+    _signal: PhantomData<S>,
+}
+
+impl<S: Signal, P> Routed<S, P> {
+    /// Relinquish the signal, yielding the pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+/// Declare the pins signals can be routed to and the
+/// alternate function modes which route them.
+///
+/// ```ignore
+/// proto_hal::signal_pins! {
+///     usart1::Tx => PA9 as AF7,
+///     usart1::Tx => PB6 as AF7,
+///     usart1::Rx => PA10 as AF7,
+/// }
+/// ```
+#[macro_export]
+macro_rules! signal_pins {
+    ($($signal:ty => $pin:ident as $mode:ty),+ $(,)?) => {
+        $(
+            impl<Mode: $crate::gpio::PinMode> $crate::gpio::alternate::SignalPin<$signal> for $pin<Mode>
+            where
+                $pin<Mode>: $crate::gpio::IntoMode<$crate::gpio::alternate::Alternate<$mode>>,
+            {
+                type Mode = $mode;
+            }
+        )+
+    };
+}
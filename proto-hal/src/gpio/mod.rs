@@ -54,6 +54,13 @@ mod tests {
         const RAW: Self::Word = 0;
     }
 
+    // signals
+    #[cfg(feature = "pin_alternates")]
+    struct Tx;
+
+    #[cfg(feature = "pin_alternates")]
+    impl gpio::alternate::Signal for Tx {}
+
     /// Dummy pin.
     struct PA0<Mode: gpio::PinMode> {
         _mode: PhantomData<Mode>,
@@ -175,6 +182,11 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "pin_alternates")]
+    crate::signal_pins! {
+        Tx => PA0 as AF0,
+    }
+
     // Explicit conversions.
     impl<Mode: gpio::PinMode> PA0<Mode> {
         fn into_input_floating(self) -> PA0<gpio::digital::Input<Floating>> {
@@ -246,6 +258,21 @@ mod tests {
         let _input_pull_down = makes_a_pull_down(input_pull_up);
     }
 
+    #[cfg(feature = "pin_alternates")]
+    #[test]
+    fn signal_routing() {
+        use gpio::alternate::SignalPin as _;
+
+        let pa0 = PA0::<gpio::analog::Analog> { _mode: PhantomData };
+
+        fn wants_tx<Pin>(tx: gpio::alternate::Routed<Tx, Pin>) -> Pin {
+            tx.release()
+        }
+
+        let tx = pa0.into_signal();
+        let _af0: PA0<gpio::alternate::Alternate<AF0>> = wants_tx(tx);
+    }
+
     mod digital {
         use super::*;
 
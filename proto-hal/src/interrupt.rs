@@ -22,3 +22,146 @@ impl Vector {
 /// `*const ()` value. This value is never
 /// read, and as such is `Sync`.
 unsafe impl Sync for Vector {}
+
+/// A handler slot for an interrupt whose handler
+/// is bound at runtime.
+///
+/// The generated vector entry invokes the bound
+/// handler with [`call`](Self::call).
+pub struct Handler {
+    f: core::sync::atomic::AtomicPtr<()>,
+}
+
+impl Handler {
+    /// Create an empty handler slot.
+    pub const fn new() -> Self {
+        Self {
+            f: core::sync::atomic::AtomicPtr::new(core::ptr::null_mut()),
+        }
+    }
+
+    /// Bind a handler function to this slot.
+    ///
+    /// *Note: Binding a new handler replaces the previous one.*
+    pub fn bind(&self, f: fn()) {
+        self.f
+            .store(f as *mut (), core::sync::atomic::Ordering::Release);
+    }
+
+    /// Invoke the bound handler (if any).
+    pub fn call(&self) {
+        let f = self.f.load(core::sync::atomic::Ordering::Acquire);
+
+        if !f.is_null() {
+            // SAFETY: the only non-null values stored
+            // are `fn()` pointers (see `bind`)
+            let f = unsafe { core::mem::transmute::<*mut (), fn()>(f) };
+
+            f();
+        }
+    }
+}
+
+impl Default for Handler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A waker slot associated with an interrupt.
+///
+/// Futures awaiting hardware events register their waker here,
+/// and the interrupt handler wakes them with [`wake`](Self::wake).
+#[cfg(feature = "async")]
+pub struct InterruptWaker {
+    waker: critical_section::Mutex<core::cell::RefCell<Option<core::task::Waker>>>,
+}
+
+#[cfg(feature = "async")]
+impl InterruptWaker {
+    /// Create an empty waker slot.
+    pub const fn new() -> Self {
+        Self {
+            waker: critical_section::Mutex::new(core::cell::RefCell::new(None)),
+        }
+    }
+
+    /// Register a waker to be woken when the interrupt fires.
+    ///
+    /// *Note: Only one waker is held at a time, registering a
+    /// new waker replaces the previous one.*
+    pub fn register(&self, waker: &core::task::Waker) {
+        critical_section::with(|cs| {
+            let mut slot = self.waker.borrow_ref_mut(cs);
+
+            match slot.as_ref() {
+                Some(registered) if registered.will_wake(waker) => {}
+                _ => {
+                    slot.replace(waker.clone());
+                }
+            }
+        });
+    }
+
+    /// Wake the registered waker (if any).
+    ///
+    /// The handlers generated by `#[interrupts(wakers)]` call this
+    /// every time their interrupt fires.
+    pub fn wake(&self) {
+        if let Some(waker) = critical_section::with(|cs| self.waker.borrow_ref_mut(cs).take()) {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Default for InterruptWaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A future which resolves when the provided condition
+/// yields a value, re-evaluating the condition every
+/// time the associated interrupt fires.
+#[cfg(feature = "async")]
+pub struct WaitFor<'a, F> {
+    waker: &'a InterruptWaker,
+    f: F,
+}
+
+#[cfg(feature = "async")]
+impl<'a, F> WaitFor<'a, F> {
+    /// Create a future which waits for `f` to yield a value.
+    pub const fn new(waker: &'a InterruptWaker, f: F) -> Self {
+        Self { waker, f }
+    }
+}
+
+// `f` is never structurally pinned.
+#[cfg(feature = "async")]
+impl<F> Unpin for WaitFor<'_, F> {}
+
+#[cfg(feature = "async")]
+impl<F, T> core::future::Future for WaitFor<'_, F>
+where
+    F: FnMut() -> Option<T>,
+{
+    type Output = T;
+
+    fn poll(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        let this = self.get_mut();
+
+        // the waker is registered *before* the condition is
+        // evaluated so an interrupt firing in between is not lost
+        this.waker.register(cx.waker());
+
+        match (this.f)() {
+            Some(value) => core::task::Poll::Ready(value),
+            None => core::task::Poll::Pending,
+        }
+    }
+}
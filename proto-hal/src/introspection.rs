@@ -0,0 +1,46 @@
+/// Describes the location of a field within a block snapshot.
+pub struct FieldInfo {
+    /// The name of the register containing the field.
+    pub register: &'static str,
+    /// The name of the field.
+    pub field: &'static str,
+    /// The index of the register within the snapshot.
+    pub index: usize,
+    pub offset: u8,
+    pub width: u8,
+}
+
+impl FieldInfo {
+    fn extract(&self, values: &[u32]) -> u32 {
+        (values[self.index] >> self.offset) & (u32::MAX >> (32 - self.width))
+    }
+}
+
+/// A change in the value of a field between two snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Change {
+    pub register: &'static str,
+    pub field: &'static str,
+    pub before: u32,
+    pub after: u32,
+}
+
+/// Compare the register values of two snapshots,
+/// yielding the fields whose values differ.
+pub fn diff<const N: usize>(
+    fields: &'static [FieldInfo],
+    before: [u32; N],
+    after: [u32; N],
+) -> impl Iterator<Item = Change> {
+    fields.iter().filter_map(move |field| {
+        let (before, after) = (field.extract(&before), field.extract(&after));
+
+        (before != after).then_some(Change {
+            register: field.register,
+            field: field.field,
+            before,
+            after,
+        })
+    })
+}
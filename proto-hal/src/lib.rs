@@ -1,15 +1,35 @@
 #![no_std]
 
+// the mock memory map is kept per test thread
+#[cfg(feature = "testing")]
+extern crate std;
+
 pub mod gpio;
 
 #[cfg(feature = "stm32")]
 pub mod stm32;
 
+#[cfg(feature = "embedded-hal")]
+pub use embedded_hal;
 pub use macros;
+pub use macros::{configure, init_fast_memory, ram_func, read};
+pub mod clock;
+pub mod error;
 pub mod interrupt;
+#[cfg(feature = "introspection")]
+pub mod introspection;
+pub mod lowpower;
 pub mod macro_utils;
+pub mod memory;
+pub mod model;
 pub mod prelude;
+#[cfg(feature = "profile")]
+pub mod profile;
 pub mod stasis;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "trace")]
+pub mod trace;
 
 /// Types that encapsulate a resource that can be configured to be
 /// in a "reset" state implement this trait.
@@ -20,3 +40,467 @@ pub trait IntoReset {
     /// Transform the implementor type into the "reset" state.
     fn into_reset(self) -> Self::Reset;
 }
+
+/// Evaluate register accesses strictly in the order they are listed,
+/// with compiler fences between each, yielding a tuple of their results.
+///
+/// Useful for sequences whose order is significant, such as unlock
+/// sequences spanning several registers or peripherals.
+///
+/// ```ignore
+/// let (keyr, cr) = proto_hal::ordered!(
+///     flash::keyr::write(|w| w.key(KEY1)),
+///     flash::cr::modify(|_, w| w.lock(false)),
+/// );
+/// ```
+///
+/// *Note: compiler fences prevent the compiler from reordering memory
+/// accesses around the fence, they do not emit barrier instructions.*
+#[macro_export]
+macro_rules! ordered {
+    ($($access:expr),+ $(,)?) => {
+        (
+            $({
+                let result = $access;
+                ::core::sync::atomic::compiler_fence(::core::sync::atomic::Ordering::SeqCst);
+                result
+            },)+
+        )
+    };
+}
+
+/// Perform the state transitions of several registers in order
+/// (e.g. reconfiguring the PLL, then switching the system clock to it).
+///
+/// Each register is bound to the provided name once written, so
+/// later transitions may use the state of earlier registers (e.g.
+/// as entitlements). Every transition must be valid for the
+/// transaction to compile, so a transaction is never partially
+/// applied.
+///
+/// Registers are built and written strictly in the order they are
+/// listed, with a memory fence after each write, yielding a tuple
+/// of the registers.
+///
+/// ```ignore
+/// let (pllcfgr, cfgr) = proto_hal::transaction! {
+///     pllcfgr = rcc.pllcfgr => |b| b.pllsrc().hse(),
+///     cfgr = rcc.cfgr => |b| b.sw().pll(),
+/// };
+/// ```
+#[macro_export]
+macro_rules! transaction {
+    ($($name:ident = $register:expr => $f:expr),+ $(,)?) => {{
+        $(
+            let $name = $crate::macro_utils::commit(
+                $crate::macro_utils::stage($register, $f),
+            );
+        )+
+
+        ($($name,)+)
+    }};
+}
+
+/// Assume fields are in the listed states without touching hardware,
+/// yielding a tuple of the corresponding state tokens.
+///
+/// ```ignore
+/// let (func, precision) = unsafe {
+///     proto_hal::assume! {
+///         cordic::csr::func => Sqrt,
+///         cordic::csr::precision => P60,
+///     }
+/// };
+/// ```
+///
+/// # Safety
+///
+/// See the `assume` function of each field.
+#[macro_export]
+macro_rules! assume {
+    ($($($segment:ident)::+ => $state:ident),+ $(,)?) => {
+        (
+            $(
+                $($segment)::+::assume::<$($segment)::+::$state>(),
+            )+
+        )
+    };
+}
+
+/// Assert the model semantics of this proto-hal are compatible
+/// with the version a device description was written for,
+/// failing to compile otherwise.
+///
+/// ```ignore
+/// proto_hal::require_model!("1.0");
+/// ```
+///
+/// See [`model`](crate::model) for details.
+#[macro_export]
+macro_rules! require_model {
+    ($required:literal $(,)?) => {
+        const _: () = match $crate::model::check_compat($required) {
+            ::core::result::Result::Ok(()) => {}
+            ::core::result::Result::Err(incompatibility) => {
+                ::core::panic!("{}", incompatibility.message())
+            }
+        };
+    };
+}
+
+/// Install the function remapping the base addresses of all
+/// blocks (i.e. to the non-secure alias of each peripheral,
+/// or to a mock memory map).
+///
+/// Requires the `remap` feature, and must be invoked exactly
+/// once in the final binary.
+///
+/// ```ignore
+/// proto_hal::remap!(|addr| addr & !0x1000_0000);
+/// ```
+#[macro_export]
+macro_rules! remap {
+    ($f:expr $(,)?) => {
+        #[doc(hidden)]
+        #[no_mangle]
+        extern "Rust" fn __proto_hal_remap(addr: u32) -> u32 {
+            let f: fn(u32) -> u32 = $f;
+
+            f(addr)
+        }
+    };
+}
+
+/// Install the function handling registers which did not
+/// read back as written (i.e. due to a clock being disabled
+/// or an unstable supply).
+///
+/// Requires the `verify-writes` feature, and must be invoked
+/// exactly once in the final binary.
+///
+/// ```ignore
+/// proto_hal::write_mismatch_handler!(|mismatch| {
+///     defmt::error!("write mismatch: {}", mismatch);
+/// });
+/// ```
+#[macro_export]
+macro_rules! write_mismatch_handler {
+    ($f:expr $(,)?) => {
+        #[doc(hidden)]
+        #[no_mangle]
+        extern "Rust" fn __proto_hal_write_mismatch(mismatch: $crate::macro_utils::WriteMismatch) {
+            let f: fn($crate::macro_utils::WriteMismatch) = $f;
+
+            f(mismatch)
+        }
+    };
+}
+
+/// Transition a field state token into the listed state for the
+/// duration of a closure, returning the field to its prior state
+/// afterwards (even if the closure panics).
+///
+/// Yields the prior state token and the result of the closure.
+///
+/// ```ignore
+/// let (func, result) = proto_hal::with_state!(func, cordic::csr::func => Sqrt, |sqrt| {
+///     compute(sqrt)
+/// });
+/// ```
+#[macro_export]
+macro_rules! with_state {
+    ($token:expr, $($segment:ident)::+ => $state:ident, $f:expr $(,)?) => {
+        $crate::stasis::scoped(
+            $token,
+            |prior| $($segment)::+::State::into_state::<$($segment)::+::$state>(prior),
+            |state| $($segment)::+::State::into_state(state),
+            $f,
+        )
+    };
+}
+
+/// Partition blocks into disjoint sets of peripherals,
+/// i.e. one for each core of a multi-core device.
+///
+/// Each set is a struct holding the listed blocks in reset state.
+/// A block claimed by multiple sets of one invocation is rejected
+/// at compile time. Across invocations, a set is only taken if none
+/// of its blocks have been taken by another set.
+///
+/// *Note: Disjointness is only guaranteed at compile time within
+/// one invocation. Sets of separate invocations may share blocks,
+/// which is only detected at runtime, when `take` returns `None`.*
+///
+/// ```ignore
+/// proto_hal::peripherals! {
+///     pub struct Core0 {
+///         rcc: crate::rcc,
+///         cordic: crate::cordic,
+///     }
+///
+///     pub struct Core1 {
+///         gpioa: crate::gpioa,
+///     }
+/// }
+///
+/// // on core 0
+/// let p = Core0::take().unwrap();
+/// ```
+#[macro_export]
+macro_rules! peripherals {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis struct $ident:ident {
+            $($field:ident: $($segment:ident)::+),* $(,)?
+        }
+    )+) => {
+        // blocks claimed more than once produce conflicting implementations
+        const _: () = {
+            trait Claimed {}
+
+            $($(
+                impl Claimed for $($segment)::+::Reset {}
+            )*)+
+        };
+
+        $(
+            $(#[$meta])*
+            $vis struct $ident {
+                $(
+                    pub $field: $($segment)::+::Reset,
+                )*
+            }
+
+            impl $ident {
+                /// Conjure the blocks of this set in reset state.
+                ///
+                /// # Safety
+                ///
+                /// The requirements of `Reset::conjure` of
+                /// each block must be upheld.
+                pub unsafe fn conjure() -> Self {
+                    Self {
+                        $(
+                            $field: $($segment)::+::Reset::conjure(),
+                        )*
+                    }
+                }
+
+                /// Capture the current states of the blocks of this set
+                /// (i.e. after a bootloader configured them), in order
+                /// of declaration.
+                ///
+                /// # Safety
+                ///
+                /// The requirements of `Current::from_current_state`
+                /// of each block must be upheld.
+                pub unsafe fn from_current_state() -> ($($($segment)::+::Current,)*) {
+                    (
+                        $(
+                            $($segment)::+::Current::from_current_state(),
+                        )*
+                    )
+                }
+
+                /// Take the blocks of this set in reset state, only
+                /// if none of them have been taken (by any set).
+                ///
+                /// *Note: The blocks must not have been conjured
+                /// prior, as they are assumed to be in reset state.*
+                pub fn take() -> ::core::option::Option<Self> {
+                    $crate::macro_utils::critical_section::with(|cs| {
+                        if false $(|| $($segment)::+::__CLAIM.is_claimed(cs))* {
+                            None
+                        } else {
+                            $(
+                                $($segment)::+::__CLAIM.claim(cs);
+                            )*
+
+                            // SAFETY: each block is only taken once (by any set),
+                            // and is in reset state as the program starts
+                            Some(unsafe { Self::conjure() })
+                        }
+                    })
+                }
+            }
+        )+
+    };
+}
+
+/// Describe the memory map of a device, producing a module
+/// with a table of the listed blocks and RAM regions.
+///
+/// ```ignore
+/// proto_hal::memory_map! {
+///     pub mod memory_map {
+///         peripherals {
+///             rcc: crate::rcc,
+///             cordic: crate::cordic,
+///         }
+///
+///         regions {
+///             sram1: 0x2000_0000, 0x1_4000, dma;
+///             ccm: 0x1000_0000, 0x8000;
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! memory_map {
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $ident:ident {
+            peripherals {
+                $($peripheral:ident: $($segment:ident)::+),* $(,)?
+            }
+
+            regions {
+                $($region:ident: $base:expr, $size:expr $(, $dma:ident)?;)*
+            }
+        }
+    ) => {
+        $(#[$meta])*
+        $vis mod $ident {
+            /// The blocks of the device.
+            pub const PERIPHERALS: &[$crate::memory::Peripheral] = &[
+                $(
+                    $crate::memory::Peripheral {
+                        name: ::core::stringify!($peripheral),
+                        base: $($segment)::+::BASE_ADDR,
+                        size: $($segment)::+::SIZE,
+                    },
+                )*
+            ];
+
+            /// The RAM regions of the device.
+            pub const REGIONS: &[$crate::memory::Region] = &[
+                $(
+                    $crate::memory::Region {
+                        name: ::core::stringify!($region),
+                        base: $base,
+                        size: $size,
+                        dma: $crate::memory_map!(@dma $($dma)?),
+                    },
+                )*
+            ];
+
+            /// The region containing the provided buffer (if any).
+            pub fn region_of<T: ?::core::marker::Sized>(buffer: &T) -> ::core::option::Option<&'static $crate::memory::Region> {
+                REGIONS.iter().find(|region| region.contains_buffer(buffer))
+            }
+
+            /// Whether the provided buffer lies within a region accessible to DMA.
+            pub fn is_dma_capable<T: ?::core::marker::Sized>(buffer: &T) -> bool {
+                region_of(buffer).is_some_and(|region| region.dma)
+            }
+        }
+    };
+    (@dma dma) => { true };
+    (@dma) => { false };
+}
+
+/// Place statics in a linker section (i.e. that of a
+/// region of the memory map), optionally aligned.
+///
+/// Aligned statics are wrapped in [`Aligned`](crate::memory::Aligned).
+///
+/// ```ignore
+/// proto_hal::place! {
+///     #[section = ".sram2", align = 32]
+///     static mut BUFFER: [u8; 512] = [0; 512];
+/// }
+/// ```
+///
+/// *Note: The runtime only initializes the sections it knows of
+/// (i.e. `.data` and `.bss`), so statics placed elsewhere must be
+/// initialized by the linker script or at runtime.*
+#[macro_export]
+macro_rules! place {
+    () => {};
+    (
+        #[section = $section:literal $(, align = $align:literal)?]
+        $(#[$meta:meta])*
+        $vis:vis static mut $ident:ident: $ty:ty = $init:expr;
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])*
+        #[link_section = $section]
+        $vis static mut $ident: $crate::place!(@ty [$($align)?] $ty) = $crate::place!(@init [$($align)?] $init);
+
+        $crate::place!($($rest)*);
+    };
+    (
+        #[section = $section:literal $(, align = $align:literal)?]
+        $(#[$meta:meta])*
+        $vis:vis static $ident:ident: $ty:ty = $init:expr;
+        $($rest:tt)*
+    ) => {
+        $(#[$meta])*
+        #[link_section = $section]
+        $vis static $ident: $crate::place!(@ty [$($align)?] $ty) = $crate::place!(@init [$($align)?] $init);
+
+        $crate::place!($($rest)*);
+    };
+    (@ty [] $ty:ty) => { $ty };
+    (@ty [$align:literal] $ty:ty) => { $crate::memory::Aligned<$align, $ty> };
+    (@init [] $init:expr) => { $init };
+    (@init [$align:literal] $init:expr) => { $crate::memory::Aligned::new($init) };
+}
+
+/// Describe the peripheral states permitted in low-power modes,
+/// producing a module with a gate for each mode.
+///
+/// A gate requires a state token of every listed field, each of
+/// which must be in a permitted state, before entering deep sleep.
+///
+/// ```ignore
+/// proto_hal::low_power! {
+///     pub mod low_power {
+///         /// Stop 2 mode.
+///         Stop2 => enter_stop2 {
+///             crate::adc::cr::aden: [Disabled],
+///             crate::pwr::cr1::lpms: [Stop2],
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! low_power {
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $ident:ident {
+            $(
+                $(#[$mode_meta:meta])*
+                $mode:ident => $enter:ident {
+                    $($($segment:ident)::+: [$($state:ident),+ $(,)?]),* $(,)?
+                }
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        $vis mod $ident {
+            $(
+                $(#[$mode_meta])*
+                pub struct $mode;
+
+                $(
+                    const _: () = {
+                        use $($segment)::+ as field;
+
+                        $(
+                            unsafe impl $crate::lowpower::Permits<$mode> for field::$state {}
+                        )+
+                    };
+                )*
+
+                /// Enter this low-power mode, provided the states
+                /// of the listed fields permit it.
+                pub fn $enter($(_: &(impl $($segment)::+::State + $crate::lowpower::Permits<$mode>),)*) {
+                    // SAFETY: the states of all fields
+                    // blocking this mode permit it
+                    unsafe { $crate::lowpower::deep_sleep() }
+                }
+            )+
+        }
+    };
+}
@@ -0,0 +1,54 @@
+//! Entry into low-power modes, gated by the states of peripherals.
+//!
+//! Some peripheral states prevent (or are corrupted by) entry into
+//! low-power modes, i.e. an enabled ADC blocks Stop 2. The states
+//! permitted in each mode are described with
+//! [`low_power!`](crate::low_power), which generates a gate for each
+//! mode requiring a state token of every listed field, where each
+//! state must permit the mode.
+//!
+//! ```ignore
+//! proto_hal::low_power! {
+//!     pub mod low_power {
+//!         /// Stop 2 mode.
+//!         Stop2 => enter_stop2 {
+//!             crate::adc::cr::aden: [Disabled],
+//!             crate::pwr::cr1::lpms: [Stop2],
+//!         }
+//!     }
+//! }
+//!
+//! low_power::enter_stop2(&adc.cr.aden, &pwr.cr1.lpms);
+//! ```
+
+/// Indicates a type-state permits entry
+/// into the low-power mode `Mode`.
+///
+/// # Safety
+///
+/// If a type implements this trait
+/// erroneously, the generated low-power
+/// gates will be invalid.
+#[diagnostic::on_unimplemented(message = "`{Self}` does not permit entry into `{Mode}`")]
+pub unsafe trait Permits<Mode> {}
+
+/// Enter deep sleep (i.e. the Stop or Standby mode selected by
+/// the power controller) until woken by an interrupt or event.
+///
+/// *Note: This has no effect on targets other than Cortex-M.*
+///
+/// # Safety
+///
+/// The states of all peripherals must permit the
+/// low-power mode selected by the power controller.
+pub unsafe fn deep_sleep() {
+    #[cfg(target_arch = "arm")]
+    {
+        const SCR: u32 = 0xe000_ed10;
+        const SLEEPDEEP: u32 = 1 << 2;
+
+        crate::macro_utils::write::<u32>(SCR, crate::macro_utils::read::<u32>(SCR) | SLEEPDEEP);
+        core::arch::asm!("dsb", "wfi", options(nostack, preserves_flags));
+        crate::macro_utils::write::<u32>(SCR, crate::macro_utils::read::<u32>(SCR) & !SLEEPDEEP);
+    }
+}
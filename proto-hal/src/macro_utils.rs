@@ -1,4 +1,5 @@
 pub use arbitrary_int;
+pub use critical_section;
 
 pub struct RegisterValue(u32);
 
@@ -86,6 +87,459 @@ impl_uint_special!(u30, 30);
 impl_uint_special!(u31, 31);
 impl_uint_standard!(u32, 32);
 
+/// Compute the mask of a field with the provided
+/// offset and width within a register.
+pub const fn mask(offset: u8, width: u8) -> u32 {
+    (u32::MAX >> (32 - width)) << offset
+}
+
+/// Round to the nearest integer, away from zero.
+///
+/// *Note: `f64::round` is unavailable in `core`. The value is
+/// truncated to `i64` (which holds every `u32` and `i32` value)
+/// and the truncated fraction decides the rounding, so no
+/// precision is lost to intermediate floats.*
+pub fn round(value: f64) -> i64 {
+    let truncated = value as i64;
+    let fraction = value - truncated as f64;
+
+    if fraction >= 0.5 {
+        truncated + 1
+    } else if fraction <= -0.5 {
+        truncated - 1
+    } else {
+        truncated
+    }
+}
+
+/// Compute the address of a bit within the Cortex-M
+/// peripheral bit-band alias region.
+///
+/// *Note: `addr` must reside within the peripheral
+/// bit-band region (`0x4000_0000..0x4010_0000`).*
+pub const fn bit_band_alias(addr: u32, bit: u8) -> u32 {
+    debug_assert!(addr >= 0x4000_0000 && addr < 0x4010_0000);
+
+    0x4200_0000 + (addr - 0x4000_0000) * 32 + bit as u32 * 4
+}
+
+/// Primitive types registers are accessed as.
+pub trait Raw: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    unsafe fn read_volatile(addr: u32) -> u32;
+    #[doc(hidden)]
+    unsafe fn write_volatile(addr: u32, value: u32);
+    #[cfg(all(target_arch = "arm", target_feature = "v7"))]
+    #[doc(hidden)]
+    unsafe fn load_exclusive(addr: u32) -> u32;
+    /// Returns whether the store succeeded.
+    #[cfg(all(target_arch = "arm", target_feature = "v7"))]
+    #[doc(hidden)]
+    unsafe fn store_exclusive(addr: u32, value: u32) -> bool;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_raw {
+    ($($ux:ident => $ldrex:literal, $strex:literal),+) => {
+        $(
+            impl sealed::Sealed for $ux {}
+
+            impl Raw for $ux {
+                #[cfg(all(target_arch = "arm", target_feature = "v7"))]
+                #[inline(always)]
+                unsafe fn load_exclusive(addr: u32) -> u32 {
+                    let value: u32;
+
+                    core::arch::asm!(
+                        concat!($ldrex, " {value}, [{addr}]"),
+                        addr = in(reg) addr,
+                        value = out(reg) value,
+                        options(nostack),
+                    );
+
+                    value
+                }
+
+                #[cfg(all(target_arch = "arm", target_feature = "v7"))]
+                #[inline(always)]
+                unsafe fn store_exclusive(addr: u32, value: u32) -> bool {
+                    let status: u32;
+
+                    core::arch::asm!(
+                        concat!($strex, " {status}, {value}, [{addr}]"),
+                        addr = in(reg) addr,
+                        value = in(reg) value,
+                        status = out(reg) status,
+                        options(nostack),
+                    );
+
+                    status == 0
+                }
+
+                #[inline(always)]
+                unsafe fn read_volatile(addr: u32) -> u32 {
+                    core::ptr::read_volatile(addr as *const $ux) as u32
+                }
+
+                #[inline(always)]
+                unsafe fn write_volatile(addr: u32, value: u32) {
+                    core::ptr::write_volatile(addr as *mut $ux, value as $ux);
+                }
+            }
+        )+
+    };
+}
+
+impl_raw!(u8 => "ldrexb", "strexb", u16 => "ldrexh", "strexh", u32 => "ldrex", "strex");
+
+/// Read the register at the provided address.
+///
+/// *Note: With the `testing` feature, the access is
+/// performed on the mock memory map instead.*
+///
+/// # Safety
+///
+/// The address must be that of a readable register of
+/// width `R`, and reading it must not violate any
+/// entitlements or read effects relied upon elsewhere.
+#[inline(always)]
+pub unsafe fn read<R: Raw>(addr: u32) -> u32 {
+    #[cfg(feature = "testing")]
+    {
+        crate::testing::read(addr, core::mem::size_of::<R>())
+    }
+
+    #[cfg(not(feature = "testing"))]
+    {
+        R::read_volatile(addr)
+    }
+}
+
+/// Write the register at the provided address.
+///
+/// *Note: With the `testing` feature, the access is
+/// performed on the mock memory map instead.*
+///
+/// # Safety
+///
+/// The address must be that of a writable register of
+/// width `R`, and the written value must not violate any
+/// states or entitlements held elsewhere.
+#[inline(always)]
+pub unsafe fn write<R: Raw>(addr: u32, value: u32) {
+    #[cfg(feature = "testing")]
+    {
+        crate::testing::write(addr, core::mem::size_of::<R>(), value);
+    }
+
+    #[cfg(not(feature = "testing"))]
+    {
+        R::write_volatile(addr, value);
+    }
+}
+
+/// Replace the value of the register at the provided address
+/// with the value produced by `f` from the current value,
+/// without disabling interrupts.
+///
+/// On ARMv7-M (and later), the access is performed with exclusive
+/// load/store instructions, and `f` is invoked again with the new
+/// current value if another access intervenes. Otherwise, the access
+/// is performed within a critical section.
+///
+/// *Note: As the register may be read more than once, it must not
+/// have read effects. Its bus must also support exclusive accesses,
+/// or the store never succeeds.*
+///
+/// # Safety
+///
+/// The address must be that of a readable and writable register
+/// of width `R`, and the value produced by `f` must not violate any
+/// states or entitlements held elsewhere.
+#[inline(always)]
+pub unsafe fn modify_exclusive<R: Raw>(addr: u32, f: impl Fn(u32) -> u32) {
+    #[cfg(all(target_arch = "arm", target_feature = "v7", not(feature = "testing")))]
+    {
+        while !R::store_exclusive(addr, f(R::load_exclusive(addr))) {}
+    }
+
+    #[cfg(not(all(target_arch = "arm", target_feature = "v7", not(feature = "testing"))))]
+    {
+        critical_section::with(|_| write::<R>(addr, f(read::<R>(addr))));
+    }
+}
+
+/// Run `f` within a critical section.
+#[inline(always)]
+pub fn guarded<R>(f: impl FnOnce() -> R) -> R {
+    critical_section::with(|_| f())
+}
+
+/// Run `f` directly, as no other context can intervene.
+#[inline(always)]
+pub fn unguarded<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+/// Whether a block has been taken, shared by every
+/// set of peripherals holding the block.
+#[doc(hidden)]
+pub struct Claim(critical_section::Mutex<core::cell::Cell<bool>>);
+
+impl Claim {
+    pub const fn new() -> Self {
+        Self(critical_section::Mutex::new(core::cell::Cell::new(false)))
+    }
+
+    /// Whether the block has been taken.
+    pub fn is_claimed(&self, cs: critical_section::CriticalSection) -> bool {
+        self.0.borrow(cs).get()
+    }
+
+    /// Mark the block as taken.
+    pub fn claim(&self, cs: critical_section::CriticalSection) {
+        self.0.borrow(cs).set(true);
+    }
+}
+
+impl Default for Claim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether blocks are accessed through their non-secure
+/// aliases (if any), as determined by the `non-secure` feature.
+///
+/// *Note: This is the case for firmware running in the
+/// non-secure world of devices with TrustZone (ARMv8-M).*
+pub const NON_SECURE: bool = cfg!(feature = "non-secure");
+
+/// Remap the base address of a block.
+///
+/// *Note: With the `remap` feature, the address is remapped
+/// by the function installed with [`remap!`](crate::remap).*
+#[inline(always)]
+pub fn remap(addr: u32) -> u32 {
+    #[cfg(feature = "remap")]
+    {
+        extern "Rust" {
+            fn __proto_hal_remap(addr: u32) -> u32;
+        }
+
+        // SAFETY: the symbol is defined by `remap!` with this exact
+        // signature (or the binary fails to link), and the installed
+        // function is safe to call with any address
+        unsafe { __proto_hal_remap(addr) }
+    }
+
+    #[cfg(not(feature = "remap"))]
+    {
+        addr
+    }
+}
+
+/// Defines the `base_addr` function of a block, which is
+/// `const` unless the `remap` feature is enabled.
+#[cfg(not(feature = "remap"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __base_addr {
+    ($(#[$meta:meta])* => $addr:expr) => {
+        $(#[$meta])*
+        #[inline(always)]
+        pub const fn base_addr() -> u32 {
+            $addr
+        }
+    };
+}
+
+/// Defines the `base_addr` function of a block, which is
+/// `const` unless the `remap` feature is enabled.
+#[cfg(feature = "remap")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __base_addr {
+    ($(#[$meta:meta])* => $addr:expr) => {
+        $(#[$meta])*
+        #[inline(always)]
+        pub fn base_addr() -> u32 {
+            $crate::macro_utils::remap($addr)
+        }
+    };
+}
+
+/// Whether all registers are read back after every write,
+/// as determined by the `verify-writes` feature.
+///
+/// *Note: Registers described with `verify` are always
+/// read back, and panic on mismatches without this feature
+/// (see [`verify_write`]).*
+pub const VERIFY_WRITES: bool = cfg!(feature = "verify-writes");
+
+/// The stable fields of a register did not read back as written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteMismatch {
+    /// The address of the register.
+    pub addr: u32,
+    /// The value written, masked to the stable fields.
+    pub expected: u32,
+    /// The value read back, masked to the stable fields.
+    pub actual: u32,
+}
+
+/// Compare the value read back from a register against the
+/// value written, considering only the bits of `mask`.
+///
+/// *Note: With the `verify-writes` feature, mismatches are
+/// reported to the handler installed with
+/// [`write_mismatch_handler!`](crate::write_mismatch_handler),
+/// otherwise they panic.*
+#[inline(always)]
+pub fn verify_write(addr: u32, written: u32, read: u32, mask: u32) {
+    if (written ^ read) & mask == 0 {
+        return;
+    }
+
+    let mismatch = WriteMismatch {
+        addr,
+        expected: written & mask,
+        actual: read & mask,
+    };
+
+    #[cfg(feature = "verify-writes")]
+    {
+        extern "Rust" {
+            fn __proto_hal_write_mismatch(mismatch: WriteMismatch);
+        }
+
+        unsafe { __proto_hal_write_mismatch(mismatch) }
+    }
+
+    #[cfg(not(feature = "verify-writes"))]
+    {
+        panic!("register write mismatch: {:?}", mismatch);
+    }
+}
+
+/// A value did not correspond to any variant of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidValue(pub u32);
+
+/// A value written to a field violated one of its constraints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConstraintViolation {
+    /// The value is outside the permitted range.
+    OutOfRange,
+    /// The value is not a multiple of the required value.
+    NotMultiple,
+    /// The value is zero.
+    Zero,
+}
+
+/// Expands to the provided items only if the
+/// `introspection` feature is enabled.
+#[cfg(feature = "introspection")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __introspection {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+/// Expands to the provided items only if the
+/// `introspection` feature is enabled.
+#[cfg(not(feature = "introspection"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __introspection {
+    ($($item:item)*) => {};
+}
+
+/// Reports a register access to the installed tracer
+/// only if the `trace` feature is enabled.
+#[cfg(feature = "trace")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace {
+    ($kind:ident, $offset:expr, $addr:expr, $value:expr) => {
+        $crate::trace::record($crate::trace::Access {
+            kind: $crate::trace::AccessKind::$kind,
+            path: ::core::module_path!(),
+            offset: $offset,
+            addr: $addr,
+            value: $value,
+        })
+    };
+}
+
+/// Reports a register access to the installed tracer
+/// only if the `trace` feature is enabled.
+#[cfg(not(feature = "trace"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace {
+    ($kind:ident, $offset:expr, $addr:expr, $value:expr) => {};
+}
+
+/// Times a gate and aggregates its cost into the profile
+/// table only if the `profile` feature is enabled.
+#[cfg(feature = "profile")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __profile {
+    ($gate:ident, $body:block) => {{
+        let start = $crate::profile::cycles();
+        let result = $body;
+
+        $crate::profile::record(
+            $crate::profile::Gate::$gate,
+            ::core::module_path!(),
+            $crate::profile::cycles().wrapping_sub(start),
+        );
+
+        result
+    }};
+}
+
+/// Times a gate and aggregates its cost into the profile
+/// table only if the `profile` feature is enabled.
+#[cfg(not(feature = "profile"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __profile {
+    ($gate:ident, $body:block) => {
+        $body
+    };
+}
+
+/// Expands to the provided items only if the
+/// `embedded-hal` feature is enabled.
+#[cfg(feature = "embedded-hal")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __embedded_hal {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+/// Expands to the provided items only if the
+/// `embedded-hal` feature is enabled.
+#[cfg(not(feature = "embedded-hal"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __embedded_hal {
+    ($($item:item)*) => {};
+}
+
 pub trait AsBuilder: Into<Self::Builder> {
     type Builder;
 }
@@ -94,10 +548,81 @@ pub trait AsRegister: Into<Self::Register> {
     type Register;
 }
 
+/// Build the state transition of a register without touching
+/// hardware, to be written by [`commit`].
+pub fn stage<R: AsBuilder, B>(register: R, f: impl FnOnce(R::Builder) -> B) -> B {
+    f(register.into())
+}
+
+/// Write a built register state, completing the write before
+/// any subsequent memory access is performed.
+pub fn commit<B: AsRegister>(builder: B) -> B::Register {
+    let register = builder.into();
+    core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+    register
+}
+
+/// The position of a register in the order its configuration
+/// must be performed in, relative to the registers providing the
+/// entitlements it depends on (see [`configure!`](crate::configure)).
+pub trait Ordered {
+    /// One more than the depth of the deepest provider of an entitlement
+    /// to the register (or its block), or zero if there is none.
+    const DEPTH: usize;
+}
+
+/// The depth of a register (or block) inheriting `inherited`,
+/// and depending on providers of the provided depths.
+pub const fn depth(inherited: usize, providers: &[usize]) -> usize {
+    let mut depth = inherited;
+    let mut i = 0;
+
+    while i < providers.len() {
+        if providers[i] + 1 > depth {
+            depth = providers[i] + 1;
+        }
+
+        i += 1;
+    }
+
+    depth
+}
+
+/// The depth of the provided register.
+pub fn depth_of<R: Ordered>(_register: &R) -> usize {
+    R::DEPTH
+}
+
 // Type-state indicating the state cannot
 // be statically determined currently.
 pub struct Unresolved;
 
 pub trait Writer {
+    /// Modify the raw value to be written.
+    ///
+    /// # Safety
+    ///
+    /// The modified value must not violate the states
+    /// or entitlements the written fields depend on.
     unsafe fn write(&mut self, f: impl FnOnce(&mut u32)) -> &mut Self;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::round;
+
+    #[test]
+    fn round_away_from_zero() {
+        assert_eq!(round(2.5), 3);
+        assert_eq!(round(2.49), 2);
+        assert_eq!(round(-2.5), -3);
+        assert_eq!(round(-2.49), -2);
+        assert_eq!(round(0.49999999999999994), 0);
+    }
+
+    #[test]
+    fn round_beyond_f32_precision() {
+        assert_eq!(round(16_777_217.), 16_777_217);
+        assert_eq!(round(4_294_967_295.4), 4_294_967_295);
+    }
+}
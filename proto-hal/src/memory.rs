@@ -0,0 +1,169 @@
+//! Descriptions of the memory map of a device.
+//!
+//! A memory map lists the blocks of a device alongside its RAM
+//! regions (i.e. SRAM2, CCM), so drivers can check whether buffers
+//! reside in memory accessible to DMA. Statics are placed in the
+//! linker section of a region with [`place!`](crate::place).
+//!
+//! ```ignore
+//! proto_hal::memory_map! {
+//!     pub mod memory_map {
+//!         peripherals {
+//!             rcc: crate::rcc,
+//!             cordic: crate::cordic,
+//!         }
+//!
+//!         regions {
+//!             sram1: 0x2000_0000, 0x1_4000, dma;
+//!             ccm: 0x1000_0000, 0x8000;
+//!         }
+//!     }
+//! }
+//!
+//! assert!(memory_map::is_dma_capable(&buffer));
+//! ```
+//!
+//! Functions are placed in fast memories (i.e. CCM, ITCM) with
+//! [`ram_func`](crate::ram_func), given the memories are described
+//! to `proto_hal_build::memory` in the build script.
+
+use core::ops::{Deref, DerefMut};
+
+/// A block within the memory map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Peripheral {
+    pub name: &'static str,
+    pub base: u32,
+    /// The size (in bytes) of the address space occupied by the block.
+    pub size: u32,
+}
+
+/// A region of memory within the memory map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Region {
+    pub name: &'static str,
+    pub base: u32,
+    /// The size (in bytes) of the region.
+    pub size: u32,
+    /// Whether the region is accessible to DMA.
+    pub dma: bool,
+}
+
+impl Region {
+    /// Whether the `len` bytes at `addr` lie within this region.
+    pub const fn contains(&self, addr: u64, len: u64) -> bool {
+        addr >= self.base as u64 && addr + len <= self.base as u64 + self.size as u64
+    }
+
+    /// Whether the provided buffer lies within this region.
+    pub fn contains_buffer<T: ?Sized>(&self, buffer: &T) -> bool {
+        self.contains(
+            buffer as *const T as *const u8 as usize as u64,
+            core::mem::size_of_val(buffer) as u64,
+        )
+    }
+}
+
+/// Copy the words at `load` into the section spanning from `start`
+/// to `end` (i.e. functions placed in a fast memory, loaded from flash).
+///
+/// Invoked by [`init_fast_memory!`](crate::init_fast_memory) with the
+/// symbols of the memory.
+///
+/// # Safety
+///
+/// The pointers must delimit a word-aligned section with a load image
+/// of the same length, and nothing in the section may be in use.
+pub unsafe fn init_section(start: *mut u32, end: *mut u32, load: *const u32) {
+    let len = (end as usize - start as usize) / core::mem::size_of::<u32>();
+
+    core::ptr::copy_nonoverlapping(load, start, len);
+
+    // the section must be populated before anything within is used
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Maps an alignment to a type of that alignment.
+pub trait Alignment {
+    type Archetype: Copy;
+}
+
+/// An alignment (in bytes).
+pub struct Align<const N: usize>;
+
+macro_rules! impl_alignment {
+    ($($archetype:ident = $n:literal),+) => {
+        $(
+            #[doc(hidden)]
+            #[derive(Clone, Copy)]
+            #[repr(align($n))]
+            pub struct $archetype;
+
+            impl Alignment for Align<$n> {
+                type Archetype = $archetype;
+            }
+        )+
+    };
+}
+
+impl_alignment!(
+    A1 = 1,
+    A2 = 2,
+    A4 = 4,
+    A8 = 8,
+    A16 = 16,
+    A32 = 32,
+    A64 = 64,
+    A128 = 128,
+    A256 = 256,
+    A512 = 512,
+    A1024 = 1024,
+    A2048 = 2048,
+    A4096 = 4096
+);
+
+/// A value aligned to `N` bytes (i.e. a DMA buffer which
+/// must be aligned to its size or a cache line).
+#[repr(C)]
+pub struct Aligned<const N: usize, T>
+where
+    Align<N>: Alignment,
+{
+    _align: [<Align<N> as Alignment>::Archetype; 0],
+    value: T,
+}
+
+impl<const N: usize, T> Aligned<N, T>
+where
+    Align<N>: Alignment,
+{
+    pub const fn new(value: T) -> Self {
+        Self { _align: [], value }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<const N: usize, T> Deref for Aligned<N, T>
+where
+    Align<N>: Alignment,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<const N: usize, T> DerefMut for Aligned<N, T>
+where
+    Align<N>: Alignment,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
@@ -0,0 +1,213 @@
+//! The version of the semantics of device descriptions.
+//!
+//! Device descriptions are interpreted by the `#[block]` attribute
+//! macros, so the meaning of a description (i.e. entitlement rules,
+//! access modalities) is that of the proto-hal it is compiled against.
+//! Generated HALs assert the version they were written for with
+//! [`require_model!`](crate::require_model), which fails to compile
+//! against incompatible versions rather than silently changing behavior.
+//!
+//! The major version is incremented whenever an existing description
+//! would be interpreted differently, and the minor version whenever
+//! descriptive capabilities are added.
+
+use core::fmt;
+
+/// A semantic version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Version {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+impl Version {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse a version of the form `major[.minor[.patch]]`,
+    /// where omitted components are zero.
+    pub const fn parse(s: &str) -> Option<Self> {
+        let bytes = s.as_bytes();
+
+        let mut components = [0u16; 3];
+        let mut component = 0;
+        let mut digits = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'.' if digits != 0 && component < 2 => {
+                    component += 1;
+                    digits = 0;
+                }
+                byte @ b'0'..=b'9' => {
+                    let Some(value) = components[component].checked_mul(10) else {
+                        return None;
+                    };
+                    let Some(value) = value.checked_add((byte - b'0') as u16) else {
+                        return None;
+                    };
+
+                    components[component] = value;
+                    digits += 1;
+                }
+                _ => return None,
+            }
+
+            i += 1;
+        }
+
+        if digits == 0 {
+            return None;
+        }
+
+        Some(Self::new(components[0], components[1], components[2]))
+    }
+
+    /// Whether descriptions written for the `required` version
+    /// have the same meaning under this version.
+    ///
+    /// As with cargo, the leftmost non-zero component must
+    /// match, and this version must be no older.
+    pub const fn is_compatible_with(&self, required: &Self) -> bool {
+        let same_series = if required.major != 0 {
+            self.major == required.major
+        } else if required.minor != 0 {
+            self.major == 0 && self.minor == required.minor
+        } else {
+            self.major == 0 && self.minor == 0 && self.patch == required.patch
+        };
+
+        let no_older = self.major > required.major
+            || (self.major == required.major
+                && (self.minor > required.minor
+                    || (self.minor == required.minor && self.patch >= required.patch)));
+
+        same_series && no_older
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// The version of the semantics implemented by this proto-hal.
+pub const VERSION: Version = Version::new(1, 0, 0);
+
+/// A required version the semantics are not compatible with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Incompatibility {
+    /// The required version is not a valid version.
+    Malformed,
+    /// The required version is newer than [`VERSION`].
+    TooOld,
+    /// The required version belongs to a different series than [`VERSION`].
+    Incompatible,
+}
+
+impl Incompatibility {
+    /// A message explaining the incompatibility.
+    pub const fn message(&self) -> &'static str {
+        match self {
+            Self::Malformed => "the required model version is malformed (expected `major[.minor[.patch]]`)",
+            Self::TooOld => "the model semantics of proto-hal are older than required, update proto-hal",
+            Self::Incompatible => "the model semantics of proto-hal are incompatible with those required, the device description must be migrated",
+        }
+    }
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+/// Check the semantics implemented by this proto-hal are
+/// compatible with the `required` version.
+pub const fn check_compat(required: &str) -> Result<(), Incompatibility> {
+    let Some(required) = Version::parse(required) else {
+        return Err(Incompatibility::Malformed);
+    };
+
+    if VERSION.is_compatible_with(&required) {
+        Ok(())
+    } else if VERSION.major == required.major
+        && (VERSION.major != 0 || VERSION.minor == required.minor)
+    {
+        Err(Incompatibility::TooOld)
+    } else {
+        Err(Incompatibility::Incompatible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_compat, Incompatibility, Version, VERSION};
+
+    #[test]
+    fn parse() {
+        assert_eq!(Version::parse("1"), Some(Version::new(1, 0, 0)));
+        assert_eq!(Version::parse("1.2"), Some(Version::new(1, 2, 0)));
+        assert_eq!(Version::parse("1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(
+            Version::parse("0.10.65535"),
+            Some(Version::new(0, 10, 65535))
+        );
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for s in [
+            "", ".", "1.", ".1", "1..2", "1.2.3.4", "v1", "1.2-rc", "65536",
+        ] {
+            assert_eq!(Version::parse(s), None, "{s:?}");
+        }
+    }
+
+    #[test]
+    fn compatibility() {
+        let compatible = |version: &str, required: &str| {
+            Version::parse(version)
+                .unwrap()
+                .is_compatible_with(&Version::parse(required).unwrap())
+        };
+
+        assert!(compatible("1.2.3", "1"));
+        assert!(compatible("1.2.3", "1.2.3"));
+        assert!(!compatible("1.2.3", "1.3"));
+        assert!(!compatible("2.0.0", "1"));
+        assert!(!compatible("1.0.0", "2"));
+
+        // the minor version is the series before 1.0
+        assert!(compatible("0.2.1", "0.2"));
+        assert!(!compatible("0.3.0", "0.2"));
+
+        // and the patch version before 0.1
+        assert!(compatible("0.0.3", "0.0.3"));
+        assert!(!compatible("0.0.4", "0.0.3"));
+    }
+
+    #[test]
+    fn check() {
+        // the cases below are relative to this version
+        assert_eq!(VERSION, Version::new(1, 0, 0));
+
+        assert_eq!(check_compat("1.0.0"), Ok(()));
+        assert_eq!(check_compat("1"), Ok(()));
+        assert_eq!(check_compat("1.0.1"), Err(Incompatibility::TooOld));
+        assert_eq!(check_compat("1.1"), Err(Incompatibility::TooOld));
+        assert_eq!(check_compat("2"), Err(Incompatibility::Incompatible));
+        assert_eq!(check_compat("0.9"), Err(Incompatibility::Incompatible));
+        assert_eq!(check_compat("1.x"), Err(Incompatibility::Malformed));
+    }
+}
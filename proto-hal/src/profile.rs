@@ -0,0 +1,250 @@
+//! Cycle profiling of the gates performed by generated code.
+//!
+//! When the `profile` feature is enabled, every state transition,
+//! write, and modify performed by generated register interfaces is
+//! timed with a cycle counter (by default, the Cortex-M DWT cycle
+//! counter) and aggregated into a static table of [`Entry`]s.
+//!
+//! ```ignore
+//! unsafe { proto_hal::profile::enable_dwt() };
+//!
+//! configure();
+//!
+//! proto_hal::profile::entries(|entries| {
+//!     for entry in entries {
+//!         defmt::info!("{}: {} cycles", entry.path, entry.mean());
+//!     }
+//! });
+//! ```
+
+use core::{
+    cell::RefCell,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use critical_section::Mutex;
+
+/// The kind of gate performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Gate {
+    Transition,
+    Write,
+    Modify,
+}
+
+/// The aggregated cost of a gate of a register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Entry {
+    pub gate: Gate,
+    /// The module path of the register (i.e. `my_hal::cordic::csr`).
+    pub path: &'static str,
+    /// The number of times the gate was performed (saturating).
+    pub count: u32,
+    /// The total number of cycles spent performing the gate.
+    pub total: u64,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl Entry {
+    const fn new(gate: Gate, path: &'static str) -> Self {
+        Self {
+            gate,
+            path,
+            count: 0,
+            total: 0,
+            min: u32::MAX,
+            max: 0,
+        }
+    }
+
+    /// The mean number of cycles spent performing the gate.
+    pub fn mean(&self) -> u32 {
+        (self.total / self.count.max(1) as u64) as u32
+    }
+}
+
+/// The number of distinct gates which can be profiled.
+///
+/// *Note: Gates beyond this are counted by [`dropped`], not profiled.*
+pub const CAPACITY: usize = 64;
+
+struct Table {
+    entries: [Entry; CAPACITY],
+    len: usize,
+    dropped: u32,
+}
+
+static TABLE: Mutex<RefCell<Table>> = Mutex::new(RefCell::new(Table {
+    entries: [Entry::new(Gate::Write, ""); CAPACITY],
+    len: 0,
+    dropped: 0,
+}));
+
+/// A function yielding the current cycle count.
+pub type Counter = fn() -> u32;
+
+static COUNTER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Install a cycle counter, replacing the DWT cycle counter
+/// (i.e. for targets without one).
+pub fn set_counter(counter: Counter) {
+    COUNTER.store(counter as *mut (), Ordering::Release);
+}
+
+/// Enable the DWT cycle counter.
+///
+/// # Safety
+///
+/// The target must be a Cortex-M with a DWT unit, and the
+/// debug registers must not be configured concurrently.
+pub unsafe fn enable_dwt() {
+    const DEMCR: u32 = 0xe000_edfc;
+    const DWT_CTRL: u32 = 0xe000_1000;
+
+    // TRCENA
+    crate::macro_utils::write::<u32>(DEMCR, crate::macro_utils::read::<u32>(DEMCR) | 1 << 24);
+    // CYCCNTENA
+    crate::macro_utils::write::<u32>(DWT_CTRL, crate::macro_utils::read::<u32>(DWT_CTRL) | 1);
+}
+
+/// The current cycle count, as reported by the installed
+/// counter (if any) or the DWT cycle counter.
+pub fn cycles() -> u32 {
+    let counter = COUNTER.load(Ordering::Acquire);
+
+    if !counter.is_null() {
+        // SAFETY: the only non-null values stored
+        // are `Counter` pointers (see `set_counter`)
+        let counter = unsafe { core::mem::transmute::<*mut (), Counter>(counter) };
+
+        return counter();
+    }
+
+    #[cfg(target_arch = "arm")]
+    {
+        const DWT_CYCCNT: u32 = 0xe000_1004;
+
+        // SAFETY: reading the cycle counter has no side effects
+        unsafe { core::ptr::read_volatile(DWT_CYCCNT as *const u32) }
+    }
+
+    #[cfg(not(target_arch = "arm"))]
+    {
+        0
+    }
+}
+
+/// Aggregate the cost of a gate into the table.
+#[doc(hidden)]
+pub fn record(gate: Gate, path: &'static str, cycles: u32) {
+    critical_section::with(|cs| {
+        let mut table = TABLE.borrow_ref_mut(cs);
+        let table = &mut *table;
+
+        let index = match table.entries[..table.len]
+            .iter()
+            .position(|entry| entry.gate == gate && entry.path == path)
+        {
+            Some(index) => index,
+            None if table.len < CAPACITY => {
+                table.entries[table.len] = Entry::new(gate, path);
+                table.len += 1;
+
+                table.len - 1
+            }
+            None => {
+                table.dropped = table.dropped.saturating_add(1);
+
+                return;
+            }
+        };
+
+        let entry = &mut table.entries[index];
+
+        entry.count = entry.count.saturating_add(1);
+        entry.total = entry.total.saturating_add(cycles as u64);
+        entry.min = entry.min.min(cycles);
+        entry.max = entry.max.max(cycles);
+    });
+}
+
+/// Inspect the profiled gates, in order of first occurrence.
+///
+/// *Note: The entries are copied out of the table (onto the stack),
+/// so `f` is not called within a critical section.*
+pub fn entries<R>(f: impl FnOnce(&[Entry]) -> R) -> R {
+    let (entries, len) = critical_section::with(|cs| {
+        let table = TABLE.borrow_ref(cs);
+
+        (table.entries, table.len)
+    });
+
+    f(&entries[..len])
+}
+
+/// The number of gates performed which did not fit in the table.
+pub fn dropped() -> u32 {
+    critical_section::with(|cs| TABLE.borrow_ref(cs).dropped)
+}
+
+/// Clear the table.
+pub fn reset() {
+    critical_section::with(|cs| {
+        let mut table = TABLE.borrow_ref_mut(cs);
+
+        table.len = 0;
+        table.dropped = 0;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cycles, entries, record, set_counter, Entry, Gate};
+
+    /// The entry of the gate, if profiled.
+    fn entry(gate: Gate, path: &'static str) -> Option<Entry> {
+        entries(|entries| {
+            entries
+                .iter()
+                .find(|entry| entry.gate == gate && entry.path == path)
+                .copied()
+        })
+    }
+
+    #[test]
+    fn record_aggregates() {
+        // the table is shared by all tests, so
+        // each test profiles gates of its own paths
+        record(Gate::Write, "profile::record_aggregates", 10);
+        record(Gate::Write, "profile::record_aggregates", 30);
+        record(Gate::Modify, "profile::record_aggregates", 5);
+
+        let write = entry(Gate::Write, "profile::record_aggregates").unwrap();
+
+        assert_eq!(write.count, 2);
+        assert_eq!(write.total, 40);
+        assert_eq!(write.min, 10);
+        assert_eq!(write.max, 30);
+        assert_eq!(write.mean(), 20);
+
+        let modify = entry(Gate::Modify, "profile::record_aggregates").unwrap();
+
+        assert_eq!(modify.count, 1);
+        assert_eq!(modify.mean(), 5);
+    }
+
+    #[test]
+    fn mean_of_unperformed() {
+        assert_eq!(Entry::new(Gate::Transition, "").mean(), 0);
+    }
+
+    #[test]
+    fn installed_counter() {
+        set_counter(|| 42);
+
+        assert_eq!(cycles(), 42);
+    }
+}
@@ -5,6 +5,18 @@ use core::{
 
 /// A trait providing an interface to freeze stateful types.
 pub trait Freeze: Sized {
+    /// The depth of the register holding this type (if any),
+    /// so registers depending on it are configured after it.
+    ///
+    /// See [`Ordered`](crate::macro_utils::Ordered).
+    const DEPTH: usize = 0;
+
+    /// Whether this type can be reached by software, which is
+    /// not the case for states of fields which can only be
+    /// written while holding unreachable states of other blocks
+    /// (i.e. blocks in a power domain that is never enabled).
+    const REACHABLE: bool = true;
+
     fn freeze<const N: usize>(self) -> (Frozen<Self, N>, [Entitlement<Self>; N]) {
         (
             Frozen { resource: self },
@@ -31,6 +43,40 @@ where
     }
 }
 
+impl<Resource> Entitlement<Resource>
+where
+    Resource: Freeze,
+{
+    /// Narrow an entitlement to a holder of states (i.e. a register)
+    /// into an entitlement to one of the states it holds.
+    ///
+    /// The state (and its position within the holder, `Index`) is
+    /// inferred from context.
+    pub fn narrow<State, Index>(self) -> Entitlement<State>
+    where
+        State: Freeze,
+        Resource: Holds<State, Index>,
+    {
+        Entitlement { _p: PhantomData }
+    }
+}
+
+/// Indicates a type holds the state token `State`,
+/// where `Index` identifies the position of the state
+/// within the holder (i.e. the field of a register).
+///
+/// # Safety
+///
+/// If a type implements this trait
+/// erroneously, narrowed entitlements
+/// will be invalid.
+pub unsafe trait Holds<State, Index> {}
+
+/// The position of a state token holding itself.
+pub struct Itself;
+
+unsafe impl<State: Freeze> Holds<State, Itself> for State {}
+
 /// A struct to hold stateful types where
 /// the state is frozen.
 pub struct Frozen<Resource, const ENTITLEMENTS: usize>
@@ -55,13 +101,219 @@ impl<Resource: Freeze, const ENTITLEMENTS: usize> DerefMut for Frozen<Resource,
 }
 
 impl<Resource: Freeze, const ENTITLEMENTS: usize> Frozen<Resource, ENTITLEMENTS> {
-    pub fn release(self, entitlements: [Entitlement<Resource>; ENTITLEMENTS]) -> Resource {
-        drop(entitlements);
-
+    pub fn release(self, _entitlements: [Entitlement<Resource>; ENTITLEMENTS]) -> Resource {
         self.resource
     }
 }
 
+/// A struct to hold stateful types where the state
+/// is frozen while entitlements are outstanding.
+///
+/// Unlike [`Frozen`], the number of entitlements is counted
+/// at runtime, so entitlements can be issued and revoked
+/// dynamically (i.e. as tasks come and go).
+///
+/// The resource is only reachable by shared reference, so it
+/// cannot be transitioned until it is released:
+///
+/// ```compile_fail
+/// use proto_hal::stasis::{Freeze, Shared};
+///
+/// struct Token;
+/// impl Freeze for Token {}
+///
+/// fn transition(token: Token) -> Token {
+///     token
+/// }
+///
+/// let shared = Shared::new(Token);
+/// transition(*shared);
+/// ```
+pub struct Shared<Resource>
+where
+    Resource: Freeze,
+{
+    resource: Resource,
+    id: u32,
+    outstanding: usize,
+}
+
+/// The identifier of the next [`Shared`] resource.
+static NEXT_ID: critical_section::Mutex<core::cell::Cell<u32>> =
+    critical_section::Mutex::new(core::cell::Cell::new(0));
+
+impl<Resource: Freeze> Shared<Resource> {
+    /// Freeze a resource with no outstanding entitlements.
+    ///
+    /// # Panics
+    ///
+    /// If more than `u32::MAX` resources have been shared.
+    pub fn new(resource: Resource) -> Self {
+        let id = critical_section::with(|cs| {
+            let next = NEXT_ID.borrow(cs);
+            let id = next.get();
+
+            next.set(
+                id.checked_add(1)
+                    .expect("shared resource identifiers exhausted"),
+            );
+
+            id
+        });
+
+        Self {
+            resource,
+            id,
+            outstanding: 0,
+        }
+    }
+
+    /// Issue an entitlement to the frozen resource.
+    ///
+    /// # Panics
+    ///
+    /// If `usize::MAX` entitlements are outstanding.
+    pub fn entitle(&mut self) -> Lease<Resource> {
+        self.outstanding = self
+            .outstanding
+            .checked_add(1)
+            .expect("outstanding entitlements overflow usize");
+
+        Lease {
+            shared: self.id,
+            entitlement: Entitlement { _p: PhantomData },
+        }
+    }
+
+    /// Return an entitlement to the frozen resource.
+    ///
+    /// If the entitlement was not issued by this resource,
+    /// it is returned unchanged.
+    pub fn revoke(&mut self, lease: Lease<Resource>) -> Result<(), Lease<Resource>> {
+        if lease.shared != self.id {
+            return Err(lease);
+        }
+
+        // a lease issued by this resource is counted as outstanding
+        self.outstanding = self.outstanding.checked_sub(1).unwrap();
+
+        Ok(())
+    }
+
+    /// The number of entitlements yet to be returned.
+    pub fn outstanding(&self) -> usize {
+        self.outstanding
+    }
+
+    /// Unfreeze the resource if no entitlements are outstanding.
+    pub fn release(self) -> Result<Resource, Self> {
+        if self.outstanding == 0 {
+            Ok(self.resource)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<Resource: Freeze> Deref for Shared<Resource> {
+    type Target = Resource;
+
+    fn deref(&self) -> &Self::Target {
+        &self.resource
+    }
+}
+
+/// An entitlement issued by a [`Shared`] resource,
+/// which can only be revoked by the resource that
+/// issued it.
+///
+/// The entitlement is borrowed from the lease
+/// to satisfy gates.
+pub struct Lease<Resource>
+where
+    Resource: Freeze,
+{
+    shared: u32,
+    entitlement: Entitlement<Resource>,
+}
+
+impl<Resource: Freeze> Deref for Lease<Resource> {
+    type Target = Entitlement<Resource>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entitlement
+    }
+}
+
+/// A register which is only exposed while a bank selector
+/// holds the state `Bank` (i.e. one of several register sets
+/// sharing an address).
+pub struct Banked<Register, Bank>
+where
+    Bank: Freeze,
+{
+    register: Register,
+    _bank: PhantomData<Bank>,
+}
+
+impl<Register, Bank: Freeze> Banked<Register, Bank> {
+    /// Select the bank of this register, holding the
+    /// entitlement to the selector state for as long
+    /// as the register is selected.
+    pub fn select(self, bank: Entitlement<Bank>) -> Selected<Register, Bank> {
+        Selected {
+            register: self.register,
+            bank,
+        }
+    }
+}
+
+/// A banked register whose bank is selected.
+pub struct Selected<Register, Bank>
+where
+    Bank: Freeze,
+{
+    register: Register,
+    bank: Entitlement<Bank>,
+}
+
+impl<Register, Bank: Freeze> Selected<Register, Bank> {
+    /// Consume the selected register (i.e. to perform a state
+    /// transition), keeping the bank selected.
+    pub fn map<R>(self, f: impl FnOnce(Register) -> R) -> Selected<R, Bank> {
+        Selected {
+            register: f(self.register),
+            bank: self.bank,
+        }
+    }
+
+    /// Deselect the bank of this register, returning
+    /// the entitlement to the selector state.
+    pub fn deselect(self) -> (Banked<Register, Bank>, Entitlement<Bank>) {
+        (
+            Banked {
+                register: self.register,
+                _bank: PhantomData,
+            },
+            self.bank,
+        )
+    }
+}
+
+impl<Register, Bank: Freeze> Deref for Selected<Register, Bank> {
+    type Target = Register;
+
+    fn deref(&self) -> &Self::Target {
+        &self.register
+    }
+}
+
+impl<Register, Bank: Freeze> DerefMut for Selected<Register, Bank> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.register
+    }
+}
+
 pub trait EntitlementLock: Sized {
     type Resource: Freeze;
 }
@@ -87,3 +339,353 @@ pub unsafe trait Entitled<State> {}
 /// A marker type for
 /// an unsatisfied entitlement.
 pub struct Unsatisfied;
+
+/// Indicates a type-state emits
+/// the internal trigger `Trigger`.
+///
+/// # Safety
+///
+/// If a type implements this trait
+/// erroneously, established routes
+/// will be invalid.
+pub unsafe trait Emits<Trigger> {}
+
+/// Indicates a type-state selects
+/// the internal trigger `Trigger`
+/// as an input.
+///
+/// # Safety
+///
+/// If a type implements this trait
+/// erroneously, established routes
+/// will be invalid.
+pub unsafe trait Connects<Trigger> {}
+
+/// A token representing an established route of the
+/// internal trigger `Trigger` from the state emitting
+/// it to the state selecting it.
+///
+/// The states are borrowed so they cannot change
+/// while the route is held.
+pub struct Route<'a, Trigger, Source, Sink> {
+    _source: PhantomData<&'a Source>,
+    _sink: PhantomData<&'a Sink>,
+    _trigger: PhantomData<fn() -> Trigger>,
+}
+
+impl<'a, Trigger, Source, Sink> Route<'a, Trigger, Source, Sink>
+where
+    Source: Emits<Trigger>,
+    Sink: Connects<Trigger>,
+{
+    /// Establish the route of the trigger from `source` to `sink`.
+    pub fn new(_source: &'a Source, _sink: &'a Sink) -> Self {
+        Self {
+            _source: PhantomData,
+            _sink: PhantomData,
+            _trigger: PhantomData,
+        }
+    }
+}
+
+/// A guard holding a state token, which is returned
+/// to its prior state when the guard is dropped.
+///
+/// The prior state token is placed in the slot the
+/// guard was created with.
+pub struct Scoped<'a, State, Prior, Exit>
+where
+    Exit: FnOnce(State) -> Prior,
+{
+    state: Option<State>,
+    exit: Option<Exit>,
+    slot: &'a mut Option<Prior>,
+}
+
+impl<'a, State, Prior, Exit> Scoped<'a, State, Prior, Exit>
+where
+    Exit: FnOnce(State) -> Prior,
+{
+    /// Guard a state token, returning it to its
+    /// prior state with `exit` when dropped.
+    pub fn new(state: State, exit: Exit, slot: &'a mut Option<Prior>) -> Self {
+        Self {
+            state: Some(state),
+            exit: Some(exit),
+            slot,
+        }
+    }
+}
+
+impl<State, Prior, Exit> Deref for Scoped<'_, State, Prior, Exit>
+where
+    Exit: FnOnce(State) -> Prior,
+{
+    type Target = State;
+
+    fn deref(&self) -> &Self::Target {
+        // the state is only taken when dropped
+        self.state.as_ref().unwrap()
+    }
+}
+
+impl<State, Prior, Exit> DerefMut for Scoped<'_, State, Prior, Exit>
+where
+    Exit: FnOnce(State) -> Prior,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // the state is only taken when dropped
+        self.state.as_mut().unwrap()
+    }
+}
+
+impl<State, Prior, Exit> Drop for Scoped<'_, State, Prior, Exit>
+where
+    Exit: FnOnce(State) -> Prior,
+{
+    fn drop(&mut self) {
+        if let (Some(state), Some(exit)) = (self.state.take(), self.exit.take()) {
+            self.slot.replace(exit(state));
+        }
+    }
+}
+
+/// Transition a state token with `enter`, run `f` with the
+/// new state, and transition back with `exit`, yielding the
+/// prior state token and the result of `f`.
+///
+/// See [`with_state!`](crate::with_state) for use with fields.
+pub fn scoped<Prior, State, R>(
+    prior: Prior,
+    enter: impl FnOnce(Prior) -> State,
+    exit: impl FnOnce(State) -> Prior,
+    f: impl FnOnce(&mut State) -> R,
+) -> (Prior, R) {
+    let mut slot = None;
+
+    let result = {
+        let mut guard = Scoped::new(enter(prior), exit, &mut slot);
+
+        f(&mut guard)
+    };
+
+    // the guard has been dropped, restoring the prior state
+    (slot.unwrap(), result)
+}
+
+/// Numeric values known at compile time (i.e. the value
+/// written to a numeric field), which can be computed with
+/// at the type level.
+///
+/// ```ignore
+/// type Brr = Quotient<Frequency<Sysclk>, Value<115_200>>;
+///
+/// stasis::prove::<LessOrEqual<Brr, Value<0xffff>>>();
+/// ```
+pub trait Numeric {
+    const VALUE: u32;
+}
+
+/// The value `V`.
+pub struct Value<const V: u32>;
+
+impl<const V: u32> Numeric for Value<V> {
+    const VALUE: u32 = V;
+}
+
+/// The frequency produced by the clock source `S`, in hertz.
+pub struct Frequency<S> {
+    _p: PhantomData<S>,
+}
+
+impl<S: crate::clock::Source> Numeric for Frequency<S> {
+    const VALUE: u32 = S::HZ;
+}
+
+macro_rules! arithmetic {
+    ($($(#[$meta:meta])* $ident:ident => $op:ident, $msg:literal;)+) => {
+        $(
+            $(#[$meta])*
+            pub struct $ident<A, B> {
+                _p: PhantomData<(A, B)>,
+            }
+
+            impl<A: Numeric, B: Numeric> Numeric for $ident<A, B> {
+                const VALUE: u32 = match A::VALUE.$op(B::VALUE) {
+                    Some(value) => value,
+                    None => panic!($msg),
+                };
+            }
+        )+
+    };
+}
+
+arithmetic! {
+    /// The value of `A + B`.
+    Sum => checked_add, "sum overflows u32";
+    /// The value of `A - B`.
+    Difference => checked_sub, "difference underflows u32";
+    /// The value of `A * B`.
+    Product => checked_mul, "product overflows u32";
+    /// The value of `A / B` (rounded down).
+    Quotient => checked_div, "quotient divides by zero";
+    /// The value of `A % B`.
+    Remainder => checked_rem, "remainder divides by zero";
+}
+
+/// A relation between values which may hold.
+pub trait Relation {
+    const HOLDS: bool;
+}
+
+macro_rules! relations {
+    ($($(#[$meta:meta])* $ident:ident => $op:tt;)+) => {
+        $(
+            $(#[$meta])*
+            pub struct $ident<A, B> {
+                _p: PhantomData<(A, B)>,
+            }
+
+            impl<A: Numeric, B: Numeric> Relation for $ident<A, B> {
+                const HOLDS: bool = A::VALUE $op B::VALUE;
+            }
+        )+
+    };
+}
+
+relations! {
+    /// `A == B`
+    Equal => ==;
+    /// `A != B`
+    NotEqual => !=;
+    /// `A < B`
+    Less => <;
+    /// `A <= B`
+    LessOrEqual => <=;
+    /// `A > B`
+    Greater => >;
+    /// `A >= B`
+    GreaterOrEqual => >=;
+}
+
+/// The value of `N`.
+pub const fn value<N: Numeric>() -> u32 {
+    N::VALUE
+}
+
+/// Prove the relation `R` holds, failing
+/// compilation if it does not.
+pub const fn prove<R: Relation>() {
+    const { assert!(R::HOLDS, "relation does not hold") }
+}
+
+/// Markers of the execution contexts (i.e. threads, interrupts)
+/// the tokens of a register may be moved or shared between.
+pub mod context {
+    /// The tokens may be moved and shared between contexts (`Send + Sync`).
+    pub type Shared = ();
+
+    /// The tokens may be moved between contexts, but not shared (`Send + !Sync`).
+    pub type Movable = core::cell::Cell<()>;
+
+    /// The tokens are bound to the context they were
+    /// created in (`!Send + !Sync`), i.e. core-local registers.
+    pub type Local = *const ();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        prove, scoped, value, Entitlement, Freeze, Itself, Less, Product, Shared, Sum, Value,
+    };
+
+    struct Token;
+
+    impl Freeze for Token {}
+
+    fn gate(_entitlement: &Entitlement<Token>) {}
+
+    #[test]
+    fn shared_release() {
+        let mut shared = Shared::new(Token);
+
+        let a = shared.entitle();
+        let b = shared.entitle();
+        assert_eq!(shared.outstanding(), 2);
+
+        gate(&a);
+
+        assert!(shared.revoke(a).is_ok());
+        let Err(mut shared) = shared.release() else {
+            panic!("released with an outstanding entitlement");
+        };
+
+        assert!(shared.revoke(b).is_ok());
+        assert_eq!(shared.outstanding(), 0);
+        assert!(shared.release().is_ok());
+    }
+
+    #[test]
+    fn shared_revoke_foreign() {
+        let mut a = Shared::new(Token);
+        let mut b = Shared::new(Token);
+
+        let lease = b.entitle();
+
+        let Err(lease) = a.revoke(lease) else {
+            panic!("revoked an entitlement issued elsewhere");
+        };
+        assert_eq!(a.outstanding(), 0);
+        assert_eq!(b.outstanding(), 1);
+
+        assert!(b.revoke(lease).is_ok());
+        assert_eq!(b.outstanding(), 0);
+    }
+
+    #[test]
+    fn frozen_release() {
+        let (frozen, entitlements) = Token.freeze::<2>();
+
+        for entitlement in &entitlements {
+            gate(entitlement);
+        }
+
+        let _: &Token = &frozen;
+        let Token = frozen.release(entitlements);
+    }
+
+    #[test]
+    fn narrow_itself() {
+        let entitlement = Entitlement::from(Token).narrow::<Token, Itself>();
+
+        gate(&entitlement);
+    }
+
+    struct Enabled(u8);
+
+    struct Disabled(u8);
+
+    #[test]
+    fn scoped_restores_prior() {
+        let (prior, result) = scoped(
+            Disabled(0),
+            |Disabled(n)| Enabled(n + 1),
+            |Enabled(n)| Disabled(n + 1),
+            |state| {
+                state.0 *= 10;
+                state.0
+            },
+        );
+
+        assert_eq!(result, 10);
+        assert_eq!(prior.0, 11);
+    }
+
+    #[test]
+    fn arithmetic() {
+        type Brr = Product<Sum<Value<2>, Value<3>>, Value<4>>;
+
+        assert_eq!(value::<Brr>(), 20);
+        prove::<Less<Brr, Value<21>>>();
+    }
+}
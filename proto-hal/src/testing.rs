@@ -0,0 +1,893 @@
+//! Host testing support.
+//!
+//! With the `testing` feature, generated register accesses are performed
+//! on a mock memory map composed of [`Memory`] regions (i.e. [`Bank`]s)
+//! rather than on the bus, so HALs can be unit-tested on the host.
+//!
+//! ```ignore
+//! static RCC: Bank<0x100> = Bank::new(rcc::BASE_ADDR);
+//!
+//! #[test]
+//! fn enables_cordic() {
+//!     testing::map(&RCC);
+//!
+//!     enable_cordic();
+//!
+//!     RCC.expect_write::<u32>(rcc::ahb1enr::OFFSET, 1);
+//! }
+//! ```
+//!
+//! *Note: A `critical-section` implementation is required
+//! (i.e. the `std` feature of `critical-section`).*
+//!
+//! *Note: The memory map and injected faults are kept per thread,
+//! so tests running in parallel do not observe each other's regions.
+//! Regions themselves are statics, so tests running in parallel must
+//! not share them (or must be run with `--test-threads=1`).*
+//!
+//! *Note: Accesses to the Cortex-M peripheral bit-band alias region
+//! are performed on the corresponding bit of the mapped memory.*
+//!
+//! Faults can be injected at addresses with [`inject`] to exercise
+//! error handling paths:
+//!
+//! ```ignore
+//! testing::inject(rcc::BASE_ADDR + rcc::cr::OFFSET, Fault::Stuck { mask: 1 << 17, value: 0 });
+//! ```
+//!
+//! Hardware side-effects of writes can be emulated with a [`Simulator`],
+//! which applies [`Rule`]s to its bank as it is accessed:
+//!
+//! ```ignore
+//! // writing START sets BUSY, and after 3 reads BUSY clears and RESULT is 7
+//! static ADC: Simulator<0x100, 1> = Simulator::new(
+//!     adc::BASE_ADDR,
+//!     [Rule::on_write(Bits::field(adc::cr::OFFSET, adc::cr::start::OFFSET, 1, 1))
+//!         .set(&[Bits::field(adc::sr::OFFSET, adc::sr::busy::OFFSET, 1, 1)])
+//!         .after_reads(
+//!             adc::sr::OFFSET,
+//!             3,
+//!             &[
+//!                 Bits::field(adc::sr::OFFSET, adc::sr::busy::OFFSET, 1, 0),
+//!                 Bits::field(adc::dr::OFFSET, 0, 16, 7),
+//!             ],
+//!         )],
+//! );
+//! ```
+//!
+//! Bits which hardware modifies rather than stores when written
+//! (i.e. status flags cleared by writing ones) are described with
+//! [`Effect`]s:
+//!
+//! ```ignore
+//! static TIM: Simulator<0x100, 0> = Simulator::new(tim::BASE_ADDR, [])
+//!     .with_effects(&[Effect::field(tim::sr::OFFSET, tim::sr::uif::OFFSET, 1, WriteEffect::ZeroToClear)]);
+//! ```
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::macro_utils::Raw;
+
+/// A region of the mock memory map.
+pub trait Memory: Sync {
+    /// Read `size` bytes at the provided address, or `None`
+    /// if the address is not within this region.
+    fn read(&self, addr: u32, size: usize) -> Option<u32>;
+
+    /// Write the masked bits of `size` bytes at the provided address,
+    /// returning `false` if the address is not within this region.
+    fn write(&self, addr: u32, size: usize, value: u32, mask: u32) -> bool;
+}
+
+struct BankState<const N: usize> {
+    bytes: [u8; N],
+    /// The value last written to each bit.
+    written: [u8; N],
+    /// The bits which have been written.
+    written_mask: [u8; N],
+}
+
+/// A fake register bank of `N` bytes located at a base address,
+/// recording the values written to it.
+pub struct Bank<const N: usize> {
+    base: u32,
+    state: Mutex<RefCell<BankState<N>>>,
+}
+
+impl<const N: usize> Bank<N> {
+    /// Create a zeroed bank located at the provided address.
+    pub const fn new(base: u32) -> Self {
+        Self {
+            base,
+            state: Mutex::new(RefCell::new(BankState {
+                bytes: [0; N],
+                written: [0; N],
+                written_mask: [0; N],
+            })),
+        }
+    }
+
+    fn offset(&self, addr: u32, size: usize) -> Option<usize> {
+        let offset = addr.checked_sub(self.base)? as usize;
+
+        (offset + size <= N).then_some(offset)
+    }
+
+    /// Set the value of the register at the provided offset
+    /// without recording a write (i.e. hardware status flags).
+    pub fn set<R: Raw>(&self, offset: u32, value: u32) {
+        let size = core::mem::size_of::<R>();
+        let offset = self
+            .base
+            .checked_add(offset)
+            .and_then(|addr| self.offset(addr, size))
+            .expect("offset exceeds bank");
+
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+
+            state.bytes[offset..offset + size].copy_from_slice(&value.to_le_bytes()[..size]);
+        });
+    }
+
+    /// Get the value of the register at the provided offset.
+    pub fn get<R: Raw>(&self, offset: u32) -> u32 {
+        self.base
+            .checked_add(offset)
+            .and_then(|addr| self.read(addr, core::mem::size_of::<R>()))
+            .expect("offset exceeds bank")
+    }
+
+    /// The value last written to each bit of the register at the
+    /// provided offset, or `None` if no bit of it has been written.
+    ///
+    /// *Note: Bits which have not been written read as zero.*
+    pub fn last_write<R: Raw>(&self, offset: u32) -> Option<u32> {
+        let size = core::mem::size_of::<R>();
+        let offset = self
+            .base
+            .checked_add(offset)
+            .and_then(|addr| self.offset(addr, size))
+            .expect("offset exceeds bank");
+
+        critical_section::with(|cs| {
+            let state = self.state.borrow_ref(cs);
+            let (mut written, mut mask) = ([0; 4], [0; 4]);
+
+            written[..size].copy_from_slice(&state.written[offset..offset + size]);
+            mask[..size].copy_from_slice(&state.written_mask[offset..offset + size]);
+
+            (u32::from_le_bytes(mask) != 0).then(|| u32::from_le_bytes(written))
+        })
+    }
+
+    /// Assert the last value written to the register at the
+    /// provided offset is `value`.
+    #[track_caller]
+    pub fn expect_write<R: Raw>(&self, offset: u32, value: u32) {
+        match self.last_write::<R>(offset) {
+            Some(written) => assert_eq!(
+                written, value,
+                "unexpected value written at offset {offset:#x}"
+            ),
+            None => panic!("no value was written at offset {offset:#x}"),
+        }
+    }
+
+    /// Assert no value has been written to the register at the provided offset.
+    #[track_caller]
+    pub fn expect_no_write<R: Raw>(&self, offset: u32) {
+        if let Some(written) = self.last_write::<R>(offset) {
+            panic!("{written:#x} was written at offset {offset:#x}");
+        }
+    }
+
+    /// The offset within the bank and size of the bytes spanned by
+    /// the mask of the register at the provided offset.
+    ///
+    /// Only the bytes spanned by the mask are accessed, so
+    /// registers narrower than 4 bytes are supported.
+    fn span(&self, offset: u32, mask: u32) -> (usize, usize) {
+        let size = (32 - mask.leading_zeros()).div_ceil(8).max(1) as usize;
+        let offset = self
+            .base
+            .checked_add(offset)
+            .and_then(|addr| self.offset(addr, size))
+            .expect("offset exceeds bank");
+
+        (offset, size)
+    }
+
+    /// The masked bits of the register at the provided offset.
+    fn bits(&self, offset: u32, mask: u32) -> u32 {
+        let (offset, size) = self.span(offset, mask);
+
+        critical_section::with(|cs| {
+            let mut bytes = [0; 4];
+
+            bytes[..size].copy_from_slice(&self.state.borrow_ref(cs).bytes[offset..offset + size]);
+
+            u32::from_le_bytes(bytes) & mask
+        })
+    }
+
+    /// Update the masked bits of the register at the provided
+    /// offset without recording a write.
+    fn update(&self, bits: &Bits) {
+        let (offset, size) = self.span(bits.offset, bits.mask);
+
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+            let mut bytes = [0; 4];
+
+            bytes[..size].copy_from_slice(&state.bytes[offset..offset + size]);
+
+            let value = (u32::from_le_bytes(bytes) & !bits.mask) | (bits.value & bits.mask);
+
+            state.bytes[offset..offset + size].copy_from_slice(&value.to_le_bytes()[..size]);
+        });
+    }
+
+    /// Zero the bank and forget all recorded writes.
+    pub fn reset(&self) {
+        critical_section::with(|cs| {
+            let mut state = self.state.borrow_ref_mut(cs);
+
+            state.bytes = [0; N];
+            state.written = [0; N];
+            state.written_mask = [0; N];
+        });
+    }
+}
+
+impl<const N: usize> Memory for Bank<N> {
+    fn read(&self, addr: u32, size: usize) -> Option<u32> {
+        let offset = self.offset(addr, size)?;
+
+        Some(critical_section::with(|cs| {
+            let state = self.state.borrow_ref(cs);
+            let mut bytes = [0; 4];
+
+            bytes[..size].copy_from_slice(&state.bytes[offset..offset + size]);
+
+            u32::from_le_bytes(bytes)
+        }))
+    }
+
+    fn write(&self, addr: u32, size: usize, value: u32, mask: u32) -> bool {
+        let Some(offset) = self.offset(addr, size) else {
+            return false;
+        };
+
+        critical_section::with(|cs| {
+            let state = &mut *self.state.borrow_ref_mut(cs);
+
+            for (i, (value, mask)) in value
+                .to_le_bytes()
+                .into_iter()
+                .zip(mask.to_le_bytes())
+                .take(size)
+                .enumerate()
+            {
+                let merge = |byte: &mut u8| *byte = (*byte & !mask) | (value & mask);
+
+                merge(&mut state.bytes[offset + i]);
+                merge(&mut state.written[offset + i]);
+                state.written_mask[offset + i] |= mask;
+            }
+        });
+
+        true
+    }
+}
+
+/// Masked bits of a register.
+#[derive(Debug, Clone, Copy)]
+pub struct Bits {
+    /// The offset of the register within the bank.
+    pub offset: u32,
+    pub mask: u32,
+    pub value: u32,
+}
+
+impl Bits {
+    pub const fn new(offset: u32, mask: u32, value: u32) -> Self {
+        Self {
+            offset,
+            mask,
+            value,
+        }
+    }
+
+    /// The bits of a field of the provided offset and width
+    /// within the register at `register`.
+    pub const fn field(register: u32, offset: u8, width: u8, value: u32) -> Self {
+        let mask = (u32::MAX >> (32 - width as u32)) << offset;
+
+        Self::new(register, mask, value << offset)
+    }
+}
+
+/// A side-effect applied by a [`Simulator`] when a write matching
+/// the trigger is performed.
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    trigger: Bits,
+    set: &'static [Bits],
+    after: Option<(u32, usize, &'static [Bits])>,
+}
+
+impl Rule {
+    /// Trigger the rule when a write to the register matches
+    /// the masked value.
+    pub const fn on_write(trigger: Bits) -> Self {
+        Self {
+            trigger,
+            set: &[],
+            after: None,
+        }
+    }
+
+    /// Set the provided bits when the rule is triggered.
+    pub const fn set(mut self, bits: &'static [Bits]) -> Self {
+        self.set = bits;
+        self
+    }
+
+    /// Set the provided bits once the register at `offset`
+    /// has been read `reads` times after the rule was triggered.
+    pub const fn after_reads(mut self, offset: u32, reads: usize, bits: &'static [Bits]) -> Self {
+        self.after = Some((offset, reads, bits));
+        self
+    }
+}
+
+/// A modification hardware applies to written bits, rather than
+/// storing them (i.e. `modifiedWriteValues` in SVD).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteEffect {
+    /// Writing ones clears the corresponding bits.
+    OneToClear,
+    /// Writing zeros clears the corresponding bits.
+    ZeroToClear,
+    /// Writing ones sets the corresponding bits.
+    OneToSet,
+    /// Writing zeros sets the corresponding bits.
+    ZeroToSet,
+    /// Writing ones toggles the corresponding bits.
+    OneToToggle,
+    /// Writing zeros toggles the corresponding bits.
+    ZeroToToggle,
+}
+
+impl WriteEffect {
+    /// The value of bits holding `current` once `written` is written to them.
+    const fn apply(self, current: u32, written: u32) -> u32 {
+        match self {
+            Self::OneToClear => current & !written,
+            Self::ZeroToClear => current & written,
+            Self::OneToSet => current | written,
+            Self::ZeroToSet => current | !written,
+            Self::OneToToggle => current ^ written,
+            Self::ZeroToToggle => current ^ !written,
+        }
+    }
+}
+
+/// A [`WriteEffect`] of the masked bits of a register.
+#[derive(Debug, Clone, Copy)]
+pub struct Effect {
+    /// The offset of the register within the bank.
+    pub offset: u32,
+    pub mask: u32,
+    pub effect: WriteEffect,
+}
+
+impl Effect {
+    /// The effect of writing a field of the provided offset
+    /// and width within the register at `register`.
+    pub const fn field(register: u32, offset: u8, width: u8, effect: WriteEffect) -> Self {
+        Self {
+            offset: register,
+            mask: (u32::MAX >> (32 - width as u32)) << offset,
+            effect,
+        }
+    }
+}
+
+/// The written value and mask of a write at `offset`, as seen
+/// by the register at `register` (i.e. for partial writes).
+fn relative(offset: u32, value: u32, mask: u32, register: u32) -> (u32, u32) {
+    let shift = (i64::from(offset) - i64::from(register)) * 8;
+
+    match shift {
+        0..=31 => (value << shift, mask << shift),
+        -31..=-1 => (value >> -shift, mask >> -shift),
+        _ => (0, 0),
+    }
+}
+
+/// A [`Bank`] applying `R` [`Rule`]s and any [`Effect`]s
+/// to itself as it is accessed, emulating the behavior of hardware.
+pub struct Simulator<const N: usize, const R: usize> {
+    bank: Bank<N>,
+    rules: [Rule; R],
+    effects: &'static [Effect],
+    /// The number of reads remaining until the deferred
+    /// bits of each rule are set.
+    pending: Mutex<RefCell<[Option<usize>; R]>>,
+}
+
+impl<const N: usize, const R: usize> Simulator<N, R> {
+    /// Create a simulator of a zeroed bank located at the provided address.
+    pub const fn new(base: u32, rules: [Rule; R]) -> Self {
+        Self {
+            bank: Bank::new(base),
+            rules,
+            effects: &[],
+            pending: Mutex::new(RefCell::new([None; R])),
+        }
+    }
+
+    /// Apply the provided effects to written bits.
+    pub const fn with_effects(mut self, effects: &'static [Effect]) -> Self {
+        assert!(
+            effects.len() <= MAX_EFFECTS,
+            "too many effects are simulated"
+        );
+
+        self.effects = effects;
+        self
+    }
+
+    /// The underlying bank (i.e. to set initial values or inspect writes).
+    pub fn bank(&self) -> &Bank<N> {
+        &self.bank
+    }
+
+    /// Zero the bank and cancel all pending effects.
+    pub fn reset(&self) {
+        self.bank.reset();
+
+        critical_section::with(|cs| {
+            *self.pending.borrow_ref_mut(cs) = [None; R];
+        });
+    }
+}
+
+impl<const N: usize, const R: usize> Memory for Simulator<N, R> {
+    fn read(&self, addr: u32, size: usize) -> Option<u32> {
+        let value = self.bank.read(addr, size)?;
+        let offset = addr - self.bank.base;
+
+        for (i, rule) in self.rules.iter().enumerate() {
+            let Some((poll, _, bits)) = rule.after else {
+                continue;
+            };
+
+            if poll != offset {
+                continue;
+            }
+
+            let elapsed = critical_section::with(|cs| {
+                let mut pending = self.pending.borrow_ref_mut(cs);
+
+                match &mut pending[i] {
+                    Some(remaining) if *remaining > 1 => {
+                        *remaining -= 1;
+                        false
+                    }
+                    Some(_) => {
+                        pending[i] = None;
+                        true
+                    }
+                    None => false,
+                }
+            });
+
+            // the effect is observed by subsequent reads
+            if elapsed {
+                bits.iter().for_each(|bits| self.bank.update(bits));
+            }
+        }
+
+        Some(value)
+    }
+
+    fn write(&self, addr: u32, size: usize, value: u32, mask: u32) -> bool {
+        if self.bank.offset(addr, size).is_none() {
+            return false;
+        }
+
+        let offset = addr - self.bank.base;
+        let mask = mask & (u32::MAX >> (32 - 8 * size as u32));
+
+        // the affected bits hold their prior value until the write is complete
+        let mut affected = [None; MAX_EFFECTS];
+
+        for (slot, effect) in affected.iter_mut().zip(self.effects) {
+            let (written, mask) = relative(offset, value, mask, effect.offset);
+            let mask = mask & effect.mask;
+
+            if mask != 0 {
+                let current = self.bank.bits(effect.offset, mask);
+
+                slot.replace(Bits::new(
+                    effect.offset,
+                    mask,
+                    effect.effect.apply(current, written),
+                ));
+            }
+        }
+
+        self.bank.write(addr, size, value, mask);
+
+        affected
+            .iter()
+            .flatten()
+            .for_each(|bits| self.bank.update(bits));
+
+        for (i, rule) in self.rules.iter().enumerate() {
+            let (written, mask) = relative(offset, value, mask, rule.trigger.offset);
+
+            // every bit of the trigger must be written
+            if rule.trigger.mask & !mask != 0 || written & rule.trigger.mask != rule.trigger.value {
+                continue;
+            }
+
+            rule.set.iter().for_each(|bits| self.bank.update(bits));
+
+            if let Some((.., reads, bits)) = rule.after {
+                if reads == 0 {
+                    bits.iter().for_each(|bits| self.bank.update(bits));
+                } else {
+                    critical_section::with(|cs| {
+                        self.pending.borrow_ref_mut(cs)[i] = Some(reads);
+                    });
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// The maximum number of effects simulated by a [`Simulator`].
+const MAX_EFFECTS: usize = 32;
+
+/// The maximum number of regions in the mock memory map.
+const MAX_REGIONS: usize = 32;
+
+std::thread_local! {
+    /// The mock memory map of the current thread.
+    static MAP: RefCell<[Option<&'static dyn Memory>; MAX_REGIONS]> =
+        const { RefCell::new([None; MAX_REGIONS]) };
+}
+
+/// Add a region to the mock memory map of the current thread.
+///
+/// *Note: Mapping a region which is already mapped has no effect.*
+pub fn map(memory: &'static dyn Memory) {
+    MAP.with_borrow_mut(|map| {
+        if map
+            .iter()
+            .flatten()
+            .any(|mapped| core::ptr::addr_eq(*mapped, memory))
+        {
+            return;
+        }
+
+        let slot = map
+            .iter_mut()
+            .find(|slot| slot.is_none())
+            .expect("mock memory map is full");
+
+        slot.replace(memory);
+    });
+}
+
+/// Remove all regions from the mock memory map of the current thread.
+pub fn unmap_all() {
+    MAP.with_borrow_mut(|map| *map = [None; MAX_REGIONS]);
+}
+
+/// A fault injected at an address of the mock memory map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Accesses panic, as a bus fault would halt the program.
+    Bus,
+    /// Reads yield the stored value with the masked bits flipped.
+    Corrupt { mask: u32 },
+    /// The masked bits always hold `value`, regardless of writes.
+    Stuck { mask: u32, value: u32 },
+}
+
+/// The maximum number of faults injected at once.
+const MAX_FAULTS: usize = 16;
+
+/// The injected faults, by address.
+type Faults = [Option<(u32, Fault)>; MAX_FAULTS];
+
+std::thread_local! {
+    /// The faults injected in the current thread.
+    static FAULTS: RefCell<Faults> = const { RefCell::new([None; MAX_FAULTS]) };
+}
+
+/// Inject a fault at the provided address, replacing any
+/// fault already injected there.
+///
+/// *Note: The fault is only observed by accesses
+/// performed by the current thread.*
+pub fn inject(addr: u32, fault: Fault) {
+    FAULTS.with_borrow_mut(|faults| {
+        let slot = match faults
+            .iter()
+            .position(|slot| slot.is_some_and(|(faulty, _)| faulty == addr))
+        {
+            Some(i) => &mut faults[i],
+            None => faults
+                .iter_mut()
+                .find(|slot| slot.is_none())
+                .expect("too many faults are injected"),
+        };
+
+        slot.replace((addr, fault));
+    });
+}
+
+/// Remove all faults injected in the current thread.
+pub fn clear_faults() {
+    FAULTS.with_borrow_mut(|faults| *faults = [None; MAX_FAULTS]);
+}
+
+fn fault_at(addr: u32) -> Option<Fault> {
+    FAULTS.with_borrow(|faults| {
+        faults
+            .iter()
+            .flatten()
+            .find_map(|(faulty, fault)| (*faulty == addr).then_some(*fault))
+    })
+}
+
+const BIT_BAND_ALIAS: core::ops::Range<u32> = 0x4200_0000..0x4400_0000;
+
+/// Decode a bit-band alias address into the byte address and bit it aliases.
+fn bit_band_target(addr: u32) -> Option<(u32, u8)> {
+    BIT_BAND_ALIAS.contains(&addr).then(|| {
+        let offset = addr - BIT_BAND_ALIAS.start;
+
+        (0x4000_0000 + offset / 32, ((offset % 32) / 4) as u8)
+    })
+}
+
+fn read_mapped(addr: u32, size: usize) -> u32 {
+    let map = MAP.with_borrow(|map| *map);
+
+    map.iter()
+        .flatten()
+        .find_map(|memory| memory.read(addr, size))
+        .unwrap_or_else(|| panic!("read from unmapped address {addr:#010x}"))
+}
+
+fn write_mapped(addr: u32, size: usize, value: u32, mask: u32) {
+    let map = MAP.with_borrow(|map| *map);
+
+    if !map
+        .iter()
+        .flatten()
+        .any(|memory| memory.write(addr, size, value, mask))
+    {
+        panic!("write of {value:#x} to unmapped address {addr:#010x}");
+    }
+}
+
+#[doc(hidden)]
+pub fn read(addr: u32, size: usize) -> u32 {
+    let value = match bit_band_target(addr) {
+        Some((addr, bit)) => (read_mapped(addr, 1) >> bit) & 1,
+        None => read_mapped(addr, size),
+    };
+
+    match fault_at(addr) {
+        Some(Fault::Bus) => panic!("bus fault reading {addr:#010x}"),
+        Some(Fault::Corrupt { mask }) => value ^ mask,
+        Some(Fault::Stuck { mask, value: stuck }) => (value & !mask) | (stuck & mask),
+        None => value,
+    }
+}
+
+#[doc(hidden)]
+pub fn write(addr: u32, size: usize, value: u32) {
+    let value = match fault_at(addr) {
+        Some(Fault::Bus) => panic!("bus fault writing {value:#x} to {addr:#010x}"),
+        Some(Fault::Stuck { mask, value: stuck }) => (value & !mask) | (stuck & mask),
+        Some(Fault::Corrupt { .. }) | None => value,
+    };
+
+    match bit_band_target(addr) {
+        // only the aliased bit is written
+        Some((addr, bit)) => write_mapped(addr, 1, (value & 1) << bit, 1 << bit),
+        None => write_mapped(addr, size, value, u32::MAX),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bank, Bits, Effect, Fault, Memory, Rule, Simulator, WriteEffect};
+
+    const BASE: u32 = 0x2000_0000;
+
+    #[test]
+    fn records_written_bits() {
+        let bank = Bank::<8>::new(BASE);
+
+        bank.write(BASE, 4, 0x1234_5678, u32::MAX);
+        bank.write(BASE + 2, 1, 0xab, u32::MAX);
+
+        assert_eq!(bank.last_write::<u32>(0), Some(0x12ab_5678));
+        assert_eq!(bank.last_write::<u8>(1), Some(0x56));
+        assert_eq!(bank.last_write::<u32>(4), None);
+
+        // setting is not a write
+        bank.set::<u32>(4, 1);
+
+        assert_eq!(bank.get::<u32>(4), 1);
+        bank.expect_no_write::<u32>(4);
+    }
+
+    #[test]
+    fn records_bit_band_bit() {
+        static BANK: Bank<4> = Bank::new(0x4000_0000);
+
+        super::map(&BANK);
+        BANK.set::<u32>(0, 0xf0);
+
+        // bit 3 of byte 1
+        super::write(0x4200_0000 + 32 + 3 * 4, 4, 1);
+
+        assert_eq!(BANK.get::<u32>(0), 0x8f0);
+        BANK.expect_write::<u32>(0, 0x800);
+        assert_eq!(super::read(0x4200_0000 + 32 + 3 * 4, 4), 1);
+
+        super::unmap_all();
+    }
+
+    #[test]
+    fn write_effects() {
+        static EFFECTS: [Effect; 6] = [
+            Effect::field(0, 0, 4, WriteEffect::OneToClear),
+            Effect::field(0, 0, 4, WriteEffect::ZeroToClear),
+            Effect::field(0, 0, 4, WriteEffect::OneToSet),
+            Effect::field(0, 0, 4, WriteEffect::ZeroToSet),
+            Effect::field(0, 0, 4, WriteEffect::OneToToggle),
+            Effect::field(0, 0, 4, WriteEffect::ZeroToToggle),
+        ];
+
+        // the field holds 0b0011 when 0b0101 is written
+        for (effect, expected) in EFFECTS
+            .iter()
+            .zip([0b0010, 0b0001, 0b0111, 0b1011, 0b0110, 0b1001])
+        {
+            let simulator =
+                Simulator::<4, 0>::new(BASE, []).with_effects(core::slice::from_ref(effect));
+
+            simulator.bank().set::<u32>(0, 0b0011);
+            simulator.write(BASE, 4, 0xa5, u32::MAX);
+
+            // bits without effects are stored as written
+            assert_eq!(
+                simulator.bank().get::<u32>(0),
+                0xa0 | expected,
+                "{effect:?}"
+            );
+            assert_eq!(simulator.bank().last_write::<u32>(0), Some(0xa5));
+
+            // writes not reaching the field have no effect
+            simulator.write(BASE + 1, 1, 0, u32::MAX);
+
+            assert_eq!(
+                simulator.bank().get::<u32>(0),
+                0xa0 | expected,
+                "{effect:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rules() {
+        const START: Bits = Bits::field(0, 0, 1, 1);
+        const BUSY: Bits = Bits::field(4, 0, 1, 1);
+        const IDLE: Bits = Bits::field(4, 0, 1, 0);
+        const RESULT: Bits = Bits::field(8, 0, 16, 7);
+
+        let simulator = Simulator::<12, 1>::new(
+            BASE,
+            [Rule::on_write(START)
+                .set(&[BUSY])
+                .after_reads(4, 2, &[IDLE, RESULT])],
+        );
+
+        simulator.write(BASE, 4, 0, u32::MAX);
+        assert_eq!(simulator.bank().get::<u32>(4), 0);
+
+        simulator.write(BASE, 4, 1, u32::MAX);
+        assert_eq!(simulator.read(BASE + 4, 4), Some(1));
+        assert_eq!(simulator.read(BASE + 4, 4), Some(1));
+        assert_eq!(simulator.read(BASE + 4, 4), Some(0));
+        assert_eq!(simulator.read(BASE + 8, 4), Some(7));
+    }
+
+    #[test]
+    fn corrupt_fault() {
+        static BANK: Bank<4> = Bank::new(0x3000_0000);
+
+        super::map(&BANK);
+        super::inject(0x3000_0000, Fault::Corrupt { mask: 0b11 });
+
+        super::write(0x3000_0000, 4, 0b0101);
+
+        // writes are unaffected, reads are corrupted
+        BANK.expect_write::<u32>(0, 0b0101);
+        assert_eq!(super::read(0x3000_0000, 4), 0b0110);
+
+        super::clear_faults();
+        assert_eq!(super::read(0x3000_0000, 4), 0b0101);
+
+        super::unmap_all();
+    }
+
+    #[test]
+    fn stuck_fault() {
+        static BANK: Bank<4> = Bank::new(0x3000_1000);
+
+        super::map(&BANK);
+        BANK.set::<u32>(0, 0b1000);
+        super::inject(
+            0x3000_1000,
+            Fault::Stuck {
+                mask: 0b1001,
+                value: 0b0001,
+            },
+        );
+
+        // the stuck bits hold their value when written and read
+        super::write(0x3000_1000, 4, 0b1110);
+
+        assert_eq!(BANK.get::<u32>(0), 0b0111);
+        BANK.set::<u32>(0, 0b1000);
+        assert_eq!(super::read(0x3000_1000, 4), 0b0001);
+
+        super::clear_faults();
+        super::unmap_all();
+    }
+
+    #[test]
+    #[should_panic(expected = "bus fault reading")]
+    fn bus_fault() {
+        static BANK: Bank<4> = Bank::new(0x3000_2000);
+
+        super::map(&BANK);
+        super::inject(0x3000_2000, Fault::Bus);
+
+        super::read(0x3000_2000, 4);
+    }
+
+    #[test]
+    fn faults_are_per_address() {
+        static BANK: Bank<8> = Bank::new(0x3000_3000);
+
+        super::map(&BANK);
+        super::inject(0x3000_3000, Fault::Bus);
+        super::inject(0x3000_3000, Fault::Corrupt { mask: 1 });
+
+        // the latest fault replaces the prior, and
+        // other addresses are unaffected
+        assert_eq!(super::read(0x3000_3000, 4), 1);
+        assert_eq!(super::read(0x3000_3004, 4), 0);
+
+        super::clear_faults();
+        super::unmap_all();
+    }
+}
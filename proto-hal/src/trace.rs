@@ -0,0 +1,79 @@
+//! Tracing of the register accesses performed by generated code.
+//!
+//! When the `trace` feature is enabled, every volatile read and write
+//! performed by generated register interfaces is reported to the
+//! installed [`Tracer`] (if any).
+
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// The kind of register access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AccessKind {
+    Read,
+    Write,
+}
+
+/// A register access performed by generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Access {
+    /// Whether the register was read or written.
+    pub kind: AccessKind,
+    /// The module path of the register (i.e. `my_hal::cordic::csr`).
+    pub path: &'static str,
+    /// The offset of the register within its block.
+    pub offset: u32,
+    /// The address accessed.
+    ///
+    /// *Note: This may differ from the address of the register
+    /// when aliases (set/clear registers, bit-banding) are used.*
+    pub addr: u32,
+    /// The value read or written.
+    pub value: u32,
+}
+
+impl Access {
+    /// The module path of the block containing the register.
+    pub fn peripheral(&self) -> &'static str {
+        self.path
+            .rsplit_once("::")
+            .map_or(self.path, |(peripheral, _)| peripheral)
+    }
+
+    /// The name of the register.
+    pub fn register(&self) -> &'static str {
+        self.path
+            .rsplit_once("::")
+            .map_or(self.path, |(_, register)| register)
+    }
+}
+
+/// A function invoked with every register access.
+pub type Tracer = fn(&Access);
+
+static TRACER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Install a tracer, replacing the previous one.
+pub fn set_tracer(tracer: Tracer) {
+    TRACER.store(tracer as *mut (), Ordering::Release);
+}
+
+/// Remove the installed tracer (if any).
+pub fn clear_tracer() {
+    TRACER.store(core::ptr::null_mut(), Ordering::Release);
+}
+
+/// Report an access to the installed tracer (if any).
+#[doc(hidden)]
+pub fn record(access: Access) {
+    let tracer = TRACER.load(Ordering::Acquire);
+
+    if !tracer.is_null() {
+        // SAFETY: the only non-null values stored
+        // are `Tracer` pointers (see `set_tracer`)
+        let tracer = unsafe { core::mem::transmute::<*mut (), Tracer>(tracer) };
+
+        tracer(&access);
+    }
+}
@@ -0,0 +1,11 @@
+//! Compile tests of the interfaces generated by the macros.
+//!
+//! Passing tests are also run, against simulated memory.
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
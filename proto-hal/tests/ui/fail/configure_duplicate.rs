@@ -0,0 +1,23 @@
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod gpio {
+    #[register(offset = 0)]
+    mod moder {
+        #[field_array(offset = 0, range = 0..4, width = 2, read(), write(), reset = Input)]
+        mod modeX {
+            #[variant(bits = 0)]
+            struct Input;
+            #[variant(bits = 1)]
+            struct Output;
+        }
+    }
+}
+
+fn main() {
+    let gpio = unsafe { gpio::Reset::conjure() };
+
+    // `mode2` is selected by both entries
+    let _ = proto_hal::configure! {
+        gpio.moder.modeX[0..3] => Output,
+        gpio.moder.mode2 => Input,
+    };
+}
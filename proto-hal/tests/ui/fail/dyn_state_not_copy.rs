@@ -0,0 +1,25 @@
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod foo {
+    #[register(offset = 0)]
+    mod cr {
+        #[field(offset = 0, width = 1, read(), write(), reset = Off)]
+        mod en {
+            #[variant(bits = 0)]
+            struct Off;
+            #[variant(bits = 1)]
+            struct On;
+        }
+    }
+}
+
+fn main() {
+    let foo = unsafe { foo::Reset::conjure() };
+
+    let dyn_state = foo::cr::DynState::from(foo.cr);
+    let values = dyn_state.values();
+
+    // the dynamic state owns the register, so
+    // applying it twice would duplicate it
+    let _ = unsafe { dyn_state.apply(values) };
+    let _ = unsafe { dyn_state.apply(values) };
+}
@@ -0,0 +1,13 @@
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod foo {
+    #[register(offset = 0)]
+    mod isr {
+        #[field(offset = 0, width = 1, read(effect(clear)), flag)]
+        mod ovr {}
+    }
+}
+
+fn main() {
+    // reading the flag clears it
+    let _ = foo::isr::is_ovr_set();
+}
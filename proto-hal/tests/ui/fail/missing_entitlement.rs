@@ -0,0 +1,32 @@
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod flash {
+    #[register(offset = 0, sequences(unlock = [key::Key1, key::Key2]))]
+    mod keyr {
+        #[field(offset = 0, width = 8, write())]
+        mod key {
+            #[variant(bits = 0x45)]
+            struct Key1;
+            #[variant(bits = 0xcd)]
+            struct Key2;
+        }
+    }
+
+    #[register(offset = 4)]
+    mod cr {
+        #[field(offset = 0, width = 8, by_value, read(), write(entitlements = [super::keyr::Unlock]))]
+        mod psize {}
+    }
+}
+
+struct Locked;
+
+impl proto_hal::stasis::Freeze for Locked {}
+
+fn main() {
+    let flash = unsafe { flash::Reset::conjure() };
+
+    // only the token of the `unlock` sequence entitles the write
+    let locked = proto_hal::stasis::Entitlement::from(Locked);
+
+    flash.cr.write(|w| w.psize(&locked, 2));
+}
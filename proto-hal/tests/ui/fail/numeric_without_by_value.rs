@@ -0,0 +1,10 @@
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod foo {
+    #[register(offset = 0)]
+    mod cr {
+        #[field(offset = 0, width = 8, read(), write())]
+        mod data {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod flash {
+    // collides with the writer of the register
+    #[register(offset = 0, sequences(write = [key::Key1, key::Key2]))]
+    mod keyr {
+        #[field(offset = 0, width = 8, write())]
+        mod key {
+            #[variant(bits = 0x45)]
+            struct Key1;
+            #[variant(bits = 0xcd)]
+            struct Key2;
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,75 @@
+use proto_hal::{
+    stasis::Entitlement,
+    testing::{self, Bank},
+};
+
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod foo {
+    #[register(offset = 0)]
+    mod cr {
+        #[field(offset = 0, width = 1, read(), write(), reset = Off)]
+        mod en {
+            #[variant(bits = 0)]
+            struct Off;
+            #[variant(bits = 1)]
+            struct On;
+        }
+
+        #[field(offset = 4, width = 8, by_value, read(), write())]
+        mod data {}
+    }
+}
+
+#[proto_hal::macros::block(base_addr = 0x4000_0100)]
+mod bar {
+    #[register(offset = 0)]
+    mod ctl {
+        #[field(offset = 0, width = 8, by_value, read(), write(entitlements = [super::super::foo::cr::en::On]))]
+        mod val {}
+    }
+
+    #[register(offset = 4)]
+    mod moder {
+        #[field_array(offset = 0, range = 0..4, width = 2, read(), write(), reset = Input)]
+        mod modeX {
+            #[variant(bits = 0)]
+            struct Input;
+            #[variant(bits = 1)]
+            struct Output;
+        }
+    }
+}
+
+static MEMORY: Bank<0x200> = Bank::new(0x4000_0000);
+
+fn main() {
+    testing::map(&MEMORY);
+
+    let foo = unsafe { foo::Reset::conjure() };
+    let bar = unsafe { bar::Reset::conjure() };
+
+    let on: Entitlement<foo::cr::en::On> =
+        unsafe { <foo::cr::en::On as foo::cr::en::State>::conjure() }.into();
+
+    // `ctl` depends on the state of `cr`, so it is configured
+    // after it, but yielded in order of appearance
+    let (ctl, cr, moder) = proto_hal::configure! {
+        bar.ctl.val(&on, 3),
+        foo.cr.en => On,
+        (foo.cr).data(5),
+        bar.moder.modeX[1..3] => Output,
+    };
+
+    let _: bar::ctl::Register = ctl;
+    let _: foo::cr::Register<foo::cr::en::On> = cr;
+    let _: bar::moder::Register<
+        bar::moder::mode0::Input,
+        bar::moder::mode1::Output,
+        bar::moder::mode2::Output,
+        bar::moder::mode3::Input,
+    > = moder;
+
+    assert_eq!(MEMORY.get::<u32>(0x000), 5 << 4 | 1);
+    assert_eq!(MEMORY.get::<u32>(0x100), 3);
+    assert_eq!(MEMORY.get::<u32>(0x104), 0b01_01 << 2);
+}
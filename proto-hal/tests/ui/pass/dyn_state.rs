@@ -0,0 +1,42 @@
+use proto_hal::testing::{self, Bank};
+
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod foo {
+    #[register(offset = 0)]
+    mod cr {
+        #[field(offset = 0, width = 1, read(), write(), reset = Off)]
+        mod en {
+            #[variant(bits = 0)]
+            struct Off;
+            #[variant(bits = 1)]
+            struct On;
+        }
+    }
+}
+
+static MEMORY: Bank<0x10> = Bank::new(0x4000_0000);
+
+fn main() {
+    testing::map(&MEMORY);
+
+    let foo = unsafe { foo::Reset::conjure() };
+
+    let dyn_state = foo::cr::DynState::from(foo.cr);
+    let mut values = dyn_state.values();
+    values.en = foo::cr::en::Variant::On;
+
+    // SAFETY: no other gate depends on the state of `en`
+    let dyn_state = unsafe { dyn_state.apply(values) };
+    assert_eq!(MEMORY.get::<u32>(0), 1);
+
+    // the state held does not match
+    let Err(dyn_state) = dyn_state.into_typed::<foo::cr::en::Off>() else {
+        panic!("typed with a state not held");
+    };
+
+    let Ok(cr) = dyn_state.into_typed::<foo::cr::en::On>() else {
+        panic!("failed to type with the state held");
+    };
+
+    let _: foo::cr::Register<foo::cr::en::On> = cr;
+}
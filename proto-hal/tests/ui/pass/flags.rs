@@ -0,0 +1,42 @@
+use proto_hal::testing::{self, Bank};
+
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod foo {
+    #[register(offset = 0)]
+    mod isr {
+        #[field(offset = 0, width = 1, read(), flag)]
+        mod tc {}
+
+        #[field(offset = 1, width = 1, read(effect(clear)), flag)]
+        mod ovr {}
+    }
+
+    #[register(offset = 4)]
+    mod icr {
+        #[field(offset = 0, width = 1, write(effect(one_to_clear)), flag)]
+        mod tcc {}
+    }
+}
+
+#[proto_hal::macros::interrupts(vector_table)]
+enum Interrupt {
+    #[source(status = foo::isr::tc, clear = foo::icr::tcc)]
+    Foo = 0,
+}
+
+static MEMORY: Bank<0x10> = Bank::new(0x4000_0000);
+
+fn main() {
+    testing::map(&MEMORY);
+
+    MEMORY.set::<u32>(0, 0b11);
+
+    assert!(foo::isr::is_tc_set());
+    // SAFETY: the flag is not relied upon elsewhere
+    assert!(unsafe { foo::isr::is_ovr_set() });
+
+    assert!(is_foo_pending());
+    // SAFETY: the interrupt is not being handled
+    unsafe { clear_foo_pending() };
+    assert_eq!(MEMORY.get::<u32>(4), 1);
+}
@@ -0,0 +1,43 @@
+use proto_hal::testing::{self, Bank};
+
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod flash {
+    #[register(offset = 0, sequences(unlock(steps = [key::Key1, key::Key2], relock = [key::Lock])))]
+    mod keyr {
+        #[field(offset = 0, width = 8, write())]
+        mod key {
+            #[variant(bits = 0x45)]
+            struct Key1;
+            #[variant(bits = 0xcd)]
+            struct Key2;
+            #[variant(bits = 0)]
+            struct Lock;
+        }
+    }
+
+    #[register(offset = 4)]
+    mod cr {
+        #[field(offset = 0, width = 8, by_value, read(), write(entitlements = [super::keyr::Unlock]))]
+        mod psize {}
+    }
+}
+
+static MEMORY: Bank<0x10> = Bank::new(0x4000_0000);
+
+fn main() {
+    testing::map(&MEMORY);
+
+    let flash = unsafe { flash::Reset::conjure() };
+
+    let unlock = flash.keyr.unlock();
+    assert_eq!(MEMORY.get::<u32>(0), 0xcd);
+
+    let (unlock, [entitlement]) = proto_hal::stasis::Freeze::freeze(unlock);
+    flash.cr.write(|w| w.psize(&entitlement, 2));
+    assert_eq!(MEMORY.get::<u32>(4), 2);
+
+    let keyr = unlock.release([entitlement]).relock();
+    assert_eq!(MEMORY.get::<u32>(0), 0);
+
+    let _: flash::keyr::Register = keyr;
+}
@@ -0,0 +1,45 @@
+use proto_hal::testing::{self, Bank};
+
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod rcc {
+    #[register(offset = 0)]
+    mod pllcfgr {
+        #[field(offset = 0, width = 1, read(), write(), reset = Hsi)]
+        mod pllsrc {
+            #[variant(bits = 0)]
+            struct Hsi;
+            #[variant(bits = 1)]
+            struct Hse;
+        }
+    }
+
+    #[register(offset = 4)]
+    mod cfgr {
+        #[field(offset = 0, width = 1, read(), write(), reset = Hsi)]
+        mod sw {
+            #[variant(bits = 0)]
+            struct Hsi;
+            #[variant(bits = 1)]
+            struct Pll;
+        }
+    }
+}
+
+static MEMORY: Bank<0x10> = Bank::new(0x4000_0000);
+
+fn main() {
+    testing::map(&MEMORY);
+
+    let rcc = unsafe { rcc::Reset::conjure() };
+
+    let (pllcfgr, cfgr) = proto_hal::transaction! {
+        pllcfgr = rcc.pllcfgr => |b| b.pllsrc().hse(),
+        cfgr = rcc.cfgr => |b| b.sw().pll(),
+    };
+
+    let _: rcc::pllcfgr::Register<rcc::pllcfgr::pllsrc::Hse> = pllcfgr;
+    let _: rcc::cfgr::Register<rcc::cfgr::sw::Pll> = cfgr;
+
+    assert_eq!(MEMORY.get::<u32>(0), 1);
+    assert_eq!(MEMORY.get::<u32>(4), 1);
+}
@@ -0,0 +1,53 @@
+use proto_hal::testing::{self, Bank};
+
+#[proto_hal::macros::block(base_addr = 0x4000_0000)]
+mod tim {
+    #[register(offset = 0)]
+    mod ccmr {
+        #[field(offset = 0, width = 1, read(), write(), reset = Output)]
+        mod ccs {
+            #[variant(bits = 0)]
+            struct Output;
+            #[variant(bits = 1)]
+            struct Input;
+        }
+
+        // the same bits are interpreted per mode
+        #[field(offset = 4, width = 1, read(entitlements = [ccs::Output]), write(entitlements = [ccs::Output]), reset = Frozen)]
+        mod ocm {
+            #[variant(bits = 0)]
+            struct Frozen;
+            #[variant(bits = 1)]
+            struct Active;
+        }
+
+        #[field(offset = 4, width = 1, read(entitlements = [ccs::Input]), write(entitlements = [ccs::Input]), reset = NoFilter)]
+        mod icf {
+            #[variant(bits = 0)]
+            struct NoFilter;
+            #[variant(bits = 1)]
+            struct Filtered;
+        }
+    }
+}
+
+static MEMORY: Bank<0x10> = Bank::new(0x4000_0000);
+
+fn main() {
+    testing::map(&MEMORY);
+
+    let tim = unsafe { tim::Reset::conjure() };
+
+    let ccmr = tim.ccmr.transition(|b| b.ocm().active());
+    assert_eq!(MEMORY.get::<u32>(0), 1 << 4);
+
+    // only the view selected by `ccs` is written
+    let ccmr = ccmr.transition(|b| b.ccs().input().icf().filtered());
+    assert_eq!(MEMORY.get::<u32>(0), 1 << 4 | 1);
+
+    let _: tim::ccmr::Register<
+        tim::ccmr::ccs::Input,
+        tim::ccmr::ocm::Active,
+        tim::ccmr::icf::Filtered,
+    > = ccmr;
+}